@@ -32,6 +32,7 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
             extensions: vec![
                 "GL_APPLE_vertex_array_object".to_string(),
                 "GL_ARB_buffer_storage".to_string(),
+                "GL_ARB_clear_buffer_object".to_string(),
                 "GL_ARB_compute_shader".to_string(),
                 "GL_ARB_copy_buffer".to_string(),
                 "GL_ARB_debug_output".to_string(),
@@ -40,20 +41,25 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
                 "GL_ARB_ES2_compatibility".to_string(),
                 "GL_ARB_ES3_compatibility".to_string(),
                 "GL_ARB_ES3_1_compatibility".to_string(),
+                "GL_ARB_framebuffer_no_attachments".to_string(),
                 "GL_ARB_framebuffer_sRGB".to_string(),
                 "GL_ARB_geometry_shader4".to_string(),
                 "GL_ARB_invalidate_subdata".to_string(),
                 "GL_ARB_multi_draw_indirect".to_string(),
                 "GL_ARB_occlusion_query".to_string(),
                 "GL_ARB_pixel_buffer_object".to_string(),
+                "GL_ARB_query_buffer_object".to_string(),
                 "GL_ARB_robustness".to_string(),
+                "GL_ARB_shader_atomic_counters".to_string(),
                 "GL_ARB_shader_image_load_store".to_string(),
                 "GL_ARB_shader_objects".to_string(),
                 "GL_ARB_texture_float".to_string(),
                 "GL_ARB_texture_multisample".to_string(),
                 "GL_ARB_texture_rg".to_string(),
                 "GL_ARB_texture_rgb10_a2ui".to_string(),
+                "GL_ARB_transform_feedback2".to_string(),
                 "GL_ARB_transform_feedback3".to_string(),
+                "GL_ARB_transform_feedback_instanced".to_string(),
                 "GL_ARB_vertex_buffer_object".to_string(),
                 "GL_ARB_vertex_shader".to_string(),
                 "GL_ATI_meminfo".to_string(),
@@ -90,6 +96,7 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
             api: gl_generator::registry::Ns::Gles2.to_string(),
             extensions: vec![
                 "GL_ARM_rgba8".to_string(),
+                "GL_EXT_discard_framebuffer".to_string(),
                 "GL_EXT_disjoint_timer_query".to_string(),
                 "GL_EXT_multi_draw_indirect".to_string(),
                 "GL_EXT_occlusion_query_boolean".to_string(),
@@ -98,6 +105,7 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
                 "GL_NV_copy_buffer".to_string(),
                 "GL_NV_pixel_buffer_object".to_string(),
                 "GL_OES_depth_texture".to_string(),
+                "GL_OES_geometry_shader".to_string(),
                 "GL_OES_packed_depth_stencil".to_string(),
                 "GL_OES_rgb8_rgba8".to_string(),
                 "GL_OES_texture_npot".to_string(),
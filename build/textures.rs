@@ -421,52 +421,71 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     }
 
     // `ToXXXAttachment` trait impl
-    if dimensions == TextureDimensions::Texture2d || dimensions == TextureDimensions::Texture2dMultisample {
+    // array textures and 3D textures are attached with all their layers
+    let attachment_variant = match dimensions {
+        TextureDimensions::Texture2d | TextureDimensions::Texture2dMultisample => Some("Texture"),
+        TextureDimensions::Texture3d | TextureDimensions::Texture1dArray |
+        TextureDimensions::Texture2dArray | TextureDimensions::Texture2dMultisampleArray => {
+            Some("LayeredTexture")
+        },
+        TextureDimensions::Texture1d => None,
+    };
+
+    if let Some(variant) = attachment_variant {
         match ty {
             TextureType::Regular => {
                 (writeln!(dest, "
                         impl ::framebuffer::ToColorAttachment for {name} {{
                             fn to_color_attachment(&self) -> ::framebuffer::ColorAttachment {{
-                                ::framebuffer::ColorAttachment::Texture(self.0.mipmap(0, 0).unwrap())
+                                ::framebuffer::ColorAttachment::{variant}(self.0.mipmap(0, 0).unwrap())
                             }}
                         }}
-                    ", name = name)).unwrap();
+                    ", name = name, variant = variant)).unwrap();
+            },
+            TextureType::Integral | TextureType::Unsigned => {
+                (writeln!(dest, "
+                        impl ::framebuffer::ToColorAttachment for {name} {{
+                            fn to_color_attachment(&self) -> ::framebuffer::ColorAttachment {{
+                                ::framebuffer::ColorAttachment::{variant}(self.0.mipmap(0, 0).unwrap())
+                            }}
+                        }}
+                    ", name = name, variant = variant)).unwrap();
             },
             TextureType::Srgb => {
                 (writeln!(dest, "
                         impl ::framebuffer::ToColorAttachment for {name} {{
                             fn to_color_attachment(&self) -> ::framebuffer::ColorAttachment {{
-                                ::framebuffer::ColorAttachment::Texture(self.0.mipmap(0, 0).unwrap())
+                                ::framebuffer::ColorAttachment::{variant}(self.0.mipmap(0, 0).unwrap())
                             }}
                         }}
-                    ", name = name)).unwrap();
+                    ", name = name, variant = variant)).unwrap();
             },
             TextureType::Depth => {
                 (writeln!(dest, "
                         impl ::framebuffer::ToDepthAttachment for {name} {{
                             fn to_depth_attachment(&self) -> ::framebuffer::DepthAttachment {{
-                                ::framebuffer::DepthAttachment::Texture(self.0.mipmap(0, 0).unwrap())
+                                ::framebuffer::DepthAttachment::{variant}(self.0.mipmap(0, 0).unwrap())
                             }}
                         }}
-                    ", name = name)).unwrap();
+                    ", name = name, variant = variant)).unwrap();
             },
             TextureType::Stencil => {
                 (writeln!(dest, "
                         impl ::framebuffer::ToStencilAttachment for {name} {{
                             fn to_stencil_attachment(&self) -> ::framebuffer::StencilAttachment {{
-                                ::framebuffer::StencilAttachment::Texture(self.0.mipmap(0, 0).unwrap())
+                                ::framebuffer::StencilAttachment::{variant}(self.0.mipmap(0, 0).unwrap())
                             }}
                         }}
-                    ", name = name)).unwrap();
+                    ", name = name, variant = variant)).unwrap();
             },
             TextureType::DepthStencil => {
                 (writeln!(dest, "
                         impl ::framebuffer::ToDepthStencilAttachment for {name} {{
                             fn to_depth_stencil_attachment(&self) -> ::framebuffer::DepthStencilAttachment {{
-                                ::framebuffer::DepthStencilAttachment::Texture(self.0.mipmap(0, 0).unwrap())
+                                ::framebuffer::DepthStencilAttachment::{variant}(self.0.mipmap(0, 0).unwrap())
                             }}
                         }}
-                    ", name = name)).unwrap();
+                    ", name = name, variant = variant)).unwrap();
             },
             _ => ()
         }
@@ -963,7 +982,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 /// FBO and re-use it. When the texture is destroyed, the FBO is destroyed too.
                 ///
                 pub fn as_surface<'a>(&'a self) -> TextureSurface<'a> {{
-                    TextureSurface(framebuffer::SimpleFrameBuffer::new(self.0.get_context(), self))
+                    TextureSurface(framebuffer::SimpleFrameBuffer::new(self.0.get_context(), self).unwrap())
                 }}
             ")).unwrap();
     }
@@ -1345,6 +1364,13 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 pub fn get_level(&self) -> u32 {{
                     self.0.get_level()
                 }}
+
+                /// Tells the backend that the content of this texture level is no longer needed.
+                ///
+                /// See `TextureAnyMipmap::invalidate`.
+                pub fn invalidate(&self) {{
+                    self.0.invalidate()
+                }}
             ", name = name)).unwrap();
 
         // writing the `get_layer` function
@@ -0,0 +1,528 @@
+/*!
+Drawing lots of textured quads with few draw calls.
+
+A `SpriteBatch` owns a streaming vertex buffer and an index buffer. Every frame, you call `begin`
+to start adding sprites, and the sprites are written in the vertex buffer and drawn together.
+The sprites are drawn when the texture changes, when the capacity of the batch is reached, and
+when you call `flush` or `finish`.
+
+Each draw call writes its vertices in the next region of a `StreamingVertexBuffer` with three
+regions, so writing the vertices only waits for the GPU if it is still drawing the sprites of the
+draw call made three draw calls earlier.
+
+The indices are `u16`s, which limits the capacity of a batch to `MAX_CAPACITY` sprites.
+
+```no_run
+# use glium::Surface;
+# let display: glium::Display = unsafe { std::mem::uninitialized() };
+# let texture: glium::texture::Texture2d = unsafe { std::mem::uninitialized() };
+use glium::batch::{Sprite, SpriteBatch};
+
+let mut batch = SpriteBatch::new(&display, 1024).unwrap();
+
+let mut target = display.draw();
+target.clear_color(0.0, 0.0, 0.0, 0.0);
+
+{
+    let mut sprites = batch.begin(&mut target, &Default::default());
+
+    sprites.add(&texture, Sprite {
+        position: [0.0, 0.0],
+        size: [0.5, 0.5],
+        rotation: 0.3,
+        .. Default::default()
+    }).unwrap();
+
+    sprites.finish().unwrap();
+}
+
+target.finish().unwrap();
+```
+
+# Sorting
+
+By default the sprites are drawn in the order in which they are added, which means that adding
+sprites with alternating textures results in one draw call per sprite. With a `SortMode` other
+than `None`, the sprites are kept in memory until `flush` or `finish` is called, and then sorted
+by texture or by depth before being drawn.
+
+# Custom programs
+
+The default program multiplies the color of the texture with the color of the sprite. You can
+pass your own program with `set_program`. The vertices are `SpriteVertex`es, and the program
+receives a `sampler2D` named `tex` containing the texture and a `mat4` named `matrix`
+containing the matrix passed to `set_matrix`.
+
+*/
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+
+use backend::Facade;
+use buffer::{BufferCreationError, StreamingVertexBuffer};
+use index::{IndexBuffer, PrimitiveType};
+use program::{translate_shader, Program, ProgramCreationError, ShaderStage};
+use texture::Texture2d;
+use uniforms::UniformsStorage;
+use vertex::{AttributeType, Vertex, VertexFormat};
+
+use DrawError;
+use DrawParameters;
+use Surface;
+
+/// Maximum number of sprites that a `SpriteBatch` can draw with one draw call. Each sprite uses
+/// four vertices, and the indices are `u16`s.
+pub const MAX_CAPACITY: usize = 16384;
+
+/// Number of regions of the vertex buffer.
+const REGIONS: usize = 3;
+
+/// A textured quad.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Sprite {
+    /// Position of the center of the sprite.
+    pub position: [f32; 2],
+
+    /// Width and height of the sprite.
+    pub size: [f32; 2],
+
+    /// Part of the texture to use, as `[left, bottom, right, top]` texture coordinates.
+    pub tex_coords: [f32; 4],
+
+    /// Color which is multiplied with the texture by the default program.
+    pub color: [f32; 4],
+
+    /// Counter-clockwise rotation around the center, in radians.
+    pub rotation: f32,
+
+    /// Depth of the sprite. It is the Z coordinate of the vertices and is used for depth
+    /// testing and by the depth `SortMode`s.
+    pub depth: f32,
+}
+
+impl Default for Sprite {
+    fn default() -> Sprite {
+        Sprite {
+            position: [0.0, 0.0],
+            size: [1.0, 1.0],
+            tex_coords: [0.0, 0.0, 1.0, 1.0],
+            color: [1.0, 1.0, 1.0, 1.0],
+            rotation: 0.0,
+            depth: 0.0,
+        }
+    }
+}
+
+/// A vertex of a sprite, as it is stored in the vertex buffer.
+///
+/// Custom programs must use these attributes.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(C)]
+pub struct SpriteVertex {
+    /// Position of the corner, with the depth of the sprite as the third component.
+    pub position: [f32; 3],
+
+    /// Texture coordinates of the corner.
+    pub tex_coords: [f32; 2],
+
+    /// Color of the sprite.
+    pub color: [f32; 4],
+}
+
+impl Vertex for SpriteVertex {
+    fn build_bindings() -> VertexFormat {
+        let f32_size = mem::size_of::<f32>();
+
+        Cow::Owned(vec![
+            (Cow::Borrowed("position"), 0, AttributeType::F32F32F32, false),
+            (Cow::Borrowed("tex_coords"), 3 * f32_size, AttributeType::F32F32, false),
+            (Cow::Borrowed("color"), 5 * f32_size, AttributeType::F32F32F32F32, false),
+        ])
+    }
+}
+
+/// Order in which the sprites are drawn.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SortMode {
+    /// The sprites are drawn in the order in which they are added. A draw call is made every
+    /// time the texture changes.
+    None,
+
+    /// The sprites are grouped by texture, which minimizes the number of draw calls. Sprites
+    /// with the same texture keep their order.
+    Texture,
+
+    /// The sprites with the largest depth are drawn first, which is what you want for
+    /// transparent sprites. Sprites with the same depth are grouped by texture.
+    BackToFront,
+
+    /// The sprites with the smallest depth are drawn first, which reduces overdraw when depth
+    /// testing is enabled. Sprites with the same depth are grouped by texture.
+    FrontToBack,
+}
+
+/// Error that can happen when creating a `SpriteBatch`.
+#[derive(Debug)]
+pub enum SpriteBatchCreationError {
+    /// The default program failed to build.
+    ProgramCreationError(ProgramCreationError),
+
+    /// The vertex buffer couldn't be created.
+    BufferCreationError(BufferCreationError),
+
+    /// The requested capacity is 0 or greater than `MAX_CAPACITY`.
+    InvalidCapacity(usize),
+}
+
+impl fmt::Display for SpriteBatchCreationError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            &SpriteBatchCreationError::ProgramCreationError(ref err) => {
+                fmt::Display::fmt(err, formatter)
+            },
+            &SpriteBatchCreationError::BufferCreationError(ref err) => {
+                write!(formatter, "Error while creating the vertex buffer: {:?}", err)
+            },
+            &SpriteBatchCreationError::InvalidCapacity(capacity) => {
+                write!(formatter, "The capacity of a sprite batch must be between 1 and {}, \
+                                   got {}", MAX_CAPACITY, capacity)
+            },
+        }
+    }
+}
+
+impl Error for SpriteBatchCreationError {
+    fn description(&self) -> &str {
+        match self {
+            &SpriteBatchCreationError::ProgramCreationError(_) => "Error while building the \
+                                                                   default program",
+            &SpriteBatchCreationError::BufferCreationError(_) => "Error while creating the vertex \
+                                                                  buffer",
+            &SpriteBatchCreationError::InvalidCapacity(_) => "The capacity of the sprite batch is \
+                                                              out of range",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match self {
+            &SpriteBatchCreationError::ProgramCreationError(ref err) => Some(err as &Error),
+            &SpriteBatchCreationError::BufferCreationError(_) => None,
+            &SpriteBatchCreationError::InvalidCapacity(_) => None,
+        }
+    }
+}
+
+impl From<ProgramCreationError> for SpriteBatchCreationError {
+    fn from(err: ProgramCreationError) -> SpriteBatchCreationError {
+        SpriteBatchCreationError::ProgramCreationError(err)
+    }
+}
+
+impl From<BufferCreationError> for SpriteBatchCreationError {
+    fn from(err: BufferCreationError) -> SpriteBatchCreationError {
+        SpriteBatchCreationError::BufferCreationError(err)
+    }
+}
+
+/// Buffers that are used to draw sprites.
+///
+/// See the module-level documentation.
+pub struct SpriteBatch {
+    vertex_buffer: StreamingVertexBuffer<SpriteVertex>,
+    index_buffer: IndexBuffer<u16>,
+    program: Program,
+    capacity: usize,
+    vertices: Vec<SpriteVertex>,
+}
+
+impl SpriteBatch {
+    /// Builds a batch that can draw up to `capacity` sprites per draw call.
+    ///
+    /// Returns an error if `capacity` is 0 or greater than `MAX_CAPACITY`, if the default
+    /// program fails to compile or if the vertex buffer can't be created.
+    pub fn new<F>(facade: &F, capacity: usize) -> Result<SpriteBatch, SpriteBatchCreationError>
+                  where F: Facade
+    {
+        if capacity == 0 || capacity > MAX_CAPACITY {
+            return Err(SpriteBatchCreationError::InvalidCapacity(capacity));
+        }
+
+        let program = try!(build_program(facade));
+
+        let vertex_buffer = try!(StreamingVertexBuffer::new(facade, capacity * 4, REGIONS));
+
+        let indices = (0 .. capacity as u16).flat_map(|n| {
+            vec![n * 4, n * 4 + 1, n * 4 + 2, n * 4 + 1, n * 4 + 3, n * 4 + 2].into_iter()
+        }).collect::<Vec<_>>();
+        let index_buffer = IndexBuffer::new(facade, PrimitiveType::TrianglesList, indices);
+
+        Ok(SpriteBatch {
+            vertex_buffer: vertex_buffer,
+            index_buffer: index_buffer,
+            program: program,
+            capacity: capacity,
+            vertices: Vec::with_capacity(capacity * 4),
+        })
+    }
+
+    /// Returns the maximum number of sprites drawn with one draw call.
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the program that is used when no custom program is set.
+    pub fn get_default_program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Starts adding sprites that will be drawn on `surface` with the given draw parameters.
+    pub fn begin<'b, S>(&'b mut self, surface: &'b mut S, draw_parameters: &DrawParameters<'b>)
+                        -> SpriteBatchFrame<'b, S> where S: Surface
+    {
+        SpriteBatchFrame {
+            batch: self,
+            surface: surface,
+            draw_parameters: *draw_parameters,
+            program: None,
+            matrix: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+            sort_mode: SortMode::None,
+            sprites: Vec::new(),
+        }
+    }
+}
+
+/// Sprites that are waiting to be drawn on a surface.
+///
+/// The remaining sprites are drawn when this object is destroyed, but the errors are then
+/// ignored. Call `finish` to get them.
+pub struct SpriteBatchFrame<'b, S: 'b> where S: Surface {
+    batch: &'b mut SpriteBatch,
+    surface: &'b mut S,
+    draw_parameters: DrawParameters<'b>,
+    program: Option<&'b Program>,
+    matrix: [[f32; 4]; 4],
+    sort_mode: SortMode,
+    sprites: Vec<(&'b Texture2d, Sprite)>,
+}
+
+impl<'b, S> SpriteBatchFrame<'b, S> where S: Surface {
+    /// Sets the program to use instead of the default one, or `None` to use the default one.
+    ///
+    /// The sprites that were added before are drawn with the previous program.
+    pub fn set_program(&mut self, program: Option<&'b Program>) -> Result<(), DrawError> {
+        try!(self.flush());
+        self.program = program;
+        Ok(())
+    }
+
+    /// Sets the value of the `matrix` uniform. The default value is the identity matrix.
+    ///
+    /// The sprites that were added before are drawn with the previous matrix.
+    pub fn set_matrix(&mut self, matrix: [[f32; 4]; 4]) -> Result<(), DrawError> {
+        try!(self.flush());
+        self.matrix = matrix;
+        Ok(())
+    }
+
+    /// Sets the order in which the sprites are drawn.
+    ///
+    /// The sprites that were added before are drawn in the previous order.
+    pub fn set_sort_mode(&mut self, sort_mode: SortMode) -> Result<(), DrawError> {
+        try!(self.flush());
+        self.sort_mode = sort_mode;
+        Ok(())
+    }
+
+    /// Adds a sprite.
+    ///
+    /// If the sort mode is `SortMode::None`, the sprites that were added before are drawn if
+    /// their texture is different or if the capacity of the batch is reached.
+    pub fn add(&mut self, texture: &'b Texture2d, sprite: Sprite) -> Result<(), DrawError> {
+        if self.sort_mode == SortMode::None {
+            let flush = match self.sprites.first() {
+                Some(&(previous, _)) => !same_texture(previous, texture) ||
+                                        self.sprites.len() >= self.batch.capacity,
+                None => false,
+            };
+
+            if flush {
+                try!(self.flush());
+            }
+        }
+
+        self.sprites.push((texture, sprite));
+        Ok(())
+    }
+
+    /// Returns the number of sprites that have been added but not drawn yet.
+    pub fn len(&self) -> usize {
+        self.sprites.len()
+    }
+
+    /// Draws all the sprites that have been added.
+    pub fn flush(&mut self) -> Result<(), DrawError> {
+        if self.sprites.is_empty() {
+            return Ok(());
+        }
+
+        let mut sprites = mem::replace(&mut self.sprites, Vec::new());
+
+        match self.sort_mode {
+            SortMode::None => (),
+            SortMode::Texture => sprites.sort_by(|a, b| texture_key(a.0).cmp(&texture_key(b.0))),
+            SortMode::BackToFront => sprites.sort_by(|a, b| {
+                match b.1.depth.partial_cmp(&a.1.depth).unwrap_or(Ordering::Equal) {
+                    Ordering::Equal => texture_key(a.0).cmp(&texture_key(b.0)),
+                    a => a
+                }
+            }),
+            SortMode::FrontToBack => sprites.sort_by(|a, b| {
+                match a.1.depth.partial_cmp(&b.1.depth).unwrap_or(Ordering::Equal) {
+                    Ordering::Equal => texture_key(a.0).cmp(&texture_key(b.0)),
+                    a => a
+                }
+            }),
+        }
+
+        let mut start = 0;
+        while start < sprites.len() {
+            let texture = sprites[start].0;
+
+            let mut end = start + 1;
+            while end < sprites.len() && end - start < self.batch.capacity &&
+                  same_texture(sprites[end].0, texture)
+            {
+                end += 1;
+            }
+
+            try!(draw_sprites(&mut *self.batch, &mut *self.surface, self.program,
+                              &self.draw_parameters, self.matrix, texture,
+                              &sprites[start .. end]));
+            start = end;
+        }
+
+        Ok(())
+    }
+
+    /// Draws all the sprites that have been added and destroys this object.
+    pub fn finish(mut self) -> Result<(), DrawError> {
+        self.flush()
+    }
+}
+
+impl<'b, S> Drop for SpriteBatchFrame<'b, S> where S: Surface {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Writes the vertices of some sprites that share the same texture and draws them. The default
+/// program of the batch is used if `program` is `None`.
+fn draw_sprites<S>(batch: &mut SpriteBatch, surface: &mut S, program: Option<&Program>,
+                   draw_parameters: &DrawParameters, matrix: [[f32; 4]; 4],
+                   texture: &Texture2d, sprites: &[(&Texture2d, Sprite)])
+                   -> Result<(), DrawError> where S: Surface
+{
+    debug_assert!(sprites.len() <= batch.capacity);
+
+    batch.vertices.clear();
+
+    for &(_, ref sprite) in sprites {
+        let (sin, cos) = sprite.rotation.sin_cos();
+        let half_width = sprite.size[0] / 2.0;
+        let half_height = sprite.size[1] / 2.0;
+        let (left, bottom) = (sprite.tex_coords[0], sprite.tex_coords[1]);
+        let (right, top) = (sprite.tex_coords[2], sprite.tex_coords[3]);
+
+        // top-left, top-right, bottom-left, bottom-right
+        let corners = [
+            (-half_width, half_height, left, top),
+            (half_width, half_height, right, top),
+            (-half_width, -half_height, left, bottom),
+            (half_width, -half_height, right, bottom),
+        ];
+
+        for &(x, y, u, v) in corners.iter() {
+            batch.vertices.push(SpriteVertex {
+                position: [sprite.position[0] + x * cos - y * sin,
+                           sprite.position[1] + x * sin + y * cos,
+                           sprite.depth],
+                tex_coords: [u, v],
+                color: sprite.color,
+            });
+        }
+    }
+
+    // only waits if the GPU is still reading the region
+    let mut vertices = batch.vertex_buffer.next_region();
+    vertices.write(&batch.vertices);
+
+    let indices = batch.index_buffer.slice(0 .. sprites.len() * 6).unwrap();
+
+    let uniforms = UniformsStorage::new("tex", texture).add("matrix", matrix);
+    let program = program.unwrap_or(&batch.program);
+
+    surface.draw(&vertices, &indices, program, &uniforms, draw_parameters)
+}
+
+/// Returns a value that identifies a texture, used to group the sprites.
+fn texture_key(texture: &Texture2d) -> usize {
+    texture as *const Texture2d as usize
+}
+
+fn same_texture(a: &Texture2d, b: &Texture2d) -> bool {
+    texture_key(a) == texture_key(b)
+}
+
+/// Source of the vertex shader of the default program, translated to the GLSL version supported
+/// by the backend.
+const VERTEX_SHADER: &'static str = "#version 140
+
+uniform mat4 matrix;
+
+in vec3 position;
+in vec2 tex_coords;
+in vec4 color;
+
+out vec2 v_tex_coords;
+out vec4 v_color;
+
+void main() {
+    gl_Position = matrix * vec4(position, 1.0);
+    v_tex_coords = tex_coords;
+    v_color = color;
+}
+";
+
+/// Source of the fragment shader of the default program.
+const FRAGMENT_SHADER: &'static str = "#version 140
+
+uniform sampler2D tex;
+
+in vec2 v_tex_coords;
+in vec4 v_color;
+
+out vec4 f_color;
+
+void main() {
+    f_color = texture(tex, v_tex_coords) * v_color;
+}
+";
+
+/// Builds the default program, translating it to the GLSL version supported by the backend.
+fn build_program<F>(facade: &F) -> Result<Program, ProgramCreationError> where F: Facade {
+    let version = facade.get_context().get_supported_glsl_version();
+
+    let vertex_shader = try!(translate_shader(VERTEX_SHADER, ShaderStage::Vertex, &version));
+    let fragment_shader = try!(translate_shader(FRAGMENT_SHADER, ShaderStage::Fragment,
+                                                &version));
+
+    Program::from_source(facade, &vertex_shader, &fragment_shader, None)
+}
@@ -12,7 +12,7 @@ use std::ops::{Deref, DerefMut, Range};
 use GlObject;
 use TransformFeedbackSessionExt;
 
-use buffer::{BufferType, BufferCreationError};
+use buffer::{BufferMode, BufferType, BufferCreationError, CopyError};
 use vertex::TransformFeedbackSession;
 use vertex_array_object::VertexAttributesSystem;
 
@@ -38,8 +38,8 @@ pub struct Buffer {
     /// the persistent mapping.
     immutable: bool,
 
-    /// If true, the buffer was created with the "dynamic" flag.
-    dynamic_creation_flag: bool,
+    /// The mode that was requested when creating the buffer.
+    mode: BufferMode,
 
     /// True if the buffer is currently mapped with something else than persistent mapping.
     ///
@@ -62,6 +62,8 @@ enum MappingImpl<'b, D> {
         offset_bytes: usize,
         data: *mut D,
         len: usize,
+        // true if `flush_range` has been called
+        flushed: bool,
     },
 
     TemporaryBuffer {
@@ -82,7 +84,7 @@ enum MappingImpl<'b, D> {
 impl Buffer {
     /// Builds a new buffer containing the given data. The size of the buffer is equal to the
     /// size of the data.
-    pub fn new<D, F>(facade: &F, data: &[D], ty: BufferType, dynamic: bool)
+    pub fn new<D, F>(facade: &F, data: &[D], ty: BufferType, mode: BufferMode)
                      -> Result<Buffer, BufferCreationError>
                      where D: Send + Copy + 'static, F: Facade
     {
@@ -91,7 +93,7 @@ impl Buffer {
         let size = data.len() * mem::size_of::<D>();
 
         let (id, immutable, persistent_mapping) = try!(unsafe {
-            create_buffer(&mut ctxt, size, Some(&data), ty, dynamic, false)
+            create_buffer(&mut ctxt, size, Some(&data), ty, mode)
         });
 
         Ok(Buffer {
@@ -101,20 +103,20 @@ impl Buffer {
             size: size,
             persistent_mapping: persistent_mapping,
             immutable: immutable,
-            dynamic_creation_flag: dynamic,
+            mode: mode,
             mapped: Cell::new(false),
             latest_shader_write: Cell::new(0),
         })
     }
 
     /// Builds a new empty buffer of the given size.
-    pub fn empty<F>(facade: &F, ty: BufferType, size: usize, dynamic: bool)
+    pub fn empty<F>(facade: &F, ty: BufferType, size: usize, mode: BufferMode)
                     -> Result<Buffer, BufferCreationError> where F: Facade
     {
         let mut ctxt = facade.get_context().make_current();
 
         let (id, immutable, persistent_mapping) = try!(unsafe {
-            create_buffer::<()>(&mut ctxt, size, None, ty, dynamic, false)
+            create_buffer::<()>(&mut ctxt, size, None, ty, mode)
         });
 
         Ok(Buffer {
@@ -124,7 +126,7 @@ impl Buffer {
             size: size,
             persistent_mapping: persistent_mapping,
             immutable: immutable,
-            dynamic_creation_flag: dynamic,
+            mode: mode,
             mapped: Cell::new(false),
             latest_shader_write: Cell::new(0),
         })
@@ -248,6 +250,19 @@ impl Buffer {
         unsafe { bind_buffer(ctxt, 0, BufferType::PixelUnpackBuffer); }
     }
 
+    /// Makes sure that the buffer is binded to the `GL_QUERY_BUFFER`.
+    pub fn prepare_and_bind_for_query(&self, mut ctxt: &mut CommandContext) {
+        self.assert_unmapped(ctxt);
+        self.assert_not_transform_feedback(ctxt);
+
+        unsafe { bind_buffer(ctxt, self.id, BufferType::QueryBuffer); }
+    }
+
+    /// Makes sure that nothing is binded to `GL_QUERY_BUFFER`.
+    pub fn unbind_query(ctxt: &mut CommandContext) {
+        unsafe { bind_buffer(ctxt, 0, BufferType::QueryBuffer); }
+    }
+
     /// Makes sure that the buffer is binded to the `GL_DRAW_INDIRECT_BUFFER` and calls
     /// `glMemoryBarrier(GL_COMMAND_BARRIER_BIT)` if necessary.
     pub fn prepare_and_bind_for_draw_indirect(&self, mut ctxt: &mut CommandContext) {
@@ -296,6 +311,24 @@ impl Buffer {
         self.latest_shader_write.set(ctxt.state.next_draw_call_id);        // TODO: put this somewhere else
     }
 
+    /// Makes sure that the buffer is binded to the indexed `GL_ATOMIC_COUNTER_BUFFER` point and
+    /// calls `glMemoryBarrier(GL_ATOMIC_COUNTER_BARRIER_BIT)` if necessary.
+    pub fn prepare_and_bind_for_atomic_counter(&self, ctxt: &mut CommandContext,
+                                               index: gl::types::GLuint, range: Range<usize>)
+    {
+        self.assert_unmapped(ctxt);
+        self.assert_not_transform_feedback(ctxt);
+
+        if self.latest_shader_write.get() >= ctxt.state.latest_memory_barrier_atomic_counter {
+            unsafe { ctxt.gl.MemoryBarrier(gl::ATOMIC_COUNTER_BARRIER_BIT); }
+            ctxt.state.latest_memory_barrier_atomic_counter = ctxt.state.next_draw_call_id;
+        }
+
+        self.indexed_bind(ctxt, BufferType::AtomicCounterBuffer, index, range);
+
+        self.latest_shader_write.set(ctxt.state.next_draw_call_id);        // TODO: put this somewhere else
+    }
+
     /// Binds the buffer to `GL_TRANSFORM_FEEDBACk_BUFFER` regardless of the current transform
     /// feedback object.
     pub fn bind_to_transform_feedback(&self, ctxt: &mut CommandContext, index: gl::types::GLuint,
@@ -365,7 +398,7 @@ impl Buffer {
 
             let (tmp_buffer, _, _) = create_buffer(&mut ctxt, to_upload, Some(data),
                                                    BufferType::CopyReadBuffer,
-                                                   true, true).unwrap();
+                                                   BufferMode::ClientStorage).unwrap();
             copy_buffer(&mut ctxt, tmp_buffer, 0, self.id, offset_bytes, to_upload);
             destroy_buffer(&mut ctxt, tmp_buffer);
 
@@ -413,6 +446,126 @@ impl Buffer {
         }
     }
 
+    /// Copies a part of this buffer to another buffer. The copy is done by the GPU.
+    ///
+    /// `range` and `dest_offset` are in bytes. Returns `Err` if the backend doesn't support
+    /// copying between buffers.
+    ///
+    /// # Panic
+    ///
+    /// Panics if out of range.
+    ///
+    pub fn copy_to(&self, range: Range<usize>, target: &Buffer, dest_offset: usize)
+                   -> Result<(), CopyError>
+    {
+        assert!(range.start <= range.end && range.end <= self.size);
+        assert!(dest_offset + (range.end - range.start) <= target.size);
+
+        let mut ctxt = self.context.make_current();
+
+        if !is_copy_supported(&mut ctxt) {
+            return Err(CopyError::NotSupported);
+        }
+
+        self.assert_unmapped(&mut ctxt);
+        self.assert_not_transform_feedback(&mut ctxt);
+        self.barrier_for_buffer_update(&mut ctxt);
+
+        target.assert_unmapped(&mut ctxt);
+        target.assert_not_transform_feedback(&mut ctxt);
+        target.barrier_for_buffer_update(&mut ctxt);
+
+        if range.start != range.end {
+            unsafe {
+                copy_buffer(&mut ctxt, self.id, range.start, target.id, dest_offset,
+                            range.end - range.start);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fills a part of the buffer with copies of `value`.
+    ///
+    /// Uses `glClearBufferSubData` if the backend supports it, if the size of `D` matches one
+    /// of the formats accepted by this function and if `offset_bytes` is a multiple of the size
+    /// of this format, and uploads data otherwise.
+    ///
+    /// # Panic
+    ///
+    /// Panics if out of range.
+    ///
+    /// # Unsafety
+    ///
+    /// If the buffer uses persistent mapping, the caller of this function must handle
+    /// synchronization.
+    ///
+    pub unsafe fn clear<D>(&self, offset_bytes: usize, elements: usize, value: D)
+                           where D: Copy + Send + 'static
+    {
+        let element_size = mem::size_of::<D>();
+        assert!(offset_bytes + elements * element_size <= self.size);
+
+        if elements == 0 || element_size == 0 {
+            return;
+        }
+
+        // the internal format, format and type that correspond to the size of `D` ; the offset
+        // and the size passed to `glClearBufferSubData` must be multiples of the size of the
+        // internal format
+        let format = if offset_bytes % element_size != 0 {
+            None
+        } else {
+            match element_size {
+                1 => Some((gl::R8UI, gl::RED_INTEGER, gl::UNSIGNED_BYTE)),
+                2 => Some((gl::R16UI, gl::RED_INTEGER, gl::UNSIGNED_SHORT)),
+                4 => Some((gl::R32UI, gl::RED_INTEGER, gl::UNSIGNED_INT)),
+                8 => Some((gl::RG32UI, gl::RG_INTEGER, gl::UNSIGNED_INT)),
+                12 => Some((gl::RGB32UI, gl::RGB_INTEGER, gl::UNSIGNED_INT)),
+                16 => Some((gl::RGBA32UI, gl::RGBA_INTEGER, gl::UNSIGNED_INT)),
+                _ => None
+            }
+        };
+
+        {
+            let mut ctxt = self.context.make_current();
+
+            let supported = ctxt.version >= &Version(Api::Gl, 4, 3) ||
+                            ctxt.extensions.gl_arb_clear_buffer_object;
+
+            if let (true, Some((internal_format, format, ty))) = (supported, format) {
+                self.assert_unmapped(&mut ctxt);
+                self.assert_not_transform_feedback(&mut ctxt);
+                self.barrier_for_buffer_update(&mut ctxt);
+
+                let data = &value as *const D as *const libc::c_void;
+
+                if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                    ctxt.extensions.gl_arb_direct_state_access
+                {
+                    ctxt.gl.ClearNamedBufferSubData(self.id, internal_format,
+                                                    offset_bytes as gl::types::GLintptr,
+                                                    (elements * element_size)
+                                                                as gl::types::GLsizeiptr,
+                                                    format, ty, data);
+
+                } else {
+                    let bind = bind_buffer(&mut ctxt, self.id, self.ty);
+                    ctxt.gl.ClearBufferSubData(bind, internal_format,
+                                               offset_bytes as gl::types::GLintptr,
+                                               (elements * element_size)
+                                                                as gl::types::GLsizeiptr,
+                                               format, ty, data);
+                }
+
+                return;
+            }
+        }
+
+        let data = vec![value; elements];
+        self.upload(offset_bytes, &data);
+    }
+
     /// Invalidates the content of the buffer. The data becomes undefined.
     ///
     /// `offset` and `size` are both in bytes.
@@ -438,12 +591,11 @@ impl Buffer {
                                                          size as gl::types::GLsizeiptr) };
             }
 
-        } else if !self.immutable {
+        } else if !self.immutable && !is_buffer_storage_supported(&mut ctxt) {
             if is_whole_buffer {
-                let flags = if self.dynamic_creation_flag {
-                    gl::DYNAMIC_DRAW
-                } else {
-                    gl::STATIC_DRAW
+                let flags = match self.mode {
+                    BufferMode::Default | BufferMode::Immutable => gl::STATIC_DRAW,
+                    _ => gl::DYNAMIC_DRAW,
                 };
 
                 if ctxt.version >= &Version(Api::Gl, 1, 5) ||
@@ -510,6 +662,7 @@ impl Buffer {
                     offset_bytes: offset_bytes,
                     data: (existing_mapping as *mut u8).offset(offset_bytes as isize) as *mut D,
                     len: elements,
+                    flushed: false,
                 },
             }
 
@@ -523,7 +676,8 @@ impl Buffer {
                 let mut ctxt = self.context.make_current();
                 let (temporary_buffer, _, _) = create_buffer::<D>(&mut ctxt, size_bytes,
                                                                   None, BufferType::CopyWriteBuffer,
-                                                                  true, true).unwrap();
+                                                                  BufferMode::ClientStorage)
+                                                                  .unwrap();
                 temporary_buffer
             };
 
@@ -686,10 +840,48 @@ impl GlObject for Buffer {
 
 unsafe impl<'a, D> Sync for Mapping<'a, D> where D: Sync {}
 
+impl<'a, D> Mapping<'a, D> {
+    /// Makes the modifications of a range of elements visible to the GPU.
+    ///
+    /// Only buffers with a non-coherent persistent mapping need to be flushed. By default the
+    /// whole mapping is flushed when it is destroyed, but once this function has been called
+    /// only the ranges passed to it are flushed. For other buffers, this is a no-op and the
+    /// modifications are written when the mapping is destroyed.
+    ///
+    /// # Panic
+    ///
+    /// Panics if out of range.
+    pub fn flush_range(&mut self, range: Range<usize>) {
+        if let MappingImpl::PersistentMapping { buffer, offset_bytes, len,
+                                                ref mut flushed, .. } = self.mapping
+        {
+            assert!(range.start <= range.end && range.end <= len);
+            *flushed = true;
+
+            if buffer.mode == BufferMode::PersistentCoherent || range.start == range.end {
+                return;
+            }
+
+            let element_size = mem::size_of::<D>();
+            let mut ctxt = buffer.context.make_current();
+
+            unsafe {
+                flush_range(&mut ctxt, buffer.id, buffer.ty,
+                            offset_bytes + range.start * element_size ..
+                            offset_bytes + range.end * element_size);
+            }
+        }
+    }
+}
+
 impl<'a, D> Drop for Mapping<'a, D> {
     fn drop(&mut self) {
         match self.mapping {
-            MappingImpl::PersistentMapping { buffer, offset_bytes, data, len } => {
+            MappingImpl::PersistentMapping { buffer, offset_bytes, len, flushed, .. } => {
+                if flushed || buffer.mode == BufferMode::PersistentCoherent {
+                    return;
+                }
+
                 let mut ctxt = buffer.context.make_current();
                 unsafe {
                     flush_range(&mut ctxt, buffer.id, buffer.ty,
@@ -772,7 +964,7 @@ impl<'a, D> DerefMut for Mapping<'a, D> {
 ///
 /// Panics if `data.len() * size_of::<D>() < size` or if `size % size_of::<D>() != 0`.
 unsafe fn create_buffer<D>(mut ctxt: &mut CommandContext, size: usize, data: Option<&[D]>,
-                           ty: BufferType, dynamic: bool, avoid_persistent: bool)
+                           ty: BufferType, mode: BufferMode)
                            -> Result<(gl::types::GLuint, bool, Option<*mut libc::c_void>),
                                      BufferCreationError>
                            where D: Send + Copy + 'static
@@ -781,6 +973,15 @@ unsafe fn create_buffer<D>(mut ctxt: &mut CommandContext, size: usize, data: Opt
         return Err(BufferCreationError::BufferTypeNotSupported);
     }
 
+    match mode {
+        BufferMode::Persistent | BufferMode::PersistentCoherent => {
+            if !is_buffer_storage_supported(ctxt) {
+                return Err(BufferCreationError::BufferModeNotSupported);
+            }
+        },
+        _ => ()
+    }
+
     if let Some(ref data) = data {
         assert!(data.len() * mem::size_of::<D>() >= size);
         assert!(size % mem::size_of::<D>() == 0);
@@ -818,18 +1019,29 @@ unsafe fn create_buffer<D>(mut ctxt: &mut CommandContext, size: usize, data: Opt
     let mut obtained_size: gl::types::GLint = mem::uninitialized();
     let immutable: bool;
 
-    let mutable_storage_flags = if dynamic {
-        gl::DYNAMIC_DRAW
-    } else {
-        gl::STATIC_DRAW
+    let mutable_storage_flags = match mode {
+        BufferMode::Default | BufferMode::Immutable => gl::STATIC_DRAW,
+        _ => gl::DYNAMIC_DRAW,
     };
 
-    let immutable_storage_flags = if dynamic && avoid_persistent {
-        gl::DYNAMIC_STORAGE_BIT | gl::MAP_READ_BIT | gl::MAP_WRITE_BIT
-    } else if dynamic {
-        gl::MAP_PERSISTENT_BIT | gl::MAP_READ_BIT | gl::MAP_WRITE_BIT
-    } else {
-        0
+    // flags for buffer storage, and whether the buffer can only be modified by copying or
+    // through the persistent mapping
+    let (immutable_storage_flags, immutable_storage) = match mode {
+        BufferMode::Default => {
+            (gl::DYNAMIC_STORAGE_BIT | gl::MAP_READ_BIT | gl::MAP_WRITE_BIT, false)
+        },
+        BufferMode::Immutable => (0, true),
+        BufferMode::ClientStorage => {
+            (gl::DYNAMIC_STORAGE_BIT | gl::MAP_READ_BIT | gl::MAP_WRITE_BIT |
+             gl::CLIENT_STORAGE_BIT, false)
+        },
+        BufferMode::Dynamic | BufferMode::Persistent => {
+            (gl::MAP_PERSISTENT_BIT | gl::MAP_READ_BIT | gl::MAP_WRITE_BIT, true)
+        },
+        BufferMode::PersistentCoherent => {
+            (gl::MAP_PERSISTENT_BIT | gl::MAP_COHERENT_BIT | gl::MAP_READ_BIT |
+             gl::MAP_WRITE_BIT, true)
+        },
     };
 
     if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
@@ -837,7 +1049,7 @@ unsafe fn create_buffer<D>(mut ctxt: &mut CommandContext, size: usize, data: Opt
                                    data_ptr as *const libc::c_void,
                                    immutable_storage_flags);
         ctxt.gl.GetNamedBufferParameteriv(id, gl::BUFFER_SIZE, &mut obtained_size);
-        immutable = !avoid_persistent;
+        immutable = immutable_storage;
 
     } else if ctxt.extensions.gl_arb_buffer_storage &&
               ctxt.extensions.gl_ext_direct_state_access
@@ -846,7 +1058,7 @@ unsafe fn create_buffer<D>(mut ctxt: &mut CommandContext, size: usize, data: Opt
                                       data_ptr as *const libc::c_void,
                                       immutable_storage_flags);
         ctxt.gl.GetNamedBufferParameterivEXT(id, gl::BUFFER_SIZE, &mut obtained_size);
-        immutable = !avoid_persistent;
+        immutable = immutable_storage;
 
     } else if ctxt.version >= &Version(Api::Gl, 4, 4) ||
               ctxt.extensions.gl_arb_buffer_storage
@@ -856,7 +1068,7 @@ unsafe fn create_buffer<D>(mut ctxt: &mut CommandContext, size: usize, data: Opt
                               data_ptr as *const libc::c_void,
                               immutable_storage_flags);
         ctxt.gl.GetBufferParameteriv(bind, gl::BUFFER_SIZE, &mut obtained_size);
-        immutable = !avoid_persistent;
+        immutable = immutable_storage;
 
     } else if ctxt.version >= &Version(Api::Gl, 1, 5) ||
         ctxt.version >= &Version(Api::GlEs, 2, 0)
@@ -892,19 +1104,27 @@ unsafe fn create_buffer<D>(mut ctxt: &mut CommandContext, size: usize, data: Opt
         return Err(BufferCreationError::OutOfMemory);
     }
 
-    let persistent_mapping = if immutable && dynamic && !avoid_persistent {
+    let persistent = match mode {
+        BufferMode::Dynamic | BufferMode::Persistent | BufferMode::PersistentCoherent => true,
+        _ => false,
+    };
+
+    let persistent_mapping = if immutable && persistent {
+        let flags = if mode == BufferMode::PersistentCoherent {
+            gl::MAP_READ_BIT | gl::MAP_WRITE_BIT | gl::MAP_PERSISTENT_BIT | gl::MAP_COHERENT_BIT
+        } else {
+            gl::MAP_READ_BIT | gl::MAP_WRITE_BIT | gl::MAP_PERSISTENT_BIT |
+            gl::MAP_FLUSH_EXPLICIT_BIT
+        };
+
         let ptr = if ctxt.version >= &Version(Api::Gl, 4, 5) {
-            ctxt.gl.MapNamedBufferRange(id, 0, size as gl::types::GLsizei,
-                                        gl::MAP_READ_BIT | gl::MAP_WRITE_BIT |
-                                        gl::MAP_PERSISTENT_BIT | gl::MAP_FLUSH_EXPLICIT_BIT)
+            ctxt.gl.MapNamedBufferRange(id, 0, size as gl::types::GLsizei, flags)
 
         } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
                   ctxt.extensions.gl_arb_map_buffer_range
         {
             let bind = bind_buffer(&mut ctxt, id, ty);
-            ctxt.gl.MapBufferRange(bind, 0, size as gl::types::GLsizeiptr,
-                                   gl::MAP_READ_BIT | gl::MAP_WRITE_BIT |
-                                   gl::MAP_PERSISTENT_BIT | gl::MAP_FLUSH_EXPLICIT_BIT)
+            ctxt.gl.MapBufferRange(bind, 0, size as gl::types::GLsizeiptr, flags)
         } else {
             unreachable!();
         };
@@ -957,6 +1177,15 @@ fn is_buffer_type_supported(ctxt: &mut CommandContext, ty: BufferType) -> bool {
             ctxt.extensions.gl_ext_multi_draw_indirect
         },
 
+        BufferType::AtomicCounterBuffer => {
+            ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+            ctxt.extensions.gl_arb_shader_atomic_counters
+        },
+
+        BufferType::QueryBuffer => {
+            ctxt.version >= &Version(Api::Gl, 4, 4) || ctxt.extensions.gl_arb_query_buffer_object
+        },
+
         _ => false,     // FIXME: 
     }
 }
@@ -1115,6 +1344,19 @@ unsafe fn indexed_bind_buffer(mut ctxt: &mut CommandContext, id: gl::types::GLui
     panic!();
 }
 
+/// Returns true if `create_buffer` uses buffer storage.
+fn is_buffer_storage_supported(ctxt: &mut CommandContext) -> bool {
+    ctxt.version >= &Version(Api::Gl, 4, 4) || ctxt.extensions.gl_arb_buffer_storage ||
+    ctxt.extensions.gl_arb_direct_state_access
+}
+
+/// Returns true if the backend supports `copy_buffer`.
+fn is_copy_supported(ctxt: &mut CommandContext) -> bool {
+    ctxt.version >= &Version(Api::Gl, 3, 1) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+    ctxt.extensions.gl_arb_copy_buffer || ctxt.extensions.gl_nv_copy_buffer ||
+    ctxt.extensions.gl_arb_direct_state_access || ctxt.extensions.gl_ext_direct_state_access
+}
+
 /// Copies from a buffer to another.
 unsafe fn copy_buffer(ctxt: &mut CommandContext, source: gl::types::GLuint,
                       source_offset: usize, dest: gl::types::GLuint, dest_offset: usize,
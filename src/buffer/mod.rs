@@ -14,12 +14,29 @@
 //!    abstractions over a subbuffer indicating their specific purpose. They implement `Deref`
 //!    for the subbuffer. These types are in the `vertex`, `index`, ... modules.
 //!
+//! In addition to this, a `StreamingBuffer` is a buffer split in multiple regions, for data that
+//! is rewritten every frame. Use a `StreamingVertexBuffer` or a `StreamingIndexBuffer` to build
+//! one.
+//!
+//! A `BufferPool` allocates many small subbuffers from a few large buffers, which avoids
+//! switching between buffers when drawing many small objects. Use a `VertexBufferPool`, an
+//! `IndexBufferPool` or a `UniformBufferPool` to build one.
+//!
 pub use self::view::{BufferView, BufferViewAny, BufferViewMutSlice};
 pub use self::view::{BufferViewSlice, BufferViewAnySlice, Mapping};
+pub use self::pool::{BufferPool, BufferPoolSlice, BufferPoolStatistics};
+pub use self::pool::{VertexBufferPool, VertexBufferPoolSlice};
+pub use self::pool::{IndexBufferPool, IndexBufferPoolSlice};
+pub use self::pool::{UniformBufferPool, UniformBufferPoolSlice};
+pub use self::streaming::{StreamingBuffer, StreamingRegion};
+pub use self::streaming::{StreamingVertexBuffer, StreamingVertexRegion};
+pub use self::streaming::{StreamingIndexBuffer, StreamingIndexRegion};
 
 use gl;
 
 mod alloc;
+mod pool;
+mod streaming;
 mod view;
 
 /// Error that can happen when creating a buffer.
@@ -30,6 +47,96 @@ pub enum BufferCreationError {
 
     /// This type of buffer is not supported.
     BufferTypeNotSupported,
+
+    /// The requested `BufferMode` is not supported by the backend.
+    BufferModeNotSupported,
+
+    /// A `StreamingBuffer` was requested with zero regions.
+    NoRegions,
+}
+
+/// How the content of a buffer is going to be used and modified.
+///
+/// The mode is a hint to the OpenGL implementation, but it also determines how glium writes,
+/// maps and synchronizes the buffer.
+///
+/// If the backend supports buffer storage (OpenGL 4.4 or `GL_ARB_buffer_storage`), the buffer
+/// is created with `glBufferStorage` and the flags given below. Otherwise it is created with
+/// `glBufferData` and the `GL_STATIC_DRAW` usage for `Default` and `Immutable`, or the
+/// `GL_DYNAMIC_DRAW` usage for the other modes.
+///
+/// `BufferView::new` and `BufferView::empty` used to take a `dynamic` flag instead of a mode.
+/// `dynamic: false` corresponds to `Immutable`, and `dynamic: true` to `Dynamic`. `Default`
+/// is new, and gives a buffer with mutable storage when buffer storage is supported.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BufferMode {
+    /// The content of the buffer is rarely modified. Writes and mappings are done directly on
+    /// the buffer.
+    ///
+    /// Flags: `GL_DYNAMIC_STORAGE_BIT | GL_MAP_READ_BIT | GL_MAP_WRITE_BIT`.
+    Default,
+
+    /// The content of the buffer is not supposed to be modified after its creation, except by
+    /// the GPU (for example with `copy_to` or transform feedback). Writes and mappings are
+    /// still possible, but go through a temporary buffer.
+    ///
+    /// Flags: none.
+    Immutable,
+
+    /// The content of the buffer is modified frequently. The buffer is persistently mapped,
+    /// like with `Persistent`, if the backend supports buffer storage, and is a regular buffer
+    /// otherwise.
+    ///
+    /// Flags: `GL_MAP_PERSISTENT_BIT | GL_MAP_READ_BIT | GL_MAP_WRITE_BIT`.
+    Dynamic,
+
+    /// Like `Default`, but hints that the buffer should be stored in client memory, which is
+    /// useful for data that the CPU reads back. Doesn't use persistent mapping.
+    ///
+    /// Flags: `GL_DYNAMIC_STORAGE_BIT | GL_MAP_READ_BIT | GL_MAP_WRITE_BIT |
+    /// GL_CLIENT_STORAGE_BIT`.
+    ClientStorage,
+
+    /// The buffer is persistently mapped in memory with `GL_MAP_FLUSH_EXPLICIT_BIT`. The
+    /// mapping is not coherent, which means that the modifications are only visible to the GPU
+    /// after they have been flushed. This is done when a `Mapping` is destroyed or with
+    /// `Mapping::flush_range`.
+    ///
+    /// Flags: `GL_MAP_PERSISTENT_BIT | GL_MAP_READ_BIT | GL_MAP_WRITE_BIT`.
+    ///
+    /// Creating the buffer fails with `BufferModeNotSupported` if the backend doesn't support
+    /// buffer storage.
+    Persistent,
+
+    /// The buffer is persistently and coherently mapped in memory. The modifications are
+    /// visible to the GPU without any flush.
+    ///
+    /// Flags: `GL_MAP_PERSISTENT_BIT | GL_MAP_COHERENT_BIT | GL_MAP_READ_BIT |
+    /// GL_MAP_WRITE_BIT`.
+    ///
+    /// Creating the buffer fails with `BufferModeNotSupported` if the backend doesn't support
+    /// buffer storage.
+    PersistentCoherent,
+}
+
+impl Default for BufferMode {
+    fn default() -> BufferMode {
+        BufferMode::Default
+    }
+}
+
+/// Error that can happen when copying data between buffers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CopyError {
+    /// The backend doesn't support copying data between buffers.
+    NotSupported,
+
+    /// The source and the target don't have the same number of elements.
+    LengthMismatch,
+
+    /// The offset of the source or of the target in its buffer is not a multiple of the size
+    /// of the elements.
+    Misaligned,
 }
 
 /// Type of a buffer.
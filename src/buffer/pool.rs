@@ -0,0 +1,677 @@
+use std::cell::{Cell, RefCell};
+use std::cmp;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut, Range};
+use std::rc::Rc;
+
+use backend::Facade;
+use context::Context;
+use index::{Index, IndicesSource, PrimitiveType};
+use sync::LinearSyncFence;
+use uniforms::{AsUniformValue, UniformBlock, UniformType, UniformValue};
+use vertex::{Vertex, VertexFormat, VerticesSource, IntoVerticesSource};
+
+use ContextExt;
+
+use buffer::BufferType;
+use buffer::BufferMode;
+use buffer::BufferCreationError;
+use buffer::alloc::Buffer;
+use buffer::view::{self, BufferViewSlice, BufferViewAnySlice, Mapping};
+
+/// Allocates many small buffers from a few large OpenGL buffers.
+///
+/// This type contains what is common to `VertexBufferPool`, `IndexBufferPool` and
+/// `UniformBufferPool`, which all deref to it. Each call to their `alloc` or `alloc_empty`
+/// methods returns a slice that represents a range of one of the buffers of the pool. When the
+/// slice is destroyed, its range is given back to the pool and can be reused by the next
+/// allocations.
+///
+/// Drawing many objects whose vertices are in the same OpenGL buffer is faster than drawing
+/// objects that each have their own buffer, because glium doesn't need to switch between
+/// vertex array objects.
+///
+/// When there is no free range large enough for an allocation, the pool creates a new buffer
+/// that can hold `chunk_len` elements, or exactly the requested number of elements if it is
+/// larger.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {
+/// # use glium::Surface;
+/// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+/// # let program: glium::Program = unsafe { std::mem::uninitialized() };
+/// #[derive(Copy, Clone)]
+/// struct Vertex {
+///     position: [f32; 2],
+/// }
+///
+/// implement_vertex!(Vertex, position);
+///
+/// let mut pool = glium::buffer::VertexBufferPool::<Vertex>::new(&display, 4096,
+///                                                  glium::buffer::BufferMode::Default).unwrap();
+///
+/// let triangle = pool.alloc(&[Vertex { position: [0.0, 0.0] }, Vertex { position: [1.0, 0.0] },
+///                             Vertex { position: [0.0, 1.0] }]).unwrap();
+///
+/// let mut target = display.draw();
+/// target.draw(&triangle, &glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
+///             &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+/// target.finish().unwrap();
+/// # }
+/// ```
+pub struct BufferPool<T> where T: Copy + Send + 'static {
+    context: Rc<Context>,
+    ty: BufferType,
+    mode: BufferMode,
+    chunk_len: usize,
+    // alignment of the offset of each allocation, in bytes
+    alignment: usize,
+    chunks: Vec<Rc<Chunk>>,
+    marker: PhantomData<T>,
+}
+
+/// Statistics about the memory used by a `BufferPool`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BufferPoolStatistics {
+    /// Number of OpenGL buffers owned by the pool.
+    pub buffers: usize,
+
+    /// Total size of the buffers, in bytes.
+    pub total_bytes: usize,
+
+    /// Number of bytes that are currently allocated, including the padding required by the
+    /// alignment.
+    pub used_bytes: usize,
+
+    /// Number of slices that are alive.
+    pub allocations: usize,
+
+    /// Number of distinct free ranges.
+    pub free_ranges: usize,
+
+    /// Size of the largest free range, in bytes. This is the largest allocation that can be
+    /// done without creating a new buffer.
+    pub largest_free_range: usize,
+}
+
+impl BufferPoolStatistics {
+    /// Returns the number of bytes that are free.
+    pub fn free_bytes(&self) -> usize {
+        self.total_bytes - self.used_bytes
+    }
+
+    /// Returns the fragmentation of the free memory, between `0.0` and `1.0`.
+    ///
+    /// This is `0.0` if all the free memory is in one range, and gets closer to `1.0` as the
+    /// free memory is split in many small ranges.
+    pub fn fragmentation(&self) -> f32 {
+        let free = self.free_bytes();
+
+        if free == 0 {
+            return 0.0;
+        }
+
+        1.0 - self.largest_free_range as f32 / free as f32
+    }
+}
+
+/// A range of elements allocated from a `BufferPool`.
+///
+/// The range is given back to the pool when this object is destroyed. If the buffer uses
+/// persistent mapping, destroying this object waits until the GPU has finished using it.
+///
+/// This type contains what is common to `VertexBufferPoolSlice`, `IndexBufferPoolSlice` and
+/// `UniformBufferPoolSlice`, which all deref to it.
+pub struct BufferPoolSlice<T> where T: Copy + Send + 'static {
+    chunk: Rc<Chunk>,
+    offset_bytes: usize,
+    size_bytes: usize,
+    len: usize,
+    fence: RefCell<Option<LinearSyncFence>>,
+    marker: PhantomData<T>,
+}
+
+/// One of the buffers of a pool.
+struct Chunk {
+    alloc: Buffer,
+    // sorted list of the free ranges, in bytes
+    free: RefCell<Vec<Range<usize>>>,
+    allocations: Cell<usize>,
+}
+
+impl<T> fmt::Debug for BufferPool<T> where T: Copy + Send + 'static {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "BufferPool({:?})", self.get_statistics())
+    }
+}
+
+impl<T> fmt::Debug for BufferPoolSlice<T> where T: Copy + Send + 'static {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "BufferPoolSlice({:?}, {} .. {})", self.chunk.alloc, self.offset_bytes,
+               self.offset_bytes + self.size_bytes)
+    }
+}
+
+impl<T> BufferPool<T> where T: Copy + Send + 'static {
+    fn new<F>(facade: &F, ty: BufferType, chunk_len: usize, mode: BufferMode)
+              -> Result<BufferPool<T>, BufferCreationError> where F: Facade
+    {
+        assert!(chunk_len >= 1, "The buffers of a pool must hold at least one element");
+
+        let context = facade.get_context().clone();
+
+        let elements_size = cmp::max(mem::size_of::<T>(), 1);
+        let alignment = if ty == BufferType::UniformBuffer {
+            let required = context.capabilities().uniform_buffer_offset_alignment;
+            lcm(elements_size, cmp::max(required, 1) as usize)
+        } else {
+            elements_size
+        };
+
+        let mut pool = BufferPool {
+            context: context,
+            ty: ty,
+            mode: mode,
+            chunk_len: chunk_len,
+            alignment: alignment,
+            chunks: Vec::new(),
+            marker: PhantomData,
+        };
+
+        // the first buffer is created immediately so that errors are reported here
+        let size = pool.chunk_size(0);
+        let chunk = try!(pool.new_chunk(size));
+        pool.chunks.push(Rc::new(chunk));
+
+        Ok(pool)
+    }
+
+    /// Returns the number of elements that each buffer of the pool can hold.
+    pub fn get_chunk_len(&self) -> usize {
+        self.chunk_len
+    }
+
+    /// Allocates a range of the pool and writes `data` in it.
+    fn alloc<P>(&mut self, data: P) -> Result<BufferPoolSlice<T>, BufferCreationError>
+                where P: AsRef<[T]>
+    {
+        let data = data.as_ref();
+        let slice = try!(self.alloc_empty(data.len()));
+        slice.write(data);
+        Ok(slice)
+    }
+
+    /// Allocates a range of `len` elements of the pool. Its content is undefined.
+    fn alloc_empty(&mut self, len: usize) -> Result<BufferPoolSlice<T>, BufferCreationError> {
+        let size_bytes = cmp::max(round_up(len * mem::size_of::<T>(), self.alignment),
+                                  self.alignment);
+
+        for chunk in self.chunks.iter() {
+            if let Some(offset) = chunk.allocate(size_bytes) {
+                return Ok(BufferPoolSlice::new(chunk.clone(), offset, size_bytes, len));
+            }
+        }
+
+        let size = self.chunk_size(size_bytes);
+        let chunk = Rc::new(try!(self.new_chunk(size)));
+        let offset = chunk.allocate(size_bytes).unwrap();
+        self.chunks.push(chunk.clone());
+
+        Ok(BufferPoolSlice::new(chunk, offset, size_bytes, len))
+    }
+
+    /// Destroys the buffers of the pool that don't contain any allocation.
+    pub fn release_unused(&mut self) {
+        self.chunks.retain(|chunk| chunk.allocations.get() != 0);
+    }
+
+    /// Returns statistics about the memory used by the pool.
+    pub fn get_statistics(&self) -> BufferPoolStatistics {
+        let mut stats = BufferPoolStatistics {
+            buffers: self.chunks.len(),
+            total_bytes: 0,
+            used_bytes: 0,
+            allocations: 0,
+            free_ranges: 0,
+            largest_free_range: 0,
+        };
+
+        for chunk in self.chunks.iter() {
+            let free = chunk.free.borrow();
+            let free_bytes = free.iter().fold(0, |acc, r| acc + r.end - r.start);
+
+            stats.total_bytes += chunk.alloc.get_size();
+            stats.used_bytes += chunk.alloc.get_size() - free_bytes;
+            stats.allocations += chunk.allocations.get();
+            stats.free_ranges += free.len();
+
+            for range in free.iter() {
+                stats.largest_free_range = cmp::max(stats.largest_free_range,
+                                                    range.end - range.start);
+            }
+        }
+
+        stats
+    }
+
+    /// Returns the size in bytes of a new buffer that can contain an allocation of `min_bytes`.
+    fn chunk_size(&self, min_bytes: usize) -> usize {
+        cmp::max(round_up(self.chunk_len * mem::size_of::<T>(), self.alignment), min_bytes)
+    }
+
+    fn new_chunk(&self, size: usize) -> Result<Chunk, BufferCreationError> {
+        let alloc = try!(Buffer::empty(&self.context, self.ty, size, self.mode));
+
+        Ok(Chunk {
+            alloc: alloc,
+            free: RefCell::new(vec![0 .. size]),
+            allocations: Cell::new(0),
+        })
+    }
+}
+
+impl Chunk {
+    /// Finds a free range of `size` bytes and returns its offset.
+    fn allocate(&self, size: usize) -> Option<usize> {
+        let mut free = self.free.borrow_mut();
+
+        let position = match free.iter().position(|r| r.end - r.start >= size) {
+            Some(p) => p,
+            None => return None
+        };
+
+        let offset = free[position].start;
+
+        if free[position].end - offset == size {
+            free.remove(position);
+        } else {
+            free[position].start += size;
+        }
+
+        self.allocations.set(self.allocations.get() + 1);
+        Some(offset)
+    }
+
+    /// Gives back a range that has been returned by `allocate`.
+    fn deallocate(&self, range: Range<usize>) {
+        let mut free = self.free.borrow_mut();
+
+        let position = free.iter().position(|r| r.start > range.start).unwrap_or(free.len());
+        free.insert(position, range);
+
+        // merging with the next range
+        if position + 1 < free.len() && free[position].end == free[position + 1].start {
+            free[position].end = free[position + 1].end;
+            free.remove(position + 1);
+        }
+
+        // merging with the previous range
+        if position >= 1 && free[position - 1].end == free[position].start {
+            free[position - 1].end = free[position].end;
+            free.remove(position);
+        }
+
+        self.allocations.set(self.allocations.get() - 1);
+    }
+}
+
+impl<T> BufferPoolSlice<T> where T: Copy + Send + 'static {
+    fn new(chunk: Rc<Chunk>, offset_bytes: usize, size_bytes: usize, len: usize)
+           -> BufferPoolSlice<T>
+    {
+        BufferPoolSlice {
+            chunk: chunk,
+            offset_bytes: offset_bytes,
+            size_bytes: size_bytes,
+            len: len,
+            fence: RefCell::new(None),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements in this slice.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Uploads some data in this slice.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the length of `data` is different from the length of this slice.
+    pub fn write<P>(&self, data: P) where P: AsRef<[T]> {
+        self.as_slice().write(data)
+    }
+
+    /// Reads the content of the slice. Returns `None` if this operation is not supported.
+    pub fn read_if_supported(&self) -> Option<Vec<T>> {
+        self.as_slice().read_if_supported()
+    }
+
+    /// Maps the slice in memory.
+    pub fn map(&mut self) -> Mapping<T> {
+        view::consume_fence(self.chunk.alloc.get_context(), &self.fence);
+
+        // the range is only accessible through this object
+        unsafe { view::build_shared_mapping(&self.chunk.alloc, self.offset_bytes, self.len) }
+    }
+
+    /// Builds a `BufferViewSlice` covering this slice.
+    pub fn as_slice(&self) -> BufferViewSlice<T> {
+        view::build_slice(&self.chunk.alloc, self.offset_bytes, self.len, &self.fence)
+    }
+
+    /// Builds a slice-any covering this slice.
+    pub fn as_slice_any(&self) -> BufferViewAnySlice {
+        view::build_slice_any(&self.chunk.alloc, self.offset_bytes, mem::size_of::<T>(),
+                              self.len, &self.fence)
+    }
+}
+
+impl<T> Drop for BufferPoolSlice<T> where T: Copy + Send + 'static {
+    fn drop(&mut self) {
+        // the range must not be reused while the GPU is still reading it
+        view::consume_fence(self.chunk.alloc.get_context(), &self.fence);
+        self.chunk.deallocate(self.offset_bytes .. self.offset_bytes + self.size_bytes);
+    }
+}
+
+/// A `BufferPool` that contains vertices.
+///
+/// A reference to one of its slices can be used as a vertices source.
+#[derive(Debug)]
+pub struct VertexBufferPool<T> where T: Vertex + Send + 'static {
+    pool: BufferPool<T>,
+    bindings: Rc<VertexFormat>,
+}
+
+impl<T> VertexBufferPool<T> where T: Vertex + Send + 'static {
+    /// Builds a pool for vertices, whose buffers hold `chunk_len` vertices.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `chunk_len` is `0`.
+    pub fn new<F>(facade: &F, chunk_len: usize, mode: BufferMode)
+                  -> Result<VertexBufferPool<T>, BufferCreationError> where F: Facade
+    {
+        let pool = try!(BufferPool::new(facade, BufferType::ArrayBuffer, chunk_len, mode));
+
+        Ok(VertexBufferPool {
+            pool: pool,
+            bindings: Rc::new(<T as Vertex>::build_bindings()),
+        })
+    }
+
+    /// Allocates a range of the pool and writes `data` in it.
+    ///
+    /// Returns an error if a new buffer had to be created and its creation failed.
+    pub fn alloc<P>(&mut self, data: P) -> Result<VertexBufferPoolSlice<T>, BufferCreationError>
+                    where P: AsRef<[T]>
+    {
+        let slice = try!(self.pool.alloc(data));
+        Ok(VertexBufferPoolSlice { slice: slice, bindings: self.bindings.clone() })
+    }
+
+    /// Allocates a range of `len` vertices of the pool. Its content is undefined.
+    ///
+    /// Returns an error if a new buffer had to be created and its creation failed.
+    pub fn alloc_empty(&mut self, len: usize)
+                       -> Result<VertexBufferPoolSlice<T>, BufferCreationError>
+    {
+        let slice = try!(self.pool.alloc_empty(len));
+        Ok(VertexBufferPoolSlice { slice: slice, bindings: self.bindings.clone() })
+    }
+}
+
+impl<T> Deref for VertexBufferPool<T> where T: Vertex + Send + 'static {
+    type Target = BufferPool<T>;
+
+    fn deref(&self) -> &BufferPool<T> {
+        &self.pool
+    }
+}
+
+impl<T> DerefMut for VertexBufferPool<T> where T: Vertex + Send + 'static {
+    fn deref_mut(&mut self) -> &mut BufferPool<T> {
+        &mut self.pool
+    }
+}
+
+/// A range of vertices allocated from a `VertexBufferPool`.
+#[derive(Debug)]
+pub struct VertexBufferPoolSlice<T> where T: Vertex + Send + 'static {
+    slice: BufferPoolSlice<T>,
+    bindings: Rc<VertexFormat>,
+}
+
+impl<T> Deref for VertexBufferPoolSlice<T> where T: Vertex + Send + 'static {
+    type Target = BufferPoolSlice<T>;
+
+    fn deref(&self) -> &BufferPoolSlice<T> {
+        &self.slice
+    }
+}
+
+impl<T> DerefMut for VertexBufferPoolSlice<T> where T: Vertex + Send + 'static {
+    fn deref_mut(&mut self) -> &mut BufferPoolSlice<T> {
+        &mut self.slice
+    }
+}
+
+impl<'a, T> IntoVerticesSource<'a> for &'a VertexBufferPoolSlice<T>
+                                       where T: Vertex + Send + 'static
+{
+    fn into_vertices_source(self) -> VerticesSource<'a> {
+        VerticesSource::VertexBuffer(self.slice.as_slice_any(), &*self.bindings, None)
+    }
+}
+
+/// A `BufferPool` that contains indices.
+///
+/// A reference to one of its slices can be used as an indices source.
+#[derive(Debug)]
+pub struct IndexBufferPool<T> where T: Index {
+    pool: BufferPool<T>,
+    primitives: PrimitiveType,
+}
+
+impl<T> IndexBufferPool<T> where T: Index {
+    /// Builds a pool for indices, whose buffers hold `chunk_len` indices.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `chunk_len` is `0`.
+    pub fn new<F>(facade: &F, primitives: PrimitiveType, chunk_len: usize, mode: BufferMode)
+                  -> Result<IndexBufferPool<T>, BufferCreationError> where F: Facade
+    {
+        let pool = try!(BufferPool::new(facade, BufferType::ElementArrayBuffer, chunk_len,
+                                        mode));
+
+        Ok(IndexBufferPool {
+            pool: pool,
+            primitives: primitives,
+        })
+    }
+
+    /// Returns the type of primitives of the indices.
+    pub fn get_primitives_type(&self) -> PrimitiveType {
+        self.primitives
+    }
+
+    /// Allocates a range of the pool and writes `data` in it.
+    ///
+    /// Returns an error if a new buffer had to be created and its creation failed.
+    pub fn alloc<P>(&mut self, data: P) -> Result<IndexBufferPoolSlice<T>, BufferCreationError>
+                    where P: AsRef<[T]>
+    {
+        let slice = try!(self.pool.alloc(data));
+        Ok(IndexBufferPoolSlice { slice: slice, primitives: self.primitives })
+    }
+
+    /// Allocates a range of `len` indices of the pool. Its content is undefined.
+    ///
+    /// Returns an error if a new buffer had to be created and its creation failed.
+    pub fn alloc_empty(&mut self, len: usize)
+                       -> Result<IndexBufferPoolSlice<T>, BufferCreationError>
+    {
+        let slice = try!(self.pool.alloc_empty(len));
+        Ok(IndexBufferPoolSlice { slice: slice, primitives: self.primitives })
+    }
+}
+
+impl<T> Deref for IndexBufferPool<T> where T: Index {
+    type Target = BufferPool<T>;
+
+    fn deref(&self) -> &BufferPool<T> {
+        &self.pool
+    }
+}
+
+impl<T> DerefMut for IndexBufferPool<T> where T: Index {
+    fn deref_mut(&mut self) -> &mut BufferPool<T> {
+        &mut self.pool
+    }
+}
+
+/// A range of indices allocated from an `IndexBufferPool`.
+#[derive(Debug)]
+pub struct IndexBufferPoolSlice<T> where T: Index {
+    slice: BufferPoolSlice<T>,
+    primitives: PrimitiveType,
+}
+
+impl<T> Deref for IndexBufferPoolSlice<T> where T: Index {
+    type Target = BufferPoolSlice<T>;
+
+    fn deref(&self) -> &BufferPoolSlice<T> {
+        &self.slice
+    }
+}
+
+impl<T> DerefMut for IndexBufferPoolSlice<T> where T: Index {
+    fn deref_mut(&mut self) -> &mut BufferPoolSlice<T> {
+        &mut self.slice
+    }
+}
+
+impl<'a, T> From<&'a IndexBufferPoolSlice<T>> for IndicesSource<'a> where T: Index {
+    fn from(slice: &'a IndexBufferPoolSlice<T>) -> IndicesSource<'a> {
+        IndicesSource::IndexBuffer {
+            buffer: slice.slice.as_slice_any(),
+            data_type: <T as Index>::get_type(),
+            primitives: slice.primitives,
+        }
+    }
+}
+
+/// A `BufferPool` that contains uniform blocks.
+///
+/// The allocations are aligned to the `GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT` of the backend, and a
+/// reference to one of its slices can be used as a uniform block.
+#[derive(Debug)]
+pub struct UniformBufferPool<T> where T: Copy + Send + 'static {
+    pool: BufferPool<T>,
+}
+
+impl<T> UniformBufferPool<T> where T: Copy + Send + 'static {
+    /// Builds a pool for uniform blocks, whose buffers hold `chunk_len` blocks.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `chunk_len` is `0`.
+    pub fn new<F>(facade: &F, chunk_len: usize, mode: BufferMode)
+                  -> Result<UniformBufferPool<T>, BufferCreationError> where F: Facade
+    {
+        let pool = try!(BufferPool::new(facade, BufferType::UniformBuffer, chunk_len, mode));
+        Ok(UniformBufferPool { pool: pool })
+    }
+
+    /// Allocates a range of the pool and writes `data` in it.
+    ///
+    /// Returns an error if a new buffer had to be created and its creation failed.
+    pub fn alloc<P>(&mut self, data: P)
+                    -> Result<UniformBufferPoolSlice<T>, BufferCreationError>
+                    where P: AsRef<[T]>
+    {
+        let slice = try!(self.pool.alloc(data));
+        Ok(UniformBufferPoolSlice { slice: slice })
+    }
+
+    /// Allocates a range of `len` blocks of the pool. Its content is undefined.
+    ///
+    /// Returns an error if a new buffer had to be created and its creation failed.
+    pub fn alloc_empty(&mut self, len: usize)
+                       -> Result<UniformBufferPoolSlice<T>, BufferCreationError>
+    {
+        let slice = try!(self.pool.alloc_empty(len));
+        Ok(UniformBufferPoolSlice { slice: slice })
+    }
+}
+
+impl<T> Deref for UniformBufferPool<T> where T: Copy + Send + 'static {
+    type Target = BufferPool<T>;
+
+    fn deref(&self) -> &BufferPool<T> {
+        &self.pool
+    }
+}
+
+impl<T> DerefMut for UniformBufferPool<T> where T: Copy + Send + 'static {
+    fn deref_mut(&mut self) -> &mut BufferPool<T> {
+        &mut self.pool
+    }
+}
+
+/// A range of uniform blocks allocated from a `UniformBufferPool`.
+#[derive(Debug)]
+pub struct UniformBufferPoolSlice<T> where T: Copy + Send + 'static {
+    slice: BufferPoolSlice<T>,
+}
+
+impl<T> Deref for UniformBufferPoolSlice<T> where T: Copy + Send + 'static {
+    type Target = BufferPoolSlice<T>;
+
+    fn deref(&self) -> &BufferPoolSlice<T> {
+        &self.slice
+    }
+}
+
+impl<T> DerefMut for UniformBufferPoolSlice<T> where T: Copy + Send + 'static {
+    fn deref_mut(&mut self) -> &mut BufferPoolSlice<T> {
+        &mut self.slice
+    }
+}
+
+impl<'a, T> AsUniformValue for &'a UniformBufferPoolSlice<T>
+                           where T: UniformBlock + Send + Copy + 'static
+{
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Block(self.slice.as_slice_any(), <T as UniformBlock>::matches)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+/// Rounds `value` up to a multiple of `multiple`.
+fn round_up(value: usize, multiple: usize) -> usize {
+    (value + multiple - 1) / multiple * multiple
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+
+    while y != 0 {
+        let t = x % y;
+        x = y;
+        y = t;
+    }
+
+    a / x * b
+}
@@ -0,0 +1,358 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+
+use backend::Facade;
+use index::{Index, IndicesSource, PrimitiveType};
+use sync::{self, LinearSyncFence};
+use vertex::{Vertex, VertexFormat, VerticesSource, IntoVerticesSource};
+
+use buffer::BufferType;
+use buffer::BufferMode;
+use buffer::BufferCreationError;
+use buffer::alloc::Buffer;
+use buffer::view::{self, BufferViewAnySlice, Mapping};
+
+/// A buffer split in multiple regions, for data that is rewritten every frame.
+///
+/// Each call to `next_region` returns the next region of the buffer, going back to the first
+/// region after the last one. When you draw with a region, a fence is put in place so that the
+/// region is not overwritten while the GPU is still reading it. If the GPU has not finished, the
+/// call to `next_region` that returns this region again blocks until it has.
+///
+/// With two or three regions, the CPU can write the data of the next frame while the GPU
+/// draws the previous ones. The buffer uses persistent mapping if the backend supports it.
+///
+/// This type contains what is common to `StreamingVertexBuffer` and `StreamingIndexBuffer`,
+/// which both deref to it.
+pub struct StreamingBuffer<T> where T: Copy + Send + 'static {
+    alloc: Buffer,
+    region_len: usize,
+    fences: Vec<RefCell<Option<LinearSyncFence>>>,
+    next_region: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T> fmt::Debug for StreamingBuffer<T> where T: Copy + Send + 'static {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "StreamingBuffer({:?}, {} regions)", self.alloc, self.fences.len())
+    }
+}
+
+impl<T> Drop for StreamingBuffer<T> where T: Copy + Send + 'static {
+    fn drop(&mut self) {
+        let mut ctxt = self.alloc.get_context().make_current();
+
+        for fence in self.fences.iter() {
+            if let Some(fence) = fence.borrow_mut().take() {
+                unsafe { sync::destroy_linear_sync_fence(&mut ctxt, fence) };
+            }
+        }
+    }
+}
+
+impl<T> StreamingBuffer<T> where T: Copy + Send + 'static {
+    fn new<F>(facade: &F, ty: BufferType, region_len: usize, regions: usize)
+              -> Result<StreamingBuffer<T>, BufferCreationError> where F: Facade
+    {
+        if regions == 0 {
+            return Err(BufferCreationError::NoRegions);
+        }
+
+        let size = match region_len.checked_mul(regions)
+                                   .and_then(|len| len.checked_mul(mem::size_of::<T>()))
+        {
+            Some(size) => size,
+            None => return Err(BufferCreationError::OutOfMemory),
+        };
+
+        let alloc = try!(Buffer::empty(facade, ty, size, BufferMode::Dynamic));
+
+        Ok(StreamingBuffer {
+            alloc: alloc,
+            region_len: region_len,
+            fences: (0 .. regions).map(|_| RefCell::new(None)).collect(),
+            next_region: 0,
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns the number of elements in each region.
+    pub fn region_len(&self) -> usize {
+        self.region_len
+    }
+
+    /// Returns the number of regions.
+    pub fn regions_count(&self) -> usize {
+        self.fences.len()
+    }
+
+    /// Returns true if this buffer uses persistent mapping.
+    pub fn is_persistent(&self) -> bool {
+        self.alloc.uses_persistent_mapping()
+    }
+
+    /// Returns the next region of the buffer.
+    ///
+    /// Blocks until the GPU has finished reading the region if necessary.
+    fn next_region(&mut self) -> StreamingRegion<T> {
+        let region = self.next_region;
+        self.next_region = (region + 1) % self.fences.len();
+
+        let fence = &self.fences[region];
+        view::consume_fence(self.alloc.get_context(), fence);
+
+        StreamingRegion {
+            alloc: &mut self.alloc,
+            offset_bytes: region * self.region_len * mem::size_of::<T>(),
+            capacity: self.region_len,
+            len: 0,
+            fence: fence,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A `StreamingBuffer` that contains vertices.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {
+/// # use glium::Surface;
+/// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+/// # let program: glium::Program = unsafe { std::mem::uninitialized() };
+/// #[derive(Copy, Clone)]
+/// struct Vertex {
+///     position: [f32; 2],
+/// }
+///
+/// implement_vertex!(Vertex, position);
+///
+/// let mut buffer = glium::buffer::StreamingVertexBuffer::<Vertex>::new(&display, 1024, 3)
+///                                                                    .unwrap();
+///
+/// loop {
+///     let mut region = buffer.next_region();
+///     region.write(&[Vertex { position: [0.0, 0.0] }, Vertex { position: [1.0, 0.0] },
+///                    Vertex { position: [0.0, 1.0] }]);
+///
+///     let mut target = display.draw();
+///     target.draw(&region, &glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
+///                 &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+///     target.finish().unwrap();
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StreamingVertexBuffer<T> where T: Vertex + Send + 'static {
+    buffer: StreamingBuffer<T>,
+    bindings: VertexFormat,
+}
+
+impl<T> StreamingVertexBuffer<T> where T: Vertex + Send + 'static {
+    /// Builds a streaming buffer for vertices, with `regions` regions of `region_len` vertices.
+    ///
+    /// Returns `NoRegions` if `regions` is `0`, and `OutOfMemory` if the size of the buffer
+    /// doesn't fit in a `usize`.
+    pub fn new<F>(facade: &F, region_len: usize, regions: usize)
+                  -> Result<StreamingVertexBuffer<T>, BufferCreationError> where F: Facade
+    {
+        let buffer = try!(StreamingBuffer::new(facade, BufferType::ArrayBuffer,
+                                               region_len, regions));
+
+        Ok(StreamingVertexBuffer {
+            buffer: buffer,
+            bindings: <T as Vertex>::build_bindings(),
+        })
+    }
+
+    /// Returns the next region of the buffer.
+    ///
+    /// Blocks until the GPU has finished reading the region if necessary.
+    pub fn next_region(&mut self) -> StreamingVertexRegion<T> {
+        StreamingVertexRegion {
+            region: self.buffer.next_region(),
+            bindings: &self.bindings,
+        }
+    }
+}
+
+impl<T> Deref for StreamingVertexBuffer<T> where T: Vertex + Send + 'static {
+    type Target = StreamingBuffer<T>;
+
+    fn deref(&self) -> &StreamingBuffer<T> {
+        &self.buffer
+    }
+}
+
+/// A `StreamingBuffer` that contains indices.
+#[derive(Debug)]
+pub struct StreamingIndexBuffer<T> where T: Index {
+    buffer: StreamingBuffer<T>,
+    primitives: PrimitiveType,
+}
+
+impl<T> StreamingIndexBuffer<T> where T: Index {
+    /// Builds a streaming buffer for indices, with `regions` regions of `region_len` indices.
+    ///
+    /// Returns `NoRegions` if `regions` is `0`, and `OutOfMemory` if the size of the buffer
+    /// doesn't fit in a `usize`.
+    pub fn new<F>(facade: &F, primitives: PrimitiveType, region_len: usize, regions: usize)
+                  -> Result<StreamingIndexBuffer<T>, BufferCreationError> where F: Facade
+    {
+        let buffer = try!(StreamingBuffer::new(facade, BufferType::ElementArrayBuffer,
+                                               region_len, regions));
+
+        Ok(StreamingIndexBuffer {
+            buffer: buffer,
+            primitives: primitives,
+        })
+    }
+
+    /// Returns the type of primitives of the indices.
+    pub fn get_primitives_type(&self) -> PrimitiveType {
+        self.primitives
+    }
+
+    /// Returns the next region of the buffer.
+    ///
+    /// Blocks until the GPU has finished reading the region if necessary.
+    pub fn next_region(&mut self) -> StreamingIndexRegion<T> {
+        StreamingIndexRegion {
+            region: self.buffer.next_region(),
+            primitives: self.primitives,
+        }
+    }
+}
+
+impl<T> Deref for StreamingIndexBuffer<T> where T: Index {
+    type Target = StreamingBuffer<T>;
+
+    fn deref(&self) -> &StreamingBuffer<T> {
+        &self.buffer
+    }
+}
+
+/// A region of a `StreamingBuffer`.
+///
+/// The region initially contains no element. Use `write` or `map` to fill it.
+pub struct StreamingRegion<'a, T> where T: Copy + Send + 'static {
+    alloc: &'a mut Buffer,
+    offset_bytes: usize,
+    capacity: usize,
+    len: usize,
+    fence: &'a RefCell<Option<LinearSyncFence>>,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T> StreamingRegion<'a, T> where T: Copy + Send + 'static {
+    /// Returns the maximum number of elements in this region.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of elements that have been written in this region.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Uploads some data at the start of the region. The region then contains the elements of
+    /// `data`.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `data` contains more elements than the capacity of the region.
+    pub fn write<P>(&mut self, data: P) where P: AsRef<[T]> {
+        let data = data.as_ref();
+        assert!(data.len() <= self.capacity);
+
+        // the synchronization has been done by `next_region`
+        unsafe { self.alloc.upload(self.offset_bytes, data); }
+        self.len = data.len();
+    }
+
+    /// Maps the first `len` elements of the region in memory. The region then contains these
+    /// elements.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `len` is larger than the capacity of the region.
+    pub fn map(&mut self, len: usize) -> Mapping<T> {
+        assert!(len <= self.capacity);
+        self.len = len;
+
+        // the synchronization has been done by `next_region`
+        unsafe { view::build_mapping(self.alloc, self.offset_bytes, len) }
+    }
+
+    fn as_slice_any(&self) -> BufferViewAnySlice {
+        view::build_slice_any(&*self.alloc, self.offset_bytes, mem::size_of::<T>(), self.len,
+                              self.fence)
+    }
+}
+
+/// A region of a `StreamingVertexBuffer`.
+///
+/// Pass a reference to the region as a vertices source once it has been filled.
+pub struct StreamingVertexRegion<'a, T> where T: Vertex + Send + 'static {
+    region: StreamingRegion<'a, T>,
+    bindings: &'a VertexFormat,
+}
+
+impl<'a, T> Deref for StreamingVertexRegion<'a, T> where T: Vertex + Send + 'static {
+    type Target = StreamingRegion<'a, T>;
+
+    fn deref(&self) -> &StreamingRegion<'a, T> {
+        &self.region
+    }
+}
+
+impl<'a, T> DerefMut for StreamingVertexRegion<'a, T> where T: Vertex + Send + 'static {
+    fn deref_mut(&mut self) -> &mut StreamingRegion<'a, T> {
+        &mut self.region
+    }
+}
+
+impl<'a, 'b, T> IntoVerticesSource<'b> for &'b StreamingVertexRegion<'a, T>
+                                           where T: Vertex + Send + 'static
+{
+    fn into_vertices_source(self) -> VerticesSource<'b> {
+        VerticesSource::VertexBuffer(self.region.as_slice_any(), self.bindings, None)
+    }
+}
+
+/// A region of a `StreamingIndexBuffer`.
+///
+/// Pass a reference to the region as an indices source once it has been filled.
+pub struct StreamingIndexRegion<'a, T> where T: Index {
+    region: StreamingRegion<'a, T>,
+    primitives: PrimitiveType,
+}
+
+impl<'a, T> Deref for StreamingIndexRegion<'a, T> where T: Index {
+    type Target = StreamingRegion<'a, T>;
+
+    fn deref(&self) -> &StreamingRegion<'a, T> {
+        &self.region
+    }
+}
+
+impl<'a, T> DerefMut for StreamingIndexRegion<'a, T> where T: Index {
+    fn deref_mut(&mut self) -> &mut StreamingRegion<'a, T> {
+        &mut self.region
+    }
+}
+
+impl<'a, 'b, T> From<&'b StreamingIndexRegion<'a, T>> for IndicesSource<'b> where T: Index {
+    fn from(region: &'b StreamingIndexRegion<'a, T>) -> IndicesSource<'b> {
+        IndicesSource::IndexBuffer {
+            buffer: region.region.as_slice_any(),
+            data_type: <T as Index>::get_type(),
+            primitives: region.primitives,
+        }
+    }
+}
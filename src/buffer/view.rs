@@ -21,7 +21,9 @@ use std::rc::Rc;
 use ContextExt;
 
 use buffer::BufferType;
+use buffer::BufferMode;
 use buffer::BufferCreationError;
+use buffer::CopyError;
 use buffer::alloc::Buffer;
 use buffer::alloc::Mapping as BufferMapping;
 
@@ -56,6 +58,22 @@ pub struct Mapping<'a, T> {
     mapping: BufferMapping<'a, T>,
 }
 
+impl<'a, T> Mapping<'a, T> {
+    /// Makes the modifications of a range of elements visible to the GPU.
+    ///
+    /// This is only useful for buffers created with `BufferMode::Persistent` or
+    /// `BufferMode::Dynamic`. By default the whole mapping is flushed when it is destroyed, but
+    /// once this function has been called only the ranges passed to it are flushed. For other
+    /// modes this is a no-op.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn flush_range(&mut self, range: Range<usize>) {
+        self.mapping.flush_range(range)
+    }
+}
+
 impl<'a, T> Deref for Mapping<'a, T> {
     type Target = [T];
     fn deref<'b>(&'b self) -> &'b [T] {
@@ -158,18 +176,14 @@ impl<T> BufferView<T> where T: Copy + Send + 'static {
     /// Builds a new buffer containing the given data. The size of the buffer is equal to the size
     /// of the data.
     ///
-    /// If `dynamic` is false, glium will attempt to use buffer storage to create an immutable
-    /// buffer (without the `DYNAMIC_STORAGE_FLAG`).
-    ///
-    /// If `dynamic` is true, glium will attempt to use persistent mapping and manage
-    /// synchronizations manually.
-    pub fn new<F>(facade: &F, data: &[T], ty: BufferType, dynamic: bool)
+    /// See the documentation of `BufferMode` for the meaning of `mode`.
+    pub fn new<F>(facade: &F, data: &[T], ty: BufferType, mode: BufferMode)
                   -> Result<BufferView<T>, BufferCreationError>
                   where F: Facade
     {
         let len = data.len();
 
-        Buffer::new(facade, data, ty, dynamic)
+        Buffer::new(facade, data, ty, mode)
             .map(|buffer| {
                 BufferView {
                     alloc: Some(buffer),
@@ -182,15 +196,11 @@ impl<T> BufferView<T> where T: Copy + Send + 'static {
 
     /// Builds a new buffer of the given size.
     ///
-    /// If `dynamic` is false, glium will attempt to use buffer storage to create an immutable
-    /// buffer (without the `DYNAMIC_STORAGE_FLAG`).
-    ///
-    /// If `dynamic` is true, glium will attempt to use persistent mapping and manage
-    /// synchronizations manually.
-    pub fn empty<F>(facade: &F, ty: BufferType, len: usize, dynamic: bool)
+    /// See the documentation of `BufferMode` for the meaning of `mode`.
+    pub fn empty<F>(facade: &F, ty: BufferType, len: usize, mode: BufferMode)
                     -> Result<BufferView<T>, BufferCreationError> where F: Facade
     {
-        Buffer::empty(facade, ty, len * mem::size_of::<T>(), dynamic)
+        Buffer::empty(facade, ty, len * mem::size_of::<T>(), mode)
             .map(|buffer| {
                 BufferView {
                     alloc: Some(buffer),
@@ -235,6 +245,21 @@ impl<T> BufferView<T> where T: Copy + Send + 'static {
         self.as_slice().invalidate()
     }
 
+    /// Copies the content of this buffer to `target`. The copy is done by the GPU.
+    ///
+    /// Returns `Err` if the backend doesn't support copying between buffers, if the length of
+    /// `target` is different from the length of this buffer, or if `target` doesn't start at a
+    /// multiple of the size of the elements in its buffer.
+    pub fn copy_to(&self, target: &BufferViewMutSlice<T>) -> Result<(), CopyError> {
+        self.as_slice().copy_to(target)
+    }
+
+    /// Fills the buffer with copies of `value`, without uploading a whole array if the backend
+    /// supports `glClearBufferSubData`.
+    pub fn clear_to(&self, value: T) {
+        self.as_slice().clear_to(value)
+    }
+
     /// Reads the content of the buffer.
     ///
     /// # Features
@@ -347,6 +372,21 @@ impl<'a, T> BufferViewSlice<'a, T> where T: Copy + Send + 'static {
         self.alloc.invalidate(self.offset_bytes, self.num_elements * mem::size_of::<T>());
     }
 
+    /// Copies the content of this slice to `target`. The copy is done by the GPU.
+    ///
+    /// Returns `Err` if the backend doesn't support copying between buffers, if the length of
+    /// `target` is different from the length of this slice, or if one of the two slices doesn't
+    /// start at a multiple of the size of the elements in its buffer.
+    pub fn copy_to(&self, target: &BufferViewMutSlice<T>) -> Result<(), CopyError> {
+        copy_to(self.alloc, self.offset_bytes, self.num_elements, self.fence, target)
+    }
+
+    /// Fills the slice with copies of `value`, without uploading a whole array if the backend
+    /// supports `glClearBufferSubData`.
+    pub fn clear_to(&self, value: T) {
+        clear_to(self.alloc, self.offset_bytes, self.num_elements, self.fence, value)
+    }
+
     /// Reads the content of the buffer.
     #[cfg(feature = "gl_read_buffer")]
     pub fn read(&self) -> Vec<T> {
@@ -453,6 +493,21 @@ impl<'a, T> BufferViewMutSlice<'a, T> where T: Copy + Send + 'static {
         self.alloc.invalidate(self.offset_bytes, self.num_elements * mem::size_of::<T>());
     }
 
+    /// Copies the content of this slice to `target`. The copy is done by the GPU.
+    ///
+    /// Returns `Err` if the backend doesn't support copying between buffers, if the length of
+    /// `target` is different from the length of this slice, or if one of the two slices doesn't
+    /// start at a multiple of the size of the elements in its buffer.
+    pub fn copy_to(&self, target: &BufferViewMutSlice<T>) -> Result<(), CopyError> {
+        copy_to(&*self.alloc, self.offset_bytes, self.num_elements, self.fence, target)
+    }
+
+    /// Fills the slice with copies of `value`, without uploading a whole array if the backend
+    /// supports `glClearBufferSubData`.
+    pub fn clear_to(&self, value: T) {
+        clear_to(&*self.alloc, self.offset_bytes, self.num_elements, self.fence, value)
+    }
+
     /// Reads the content of the buffer.
     #[cfg(feature = "gl_read_buffer")]
     pub fn read(&self) -> Vec<T> {
@@ -665,6 +720,15 @@ impl<T> BufferViewExt for BufferView<T> where T: Copy + Send + 'static {
         Buffer::unbind_pixel_unpack(ctxt)
     }
 
+    fn prepare_and_bind_for_query(&self, ctxt: &mut CommandContext) {
+        let alloc = self.alloc.as_ref().unwrap();
+        alloc.prepare_and_bind_for_query(ctxt);
+    }
+
+    fn unbind_query(ctxt: &mut CommandContext) {
+        Buffer::unbind_query(ctxt)
+    }
+
     fn prepare_and_bind_for_draw_indirect(&self, ctxt: &mut CommandContext) {
         let alloc = self.alloc.as_ref().unwrap();
         alloc.prepare_and_bind_for_draw_indirect(ctxt);
@@ -680,6 +744,11 @@ impl<T> BufferViewExt for BufferView<T> where T: Copy + Send + 'static {
         alloc.prepare_and_bind_for_shared_storage(ctxt, index, 0 .. alloc.get_size());
     }
 
+    fn prepare_and_bind_for_atomic_counter(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
+        let alloc = self.alloc.as_ref().unwrap();
+        alloc.prepare_and_bind_for_atomic_counter(ctxt, index, 0 .. alloc.get_size());
+    }
+
     fn bind_to_transform_feedback(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
         let alloc = self.alloc.as_ref().unwrap();
         alloc.bind_to_transform_feedback(ctxt, index, 0 .. alloc.get_size());
@@ -733,16 +802,31 @@ impl<'a, T> BufferViewExt for BufferViewSlice<'a, T> where T: Copy + Send + 'sta
         Buffer::unbind_pixel_unpack(ctxt)
     }
 
+    fn prepare_and_bind_for_query(&self, ctxt: &mut CommandContext) {
+        self.alloc.prepare_and_bind_for_query(ctxt);
+    }
+
+    fn unbind_query(ctxt: &mut CommandContext) {
+        Buffer::unbind_query(ctxt)
+    }
+
     fn prepare_and_bind_for_draw_indirect(&self, ctxt: &mut CommandContext) {
         self.alloc.prepare_and_bind_for_draw_indirect(ctxt);
     }
 
     fn prepare_and_bind_for_uniform(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_uniform(ctxt, index, 0 .. self.alloc.get_size());
+        let range = self.offset_bytes .. self.offset_bytes + self.num_elements * mem::size_of::<T>();
+        self.alloc.prepare_and_bind_for_uniform(ctxt, index, range);
     }
 
     fn prepare_and_bind_for_shared_storage(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_shared_storage(ctxt, index, 0 .. self.alloc.get_size());
+        let range = self.offset_bytes .. self.offset_bytes + self.num_elements * mem::size_of::<T>();
+        self.alloc.prepare_and_bind_for_shared_storage(ctxt, index, range);
+    }
+
+    fn prepare_and_bind_for_atomic_counter(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
+        let range = self.offset_bytes .. self.offset_bytes + self.num_elements * mem::size_of::<T>();
+        self.alloc.prepare_and_bind_for_atomic_counter(ctxt, index, range);
     }
 
     fn bind_to_transform_feedback(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
@@ -787,6 +871,14 @@ impl BufferViewExt for BufferViewAny {
         Buffer::unbind_pixel_unpack(ctxt)
     }
 
+    fn prepare_and_bind_for_query(&self, ctxt: &mut CommandContext) {
+        self.alloc.prepare_and_bind_for_query(ctxt);
+    }
+
+    fn unbind_query(ctxt: &mut CommandContext) {
+        Buffer::unbind_query(ctxt)
+    }
+
     fn prepare_and_bind_for_draw_indirect(&self, ctxt: &mut CommandContext) {
         self.alloc.prepare_and_bind_for_draw_indirect(ctxt);
     }
@@ -799,6 +891,10 @@ impl BufferViewExt for BufferViewAny {
         self.alloc.prepare_and_bind_for_shared_storage(ctxt, index, 0 .. self.alloc.get_size());
     }
 
+    fn prepare_and_bind_for_atomic_counter(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
+        self.alloc.prepare_and_bind_for_atomic_counter(ctxt, index, 0 .. self.alloc.get_size());
+    }
+
     fn bind_to_transform_feedback(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
         self.alloc.bind_to_transform_feedback(ctxt, index, 0 .. self.alloc.get_size());
     }
@@ -851,27 +947,147 @@ impl<'a> BufferViewExt for BufferViewAnySlice<'a> {
         Buffer::unbind_pixel_unpack(ctxt)
     }
 
+    fn prepare_and_bind_for_query(&self, ctxt: &mut CommandContext) {
+        self.alloc.prepare_and_bind_for_query(ctxt);
+    }
+
+    fn unbind_query(ctxt: &mut CommandContext) {
+        Buffer::unbind_query(ctxt)
+    }
+
     fn prepare_and_bind_for_draw_indirect(&self, ctxt: &mut CommandContext) {
         self.alloc.prepare_and_bind_for_draw_indirect(ctxt);
     }
 
     fn prepare_and_bind_for_uniform(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_uniform(ctxt, index, 0 .. self.alloc.get_size());
+        let range = self.offset_bytes .. self.offset_bytes + self.get_size();
+        self.alloc.prepare_and_bind_for_uniform(ctxt, index, range);
     }
 
     fn prepare_and_bind_for_shared_storage(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_shared_storage(ctxt, index, 0 .. self.alloc.get_size());
+        let range = self.offset_bytes .. self.offset_bytes + self.get_size();
+        self.alloc.prepare_and_bind_for_shared_storage(ctxt, index, range);
+    }
+
+    fn prepare_and_bind_for_atomic_counter(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
+        let range = self.offset_bytes .. self.offset_bytes + self.get_size();
+        self.alloc.prepare_and_bind_for_atomic_counter(ctxt, index, range);
     }
 
     fn bind_to_transform_feedback(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
-        self.alloc.bind_to_transform_feedback(ctxt, index, 0 .. self.alloc.get_size());
+        let range = self.offset_bytes .. self.offset_bytes + self.get_size();
+        self.alloc.bind_to_transform_feedback(ctxt, index, range);
+    }
+}
+
+/// Builds a slice-any of a part of a buffer, protected by the given fence.
+pub fn build_slice_any<'a>(alloc: &'a Buffer, offset_bytes: usize, elements_size: usize,
+                           elements_count: usize, fence: &'a RefCell<Option<LinearSyncFence>>)
+                           -> BufferViewAnySlice<'a>
+{
+    BufferViewAnySlice {
+        alloc: alloc,
+        offset_bytes: offset_bytes,
+        elements_size: elements_size,
+        elements_count: elements_count,
+        fence: fence,
+    }
+}
+
+/// Builds a slice of a part of a buffer, protected by the given fence.
+pub fn build_slice<'a, T>(alloc: &'a Buffer, offset_bytes: usize, elements_count: usize,
+                          fence: &'a RefCell<Option<LinearSyncFence>>) -> BufferViewSlice<'a, T>
+                          where T: Copy + Send + 'static
+{
+    BufferViewSlice {
+        alloc: alloc,
+        offset_bytes: offset_bytes,
+        num_elements: elements_count,
+        fence: fence,
+        marker: PhantomData,
+    }
+}
+
+/// Maps a part of a buffer that is shared with other objects in memory. The caller must make
+/// sure that nothing else accesses this part of the buffer and must handle synchronization.
+pub unsafe fn build_shared_mapping<T>(alloc: &Buffer, offset_bytes: usize, elements: usize)
+                                      -> Mapping<T> where T: Copy + Send + 'static
+{
+    Mapping {
+        mapping: alloc.map(offset_bytes, elements),
+    }
+}
+
+/// Maps a part of a buffer in memory. The caller must handle synchronization.
+pub unsafe fn build_mapping<T>(alloc: &mut Buffer, offset_bytes: usize, elements: usize)
+                               -> Mapping<T> where T: Copy + Send + 'static
+{
+    Mapping {
+        mapping: alloc.map_mut(offset_bytes, elements),
     }
 }
 
 /// Waits for the fence to be sync'ed.
-fn consume_fence(context: &Rc<Context>, fence: &RefCell<Option<LinearSyncFence>>) {
+pub fn consume_fence(context: &Rc<Context>, fence: &RefCell<Option<LinearSyncFence>>) {
     let fence = fence.borrow_mut().take();
     if let Some(fence) = fence {
         fence.into_sync_fence(context).wait();
     }
 }
+
+/// Copies a part of `alloc` to `target` and puts fences in place for persistent-mapped buffers.
+fn copy_to<T>(alloc: &Buffer, offset_bytes: usize, num_elements: usize,
+              fence: &RefCell<Option<LinearSyncFence>>, target: &BufferViewMutSlice<T>)
+              -> Result<(), CopyError> where T: Copy + Send + 'static
+{
+    if target.num_elements != num_elements {
+        return Err(CopyError::LengthMismatch);
+    }
+
+    let element_size = mem::size_of::<T>();
+    if element_size != 0 && (offset_bytes % element_size != 0 ||
+                             target.offset_bytes % element_size != 0)
+    {
+        return Err(CopyError::Misaligned);
+    }
+
+    let size = num_elements * element_size;
+    try!(alloc.copy_to(offset_bytes .. offset_bytes + size, &*target.alloc,
+                       target.offset_bytes));
+
+    insert_fence(alloc, fence);
+    insert_fence(&*target.alloc, target.fence);
+    Ok(())
+}
+
+/// Fills a part of `alloc` with `value` and puts a fence in place for persistent-mapped buffers.
+fn clear_to<T>(alloc: &Buffer, offset_bytes: usize, num_elements: usize,
+               fence: &RefCell<Option<LinearSyncFence>>, value: T)
+               where T: Copy + Send + 'static
+{
+    // the data may be written through the persistent mapping
+    consume_fence(alloc.get_context(), fence);
+
+    unsafe { alloc.clear(offset_bytes, num_elements, value); }
+    insert_fence(alloc, fence);
+}
+
+/// Puts a fence after the latest commands if the buffer uses persistent mapping, so that it isn't
+/// modified by the CPU while the GPU is still using it.
+fn insert_fence(alloc: &Buffer, fence: &RefCell<Option<LinearSyncFence>>) {
+    if !alloc.uses_persistent_mapping() {
+        return;
+    }
+
+    let mut ctxt = alloc.get_context().make_current();
+
+    let mut new_fence = Some(unsafe {
+        sync::new_linear_sync_fence_if_supported(&mut ctxt)
+    }.unwrap());
+
+    mem::swap(&mut new_fence, &mut *fence.borrow_mut());
+
+    if let Some(new_fence) = new_fence {
+        unsafe { sync::destroy_linear_sync_fence(&mut ctxt, new_fence) };
+    }
+}
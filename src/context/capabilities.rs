@@ -47,6 +47,22 @@ pub struct Capabilities {
     /// Maximum number of elements that can be passed with `glDrawBuffers`.
     pub max_draw_buffers: gl::types::GLint,
 
+    /// Maximum width of a framebuffer without any attachment. `None` if framebuffers without
+    /// attachments are not supported.
+    pub max_framebuffer_width: Option<gl::types::GLint>,
+
+    /// Maximum height of a framebuffer without any attachment. `None` if framebuffers without
+    /// attachments are not supported.
+    pub max_framebuffer_height: Option<gl::types::GLint>,
+
+    /// Maximum number of layers of a framebuffer without any attachment. `None` if
+    /// framebuffers without attachments are not supported or can't be layered.
+    pub max_framebuffer_layers: Option<gl::types::GLint>,
+
+    /// Maximum number of samples of a framebuffer without any attachment. `None` if
+    /// framebuffers without attachments are not supported.
+    pub max_framebuffer_samples: Option<gl::types::GLint>,
+
     /// Maximum number of vertices per patch. `None` if tessellation is not supported.
     pub max_patch_vertices: Option<gl::types::GLint>,
 
@@ -62,6 +78,9 @@ pub struct Capabilities {
     /// Number of available buffer bind points for `GL_UNIFORM_BUFFER`.
     pub max_indexed_uniform_buffer: gl::types::GLint,
 
+    /// Required alignment of the offset of a range bound to `GL_UNIFORM_BUFFER`, in bytes.
+    pub uniform_buffer_offset_alignment: gl::types::GLint,
+
     /// Number of work groups for compute shaders.
     pub max_compute_work_group_count: (gl::types::GLint, gl::types::GLint, gl::types::GLint),
 }
@@ -245,6 +264,49 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        max_framebuffer_width: if version >= &Version(Api::Gl, 4, 3) ||
+                                  version >= &Version(Api::GlEs, 3, 1) ||
+                                  extensions.gl_arb_framebuffer_no_attachments
+        {
+            let mut val = mem::uninitialized();
+            gl.GetIntegerv(gl::MAX_FRAMEBUFFER_WIDTH, &mut val);
+            Some(val)
+        } else {
+            None
+        },
+
+        max_framebuffer_height: if version >= &Version(Api::Gl, 4, 3) ||
+                                   version >= &Version(Api::GlEs, 3, 1) ||
+                                   extensions.gl_arb_framebuffer_no_attachments
+        {
+            let mut val = mem::uninitialized();
+            gl.GetIntegerv(gl::MAX_FRAMEBUFFER_HEIGHT, &mut val);
+            Some(val)
+        } else {
+            None
+        },
+
+        max_framebuffer_layers: if version >= &Version(Api::Gl, 4, 3) ||
+                                   extensions.gl_arb_framebuffer_no_attachments
+        {
+            let mut val = mem::uninitialized();
+            gl.GetIntegerv(gl::MAX_FRAMEBUFFER_LAYERS, &mut val);
+            Some(val)
+        } else {
+            None
+        },
+
+        max_framebuffer_samples: if version >= &Version(Api::Gl, 4, 3) ||
+                                    version >= &Version(Api::GlEs, 3, 1) ||
+                                    extensions.gl_arb_framebuffer_no_attachments
+        {
+            let mut val = mem::uninitialized();
+            gl.GetIntegerv(gl::MAX_FRAMEBUFFER_SAMPLES, &mut val);
+            Some(val)
+        } else {
+            None
+        },
+
         max_patch_vertices: if version >= &Version(Api::Gl, 4, 0) ||
             extensions.gl_arb_tessellation_shader
         {
@@ -258,7 +320,10 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             None
         },
 
-        max_indexed_atomic_counter_buffer: if version >= &Version(Api::Gl, 4, 2) {      // TODO: ARB_shader_atomic_counters   // TODO: GLES
+        max_indexed_atomic_counter_buffer: if version >= &Version(Api::Gl, 4, 2) ||
+                                              version >= &Version(Api::GlEs, 3, 1) ||
+                                              extensions.gl_arb_shader_atomic_counters
+        {
             let mut val = mem::uninitialized();
             gl.GetIntegerv(gl::MAX_ATOMIC_COUNTER_BUFFER_BINDINGS, &mut val);
             val
@@ -300,6 +365,16 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        uniform_buffer_offset_alignment: {
+            if version >= &Version(Api::Gl, 3, 1) || extensions.gl_arb_uniform_buffer_object {      // TODO: GLES
+                let mut val = mem::uninitialized();
+                gl.GetIntegerv(gl::UNIFORM_BUFFER_OFFSET_ALIGNMENT, &mut val);
+                val
+            } else {
+                1
+            }
+        },
+
         max_compute_work_group_count: if version >= &Version(Api::Gl, 4, 3) ||
                                          version >= &Version(Api::GlEs, 3, 1) ||
                                          extensions.gl_arb_compute_shader
@@ -10,6 +10,10 @@ pub struct ExtensionsList {
     pub gl_apple_vertex_array_object: bool,
     /// GL_ARB_buffer_storage
     pub gl_arb_buffer_storage: bool,
+    /// GL_ARB_base_instance
+    pub gl_arb_base_instance: bool,
+    /// GL_ARB_clear_buffer_object
+    pub gl_arb_clear_buffer_object: bool,
     /// GL_ARB_compute_shader
     pub gl_arb_compute_shader: bool,
     /// GL_ARB_copy_buffer
@@ -20,6 +24,8 @@ pub struct ExtensionsList {
     pub gl_arb_depth_texture: bool,
     /// GL_ARB_direct_state_access
     pub gl_arb_direct_state_access: bool,
+    /// GL_ARB_draw_elements_base_vertex
+    pub gl_arb_draw_elements_base_vertex: bool,
     /// GL_ARB_compatibility
     pub gl_arb_compatibility: bool,
     /// GL_ARB_ES2_compatibility
@@ -32,10 +38,14 @@ pub struct ExtensionsList {
     pub gl_arb_fragment_shader: bool,
     /// GL_ARB_framebuffer_sRGB
     pub gl_arb_framebuffer_srgb: bool,
+    /// GL_ARB_framebuffer_no_attachments
+    pub gl_arb_framebuffer_no_attachments: bool,
     /// GL_ARB_geometry_shader4
     pub gl_arb_geometry_shader4: bool,
     /// GL_ARB_get_program_binary
     pub gl_arb_get_programy_binary: bool,
+    /// GL_ARB_gpu_shader_fp64
+    pub gl_arb_gpu_shader_fp64: bool,
     /// GL_ARB_instanced_arrays
     pub gl_arb_instanced_arrays: bool,
     /// GL_ARB_invalidate_subdata
@@ -48,6 +58,8 @@ pub struct ExtensionsList {
     pub gl_arb_occlusion_query: bool,
     /// GL_ARB_occlusion_query2
     pub gl_arb_occlusion_query2: bool,
+    /// GL_ARB_query_buffer_object
+    pub gl_arb_query_buffer_object: bool,
     /// GL_ARB_robustness
     pub gl_arb_robustness: bool,
     /// GL_ARB_robust_buffer_access_behavior
@@ -58,6 +70,8 @@ pub struct ExtensionsList {
     pub gl_arb_program_interface_query: bool,
     /// GL_ARB_sampler_objects
     pub gl_arb_sampler_objects: bool,
+    /// GL_ARB_shader_atomic_counters
+    pub gl_arb_shader_atomic_counters: bool,
     /// GL_ARB_shader_image_load_store
     pub gl_arb_shader_image_load_store: bool,
     /// GL_ARB_shader_objects
@@ -84,8 +98,12 @@ pub struct ExtensionsList {
     pub gl_arb_texture_storage: bool,
     /// GL_ARB_timer_query
     pub gl_arb_timer_query: bool,
+    /// GL_ARB_transform_feedback2
+    pub gl_arb_transform_feedback2: bool,
     /// GL_ARB_transform_feedback3
     pub gl_arb_transform_feedback3: bool,
+    /// GL_ARB_transform_feedback_instanced
+    pub gl_arb_transform_feedback_instanced: bool,
     /// GL_ARB_uniform_buffer_object
     pub gl_arb_uniform_buffer_object: bool,
     /// GL_ARB_vertex_array_object
@@ -94,6 +112,10 @@ pub struct ExtensionsList {
     pub gl_arb_vertex_buffer_object: bool,
     /// GL_ARB_vertex_shader
     pub gl_arb_vertex_shader: bool,
+    /// GL_ARB_vertex_type_10f_11f_11f_rev
+    pub gl_arb_vertex_type_10f_11f_11f_rev: bool,
+    /// GL_ARB_vertex_type_2_10_10_10_rev
+    pub gl_arb_vertex_type_2_10_10_10_rev: bool,
     /// GL_ARM_rgba8
     pub gl_arm_rgba8: bool,
     /// GL_ATI_meminfo
@@ -102,6 +124,8 @@ pub struct ExtensionsList {
     pub gl_ext_debug_marker: bool,
     /// GL_EXT_direct_state_access
     pub gl_ext_direct_state_access: bool,
+    /// GL_EXT_discard_framebuffer
+    pub gl_ext_discard_framebuffer: bool,
     /// GL_EXT_disjoint_timer_query
     pub gl_ext_disjoint_timer_query: bool,
     /// GL_EXT_framebuffer_blit
@@ -152,6 +176,8 @@ pub struct ExtensionsList {
     pub gl_nvx_gpu_memory_info: bool,
     /// GL_OES_depth_texture
     pub gl_oes_depth_texture: bool,
+    /// GL_OES_geometry_shader
+    pub gl_oes_geometry_shader: bool,
     /// GL_OES_packed_depth_stencil
     pub gl_oes_packed_depth_stencil: bool,
     /// GL_OES_rgb8_rgba8
@@ -177,19 +203,24 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
     let mut extensions = ExtensionsList {
         gl_apple_vertex_array_object: false,
         gl_arb_buffer_storage: false,
+        gl_arb_base_instance: false,
+        gl_arb_clear_buffer_object: false,
         gl_arb_copy_buffer: false,
         gl_arb_compute_shader: false,
         gl_arb_debug_output: false,
         gl_arb_depth_texture: false,
         gl_arb_direct_state_access: false,
+        gl_arb_draw_elements_base_vertex: false,
         gl_arb_compatibility: false,
         gl_arb_es2_compatibility: false,
         gl_arb_es3_compatibility: false,
         gl_arb_es3_1_compatibility: false,
         gl_arb_fragment_shader: false,
+        gl_arb_framebuffer_no_attachments: false,
         gl_arb_framebuffer_srgb: false,
         gl_arb_geometry_shader4: false,
         gl_arb_get_programy_binary: false,
+        gl_arb_gpu_shader_fp64: false,
         gl_arb_instanced_arrays: false,
         gl_arb_invalidate_subdata: false,
         gl_arb_occlusion_query: false,
@@ -198,9 +229,11 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         gl_arb_multi_draw_indirect: false,
         gl_arb_pixel_buffer_object: false,
         gl_arb_program_interface_query: false,
+        gl_arb_query_buffer_object: false,
         gl_arb_robustness: false,
         gl_arb_robust_buffer_access_behavior: false,
         gl_arb_sampler_objects: false,
+        gl_arb_shader_atomic_counters: false,
         gl_arb_shader_image_load_store: false,
         gl_arb_shader_objects: false,
         gl_arb_shader_storage_buffer_object: false,
@@ -214,15 +247,20 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         gl_arb_texture_rgb10_a2ui: false,
         gl_arb_texture_storage: false,
         gl_arb_timer_query: false,
+        gl_arb_transform_feedback2: false,
         gl_arb_transform_feedback3: false,
+        gl_arb_transform_feedback_instanced: false,
         gl_arb_uniform_buffer_object: false,
         gl_arb_vertex_array_object: false,
         gl_arb_vertex_buffer_object: false,
         gl_arb_vertex_shader: false,
+        gl_arb_vertex_type_10f_11f_11f_rev: false,
+        gl_arb_vertex_type_2_10_10_10_rev: false,
         gl_arm_rgba8: false,
         gl_ati_meminfo: false,
         gl_ext_debug_marker: false,
         gl_ext_direct_state_access: false,
+        gl_ext_discard_framebuffer: false,
         gl_ext_disjoint_timer_query: false,
         gl_ext_framebuffer_blit: false,
         gl_ext_framebuffer_object: false,
@@ -248,6 +286,7 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         gl_nv_pixel_buffer_object: false,
         gl_nvx_gpu_memory_info: false,
         gl_oes_depth_texture: false,
+        gl_oes_geometry_shader: false,
         gl_oes_packed_depth_stencil: false,
         gl_oes_rgb8_rgba8: false,
         gl_oes_vertex_array_object: false,
@@ -257,19 +296,28 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         match &extension[..] {
             "GL_APPLE_vertex_array_object" => extensions.gl_apple_vertex_array_object = true,
             "GL_ARB_buffer_storage" => extensions.gl_arb_buffer_storage = true,
+            "GL_ARB_base_instance" => extensions.gl_arb_base_instance = true,
+            "GL_ARB_clear_buffer_object" => extensions.gl_arb_clear_buffer_object = true,
             "GL_ARB_compute_shader" => extensions.gl_arb_compute_shader = true,
             "GL_ARB_copy_buffer" => extensions.gl_arb_copy_buffer = true,
             "GL_ARB_debug_output" => extensions.gl_arb_debug_output = true,
             "GL_ARB_depth_texture" => extensions.gl_arb_depth_texture = true,
             "GL_ARB_direct_state_access" => extensions.gl_arb_direct_state_access = true,
+            "GL_ARB_draw_elements_base_vertex" => {
+                extensions.gl_arb_draw_elements_base_vertex = true
+            },
             "GL_ARB_compatibility" => extensions.gl_arb_compatibility = true,
             "GL_ARB_ES2_compatibility" => extensions.gl_arb_es2_compatibility = true,
             "GL_ARB_ES3_compatibility" => extensions.gl_arb_es3_compatibility = true,
             "GL_ARB_ES3_1_compatibility" => extensions.gl_arb_es3_1_compatibility = true,
             "GL_ARB_fragment_shader" => extensions.gl_arb_fragment_shader = true,
+            "GL_ARB_framebuffer_no_attachments" => {
+                extensions.gl_arb_framebuffer_no_attachments = true
+            },
             "GL_ARB_framebuffer_sRGB" => extensions.gl_arb_framebuffer_srgb = true,
             "GL_ARB_geometry_shader4" => extensions.gl_arb_geometry_shader4 = true,
             "GL_ARB_get_program_binary" => extensions.gl_arb_get_programy_binary = true,
+            "GL_ARB_gpu_shader_fp64" => extensions.gl_arb_gpu_shader_fp64 = true,
             "GL_ARB_instanced_arrays" => extensions.gl_arb_instanced_arrays = true,
             "GL_ARB_invalidate_subdata" => extensions.gl_arb_invalidate_subdata = true,
             "GL_ARB_occlusion_query" => extensions.gl_arb_occlusion_query = true,
@@ -278,9 +326,11 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
             "GL_ARB_program_interface_query" => extensions.gl_arb_program_interface_query = true,
             "GL_ARB_map_buffer_range" => extensions.gl_arb_map_buffer_range = true,
             "GL_ARB_multi_draw_indirect" => extensions.gl_arb_multi_draw_indirect = true,
+            "GL_ARB_query_buffer_object" => extensions.gl_arb_query_buffer_object = true,
             "GL_ARB_robustness" => extensions.gl_arb_robustness = true,
             "GL_ARB_robust_buffer_access_behavior" => extensions.gl_arb_robust_buffer_access_behavior = true,
             "GL_ARB_sampler_objects" => extensions.gl_arb_sampler_objects = true,
+            "GL_ARB_shader_atomic_counters" => extensions.gl_arb_shader_atomic_counters = true,
             "GL_ARB_shader_image_load_store" => extensions.gl_arb_shader_image_load_store = true,
             "GL_ARB_shader_objects" => extensions.gl_arb_shader_objects = true,
             "GL_ARB_shader_storage_buffer_object" => extensions.gl_arb_shader_storage_buffer_object = true,
@@ -294,15 +344,24 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
             "GL_ARB_texture_rgb10_a2ui" => extensions.gl_arb_texture_rgb10_a2ui = true,
             "GL_ARB_texture_storage" => extensions.gl_arb_texture_storage = true,
             "GL_ARB_timer_query" => extensions.gl_arb_timer_query = true,
+            "GL_ARB_transform_feedback2" => extensions.gl_arb_transform_feedback2 = true,
             "GL_ARB_transform_feedback3" => extensions.gl_arb_transform_feedback3 = true,
+            "GL_ARB_transform_feedback_instanced" => extensions.gl_arb_transform_feedback_instanced = true,
             "GL_ARB_uniform_buffer_object" => extensions.gl_arb_uniform_buffer_object = true,
             "GL_ARB_vertex_array_object" => extensions.gl_arb_vertex_array_object = true,
             "GL_ARB_vertex_buffer_object" => extensions.gl_arb_vertex_buffer_object = true,
             "GL_ARB_vertex_shader" => extensions.gl_arb_vertex_shader = true,
+            "GL_ARB_vertex_type_10f_11f_11f_rev" => {
+                extensions.gl_arb_vertex_type_10f_11f_11f_rev = true
+            },
+            "GL_ARB_vertex_type_2_10_10_10_rev" => {
+                extensions.gl_arb_vertex_type_2_10_10_10_rev = true
+            },
             "GL_ARM_rgba8" => extensions.gl_arm_rgba8 = true,
             "GL_ATI_meminfo" => extensions.gl_ati_meminfo = true,
             "GL_EXT_debug_marker" => extensions.gl_ext_debug_marker = true,
             "GL_EXT_direct_state_access" => extensions.gl_ext_direct_state_access = true,
+            "GL_EXT_discard_framebuffer" => extensions.gl_ext_discard_framebuffer = true,
             "GL_EXT_disjoint_timer_query" => extensions.gl_ext_disjoint_timer_query = true,
             "GL_EXT_framebuffer_blit" => extensions.gl_ext_framebuffer_blit = true,
             "GL_EXT_framebuffer_object" => extensions.gl_ext_framebuffer_object = true,
@@ -328,6 +387,7 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
             "GL_NV_pixel_buffer_object" => extensions.gl_nv_pixel_buffer_object = true,
             "GL_NVX_gpu_memory_info" => extensions.gl_nvx_gpu_memory_info = true,
             "GL_OES_depth_texture" => extensions.gl_oes_depth_texture = true,
+            "GL_OES_geometry_shader" => extensions.gl_oes_geometry_shader = true,
             "GL_OES_packed_depth_stencil" => extensions.gl_oes_packed_depth_stencil = true,
             "GL_OES_rgb8_rgba8" => extensions.gl_oes_rgb8_rgba8 = true,
             "GL_OES_vertex_array_object" => extensions.gl_oes_vertex_array_object = true,
@@ -60,6 +60,12 @@ pub struct GlState {
     /// Whether GL_POLYGON_SMOOTH is enabled
     pub enabled_polygon_smooth: bool,
 
+    /// Whether GL_PRIMITIVE_RESTART is enabled
+    pub enabled_primitive_restart: bool,
+
+    /// Whether GL_PRIMITIVE_RESTART_FIXED_INDEX is enabled
+    pub enabled_primitive_restart_fixed_index: bool,
+
     /// The latest value passed to `glUseProgram`.
     pub program: Handle,
 
@@ -203,6 +209,9 @@ pub struct GlState {
     /// The latest value passed to `glPixelStore` with `GL_PACK_ALIGNMENT`.
     pub pixel_store_pack_alignment: gl::types::GLint,
 
+    /// The latest value passed to `glPrimitiveRestartIndex`.
+    pub primitive_restart_index: gl::types::GLuint,
+
     /// The latest value passed to `glPatchParameter` with `GL_PATCH_VERTICES`.
     pub patch_patch_vertices: gl::types::GLint,
 
@@ -235,6 +244,9 @@ pub struct GlState {
     /// Latest value passed to `glBeginConditionalRender​`.
     pub conditional_render: Option<(gl::types::GLuint, gl::types::GLenum)>,
 
+    /// The latest value passed to `glBindTransformFeedback`.
+    pub transform_feedback: gl::types::GLuint,
+
     /// If `glBeginTransformFeedback​` has been called, the current primitive types. Otherwise None.
     // TODO: move this inside transform feedback objects
     pub transform_feedback_enabled: Option<gl::types::GLenum>,
@@ -243,6 +255,10 @@ pub struct GlState {
     // TODO: move this inside transform feedback objects
     pub transform_feedback_paused: bool,
 
+    /// Transform feedback objects that are not bound but whose transform feedback is active and
+    /// paused, with their primitive types.
+    pub paused_transform_feedback_objects: Vec<(gl::types::GLuint, gl::types::GLenum)>,
+
     /// Current draw call ID.
     /// We maintain a counter that is incremented at each draw call.
     pub next_draw_call_id: u64,
@@ -351,6 +367,8 @@ impl Default for GlState {
             enabled_stencil_test: false,
             enabled_line_smooth: false,
             enabled_polygon_smooth: false,
+            enabled_primitive_restart: false,
+            enabled_primitive_restart_fixed_index: false,
 
             program: Handle::Id(0),
             vertex_array: 0,
@@ -398,6 +416,7 @@ impl Default for GlState {
             smooth: (gl::DONT_CARE, gl::DONT_CARE),
             pixel_store_unpack_alignment: 4,
             pixel_store_pack_alignment: 4,
+            primitive_restart_index: 0,
             patch_patch_vertices: 3,
             active_texture: 0,
             texture_units: small_vec_one(),
@@ -408,8 +427,10 @@ impl Default for GlState {
             transform_feedback_primitives_written_query: 0,
             time_elapsed_query: 0,
             conditional_render: None,
+            transform_feedback: 0,
             transform_feedback_enabled: None,
             transform_feedback_paused: false,
+            paused_transform_feedback_objects: Vec::new(),
 
             next_draw_call_id: 1,
             latest_memory_barrier_vertex_attrib_array: 1,
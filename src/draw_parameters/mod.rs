@@ -64,6 +64,19 @@
 //! If you use conditional rendering, glium will submit the draw command but the GPU will execute
 //! it only if the query contains a value different from 0.
 //!
+//! The result of a query can also be written by the GPU in a buffer, without any
+//! synchronization with the CPU:
+//!
+//! ```no_run
+//! # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+//! # let query: glium::draw_parameters::SamplesPassedQuery = unsafe { std::mem::uninitialized() };
+//! let buffer = glium::buffer::BufferView::<u32>::empty(&display,
+//!                                                     glium::buffer::BufferType::QueryBuffer,
+//!                                                     1, glium::buffer::BufferMode::Default)
+//!                                                     .unwrap();
+//! query.write_u32_to_buffer(buffer.as_slice()).unwrap();
+//! ```
+//!
 //! ## WrongQueryOperation errors
 //!
 //! OpenGL puts some restrictions about the usage of queries. If you draw one or several times
@@ -82,6 +95,7 @@ use ContextExt;
 use DrawError;
 use Rect;
 use ToGlEnum;
+use index::IndexType;
 use vertex::TransformFeedbackSession;
 
 use std::ops::{Deref, DerefMut};
@@ -89,6 +103,7 @@ use std::rc::Rc;
 
 pub use self::query::{SamplesPassedQuery, TimeElapsedQuery, PrimitivesGeneratedQuery};
 pub use self::query::{AnySamplesPassedQuery, TransformFeedbackPrimitivesWrittenQuery};
+pub use self::query::ToBufferError;
 
 mod query;
 
@@ -529,6 +544,43 @@ impl ToGlEnum for Smooth {
     }
 }
 
+/// Specifies which index restarts a primitive when drawing strips or fans with indices.
+///
+/// When the restart index is encountered in the index buffer, the current primitive ends and
+/// a new one starts with the next index. This allows you to draw multiple strips with a single
+/// draw call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrimitiveRestart {
+    /// The maximum value of the index type (`0xff`, `0xffff` or `0xffffffff`) restarts the
+    /// primitive.
+    ///
+    /// Requires OpenGL 4.3, OpenGL ES 3.0 or `GL_ARB_ES3_compatibility`.
+    FixedIndex,
+
+    /// The given value restarts the primitive. Must be representable by the index type.
+    ///
+    /// Requires OpenGL 3.1.
+    Index(u32),
+}
+
+impl PrimitiveRestart {
+    /// Returns the index that restarts a primitive for the given index type, or `None` if the
+    /// index can't be represented by this type.
+    pub fn get_index(&self, ty: IndexType) -> Option<u32> {
+        let max = match ty {
+            IndexType::U8 => 0xff,
+            IndexType::U16 => 0xffff,
+            IndexType::U32 => 0xffffffff,
+        };
+
+        match *self {
+            PrimitiveRestart::FixedIndex => Some(max),
+            PrimitiveRestart::Index(index) if index <= max => Some(index),
+            PrimitiveRestart::Index(_) => None,
+        }
+    }
+}
+
 /// Represents the parameters to use when drawing.
 ///
 /// Example:
@@ -784,6 +836,45 @@ pub struct DrawParameters<'a> {
     /// 
     /// Note that blending needs to be enabled for this to work.
     pub smooth: Option<Smooth>,
+
+    /// Value added to each index before reading a vertex from the vertex buffers. The default
+    /// value is `0`.
+    ///
+    /// This allows you to store multiple meshes in the same vertex buffer and index buffer
+    /// without having to modify the indices.
+    ///
+    /// When drawing without indices, there is no index to add this value to and it is instead
+    /// the index of the first vertex to draw, like the `first` parameter of `glDrawArrays`. In
+    /// that case drawing returns `BaseVertexOutOfRange` if it is not between `0` and the number
+    /// of vertices.
+    ///
+    /// The commands of a multidraw buffer contain their own first vertex, so this value must be
+    /// `0` when drawing with one.
+    ///
+    /// Drawing returns `BaseVertexNotSupported` if this value is not `0` and the backend or the
+    /// indices source doesn't support it.
+    pub base_vertex: i32,
+
+    /// Index of the first element to read from per-instance vertex sources. The default value
+    /// is `0`.
+    ///
+    /// The number of instances to draw is the number of elements in the per-instance sources
+    /// minus this value.
+    ///
+    /// The commands of a multidraw buffer contain their own base instance, so this value must
+    /// be `0` when drawing with one.
+    ///
+    /// Drawing returns `BaseInstanceNotSupported` if this value is not `0` and the backend or
+    /// the indices source doesn't support it.
+    pub base_instance: u32,
+
+    /// If set, the given index restarts the current primitive when drawing with an index
+    /// buffer. The default value is `None`.
+    ///
+    /// Drawing returns `PrimitiveRestartNotSupported` if the backend doesn't support the
+    /// requested mode, and `PrimitiveRestartIndexOutOfRange` if the index can't be represented
+    /// by the type of the indices.
+    pub primitive_restart: Option<PrimitiveRestart>,
 }
 
 /// Condition whether to render or not.
@@ -858,6 +949,9 @@ impl<'a> Default for DrawParameters<'a> {
             condition: None,
             transform_feedback: None,
             smooth: None,
+            base_vertex: 0,
+            base_instance: 0,
+            primitive_restart: None,
         }
     }
 }
@@ -1001,6 +1095,36 @@ impl<'a> DrawParametersBuilder<'a> {
         self
     }
 
+    /// Sets the value to add to each index before reading a vertex.
+    ///
+    /// Returns `Err` if the backend doesn't support this operation.
+    pub fn with_base_vertex_if_supported(mut self, base_vertex: i32)
+                                         -> Result<DrawParametersBuilder<'a>,
+                                                   DrawParametersBuilder<'a>>
+    {
+        if !is_base_vertex_supported(self.context) {
+            return Err(self);
+        }
+
+        self.params.base_vertex = base_vertex;
+        Ok(self)
+    }
+
+    /// Sets the index of the first element to read from per-instance vertex sources.
+    ///
+    /// Returns `Err` if the backend doesn't support this operation.
+    pub fn with_base_instance_if_supported(mut self, base_instance: u32)
+                                           -> Result<DrawParametersBuilder<'a>,
+                                                     DrawParametersBuilder<'a>>
+    {
+        if !is_base_instance_supported(self.context) {
+            return Err(self);
+        }
+
+        self.params.base_instance = base_instance;
+        Ok(self)
+    }
+
     /// See the `ConditionalRendering` struct.
     pub fn with_conditional_rendering<Q>(mut self, query: Q, wait: bool, per_region: bool)
                                          -> DrawParametersBuilder<'a>
@@ -1044,5 +1168,26 @@ pub fn validate(context: &Context, params: &DrawParameters) -> Result<(), DrawEr
         return Err(DrawError::TransformFeedbackNotSupported);
     }
 
+    if params.base_vertex != 0 && !is_base_vertex_supported(context) {
+        return Err(DrawError::BaseVertexNotSupported);
+    }
+
+    if params.base_instance != 0 && !is_base_instance_supported(context) {
+        return Err(DrawError::BaseInstanceNotSupported);
+    }
+
     Ok(())
 }
+
+/// Returns true if the backend supports `DrawParameters::base_vertex`.
+fn is_base_vertex_supported(context: &Context) -> bool {
+    context.get_version() >= &Version(Api::Gl, 3, 2) ||
+    context.get_version() >= &Version(Api::GlEs, 3, 2) ||
+    context.get_extensions().gl_arb_draw_elements_base_vertex
+}
+
+/// Returns true if the backend supports `DrawParameters::base_instance`.
+fn is_base_instance_supported(context: &Context) -> bool {
+    context.get_version() >= &Version(Api::Gl, 4, 2) ||
+    context.get_extensions().gl_arb_base_instance
+}
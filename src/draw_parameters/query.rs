@@ -1,6 +1,10 @@
 use backend::Facade;
+use buffer::BufferViewSlice;
 use context::Context;
 use context::CommandContext;
+use sync;
+use BufferViewExt;
+use BufferViewSliceExt;
 use ContextExt;
 use ToGlEnum;
 use GlObject;
@@ -25,6 +29,19 @@ pub struct RawQuery {
     has_been_used: Cell<bool>,
 }
 
+/// Error that can happen when writing the result of a query to a buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ToBufferError {
+    /// The backend doesn't support writing the result of a query to a buffer.
+    NotSupported,
+
+    /// The query has never been used in a draw command, so it doesn't have any result.
+    QueryNotUsed,
+
+    /// The buffer slice doesn't contain exactly one element.
+    WrongLength,
+}
+
 pub enum QueryType {
     SamplesPassed,
     AnySamplesPassed,
@@ -250,6 +267,69 @@ impl RawQuery {
         self.get_u32() != 0
     }
 
+    /// Writes the value of the query to a buffer once it is available. Doesn't block.
+    pub fn write_u32_to_buffer(&self, target: BufferViewSlice<u32>) -> Result<(), ToBufferError> {
+        self.write_to_buffer(target, false)
+    }
+
+    /// Writes the value of the query to a buffer once it is available. Doesn't block.
+    pub fn write_u64_to_buffer(&self, target: BufferViewSlice<u64>) -> Result<(), ToBufferError> {
+        self.write_to_buffer(target, true)
+    }
+
+    fn write_to_buffer<T>(&self, target: BufferViewSlice<T>, use_u64: bool)
+                          -> Result<(), ToBufferError> where T: Copy + Send + 'static
+    {
+        if target.len() != 1 {
+            return Err(ToBufferError::WrongLength);
+        }
+
+        // the result of a query that has never been begun is undefined
+        if !self.has_been_used.get() {
+            return Err(ToBufferError::QueryNotUsed);
+        }
+
+        let mut ctxt = self.context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 4)) &&
+           !ctxt.extensions.gl_arb_query_buffer_object
+        {
+            return Err(ToBufferError::NotSupported);
+        }
+
+        self.deactivate(&mut ctxt);
+
+        // while a buffer is binded to `GL_QUERY_BUFFER`, the last parameter of
+        // `glGetQueryObject*` is an offset within this buffer
+        let offset = target.get_offset_bytes();
+        target.prepare_and_bind_for_query(&mut ctxt);
+
+        unsafe {
+            if use_u64 {
+                ctxt.gl.GetQueryObjectui64v(self.id, gl::QUERY_RESULT, offset as *mut _);
+            } else {
+                ctxt.gl.GetQueryObjectuiv(self.id, gl::QUERY_RESULT, offset as *mut _);
+            }
+        }
+
+        // unbinding so that `get_u32` and `get_u64` write to client memory again
+        BufferViewSlice::<T>::unbind_query(&mut ctxt);
+
+        if let Some(fence) = target.add_fence() {
+            let mut new_fence = Some(unsafe {
+                sync::new_linear_sync_fence_if_supported(&mut ctxt)
+            }.unwrap());
+
+            mem::swap(&mut new_fence, &mut *fence.borrow_mut());
+
+            if let Some(new_fence) = new_fence {
+                unsafe { sync::destroy_linear_sync_fence(&mut ctxt, new_fence) };
+            }
+        }
+
+        Ok(())
+    }
+
     /// If the query is active, unactivates it.
     fn deactivate(&self, ctxt: &mut CommandContext) {
         if ctxt.state.samples_passed_query == self.id {
@@ -358,6 +438,27 @@ macro_rules! impl_helper {
             pub fn get(self) -> $ret {
                 self.query.$get_fn()
             }
+
+            /// Writes the value of the query to a buffer once it is available, without blocking.
+            ///
+            /// The value is written by the GPU, which means that it can be used for example
+            /// by a compute shader or as the parameters of an indirect draw command without
+            /// being read back by the CPU.
+            ///
+            /// Returns an error if the backend doesn't support this, if the query has never
+            /// been used in a draw command, or if the slice doesn't contain exactly one element.
+            pub fn write_u32_to_buffer(&self, target: BufferViewSlice<u32>)
+                                       -> Result<(), ToBufferError>
+            {
+                self.query.write_u32_to_buffer(target)
+            }
+
+            /// Same as `write_u32_to_buffer`, but writes a 64 bits value.
+            pub fn write_u64_to_buffer(&self, target: BufferViewSlice<u64>)
+                                       -> Result<(), ToBufferError>
+            {
+                self.query.write_u64_to_buffer(target)
+            }
         }
 
         impl GlObject for $name {
@@ -63,6 +63,7 @@ use framebuffer::RenderBufferAny;
 
 use gl;
 use context::CommandContext;
+use Rect;
 use version::Version;
 use version::Api;
 
@@ -115,20 +116,30 @@ pub enum Attachment<'a> {
         /// Mipmap level to use. The main texture is level 0.
         level: u32,
     },
+    /// All the layers of a texture array or of a 3D texture. Geometry shaders can choose the
+    /// layer to write to with `gl_Layer`. Cubemaps are not supported.
+    TextureLayered {
+        /// The texture.
+        texture: &'a TextureAny,
+        /// Mipmap level to use. The main texture is level 0.
+        level: u32,
+    },
     /// A renderbuffer.
     RenderBuffer(&'a RenderBufferAny),
 }
 
 impl<'a> FramebufferAttachments<'a> {
     /// After building a `FramebufferAttachments` struct, you must use this function
-    /// to "compile" the attachments and make sure that they are valid together.
-    pub fn validate(self)
+    /// to "compile" the attachments and make sure that they are valid together and
+    /// supported by the backend.
+    pub fn validate(self, ctxt: &CommandContext)
                     -> Result<ValidatedAttachments<'a>, ValidationError>
     {
         // turning the attachments into raw attachments
-        let (raw_attachments, dimensions, depth_bits, stencil_bits) = {
+        let (raw_attachments, dimensions, layers, depth_bits, stencil_bits) = {
             fn handle_attachment(a: &Attachment, dim: &mut Option<(u32, u32)>,
-                                 num_bits: Option<&mut Option<u16>>)
+                                 num_bits: Option<&mut Option<u16>>,
+                                 layers: &mut Vec<Option<u32>>)
                                  -> RawAttachment
             {
                 match a {
@@ -146,11 +157,14 @@ impl<'a> FramebufferAttachments<'a> {
                             }
                         }
 
+                        layers.push(None);
+
                         RawAttachment::Texture {
                             texture: texture.get_id(),
                             bind_point: texture.get_bind_point(),
                             layer: 0,
                             level: level,       // TODO: check validity
+                            layered: false,
                         }
                     },
                     &Attachment::TextureLayer { ref texture, level, layer } => {
@@ -167,11 +181,46 @@ impl<'a> FramebufferAttachments<'a> {
                             }
                         }
 
+                        layers.push(None);
+
                         RawAttachment::Texture {
                             texture: texture.get_id(),
                             bind_point: texture.get_bind_point(),
                             layer: layer,       // TODO: check validity
                             level: level,       // TODO: check validity
+                            layered: false,
+                        }
+                    },
+                    &Attachment::TextureLayered { ref texture, level } => {
+                        if let Some(num_bits) = num_bits {
+                            *num_bits = Some(texture.get_internal_format_if_supported()
+                                               .map(|f| f.get_total_bits()).unwrap_or(24) as u16);     // TODO: how to handle this?
+                        }
+
+                        match dim {
+                            d @ &mut None => *d = Some((texture.get_width(), texture.get_height().unwrap_or(1))),
+                            &mut Some((ref mut x, ref mut y)) => {
+                                *x = cmp::min(*x, texture.get_width());
+                                *y = cmp::min(*y, texture.get_height().unwrap_or(1));
+                            }
+                        }
+
+                        // array textures have one layer per element, and 3D textures have one
+                        // layer per depth slice of the mipmap level
+                        let num_layers = match (texture.get_array_size(), texture.get_depth()) {
+                            (Some(size), _) => size,
+                            (None, Some(depth)) => cmp::max(1, depth >> level),
+                            (None, None) => 1,
+                        };
+
+                        layers.push(Some(num_layers));
+
+                        RawAttachment::Texture {
+                            texture: texture.get_id(),
+                            bind_point: texture.get_bind_point(),
+                            layer: 0,
+                            level: level,       // TODO: check validity
+                            layered: true,
                         }
                     },
                     &Attachment::RenderBuffer(ref buffer) => {
@@ -188,16 +237,19 @@ impl<'a> FramebufferAttachments<'a> {
                             }
                         }
 
+                        layers.push(None);
+
                         RawAttachment::RenderBuffer(buffer.get_id())
                     },
                 }
             }
 
             // TODO: check number of samples
-            // TODO: check layering
 
             // the dimensions of the framebuffer object
             let mut dimensions = None;
+            // number of layers of each attachment, or `None` for non-layered attachments
+            let mut attachments_layers = Vec::with_capacity(self.colors.len() + 2);
             // number of depth bits
             let mut depth_bits = None;
             // number of stencil bits
@@ -208,26 +260,35 @@ impl<'a> FramebufferAttachments<'a> {
                 depth: None,
                 stencil: None,
                 depth_stencil: None,
+                default_dimensions: None,
+                default_layers: None,
+                default_samples: None,
             };
 
             for &(index, ref a) in &self.colors {
-                raw_attachments.color.push((index, handle_attachment(a, &mut dimensions, None)));
+                raw_attachments.color.push((index, handle_attachment(a, &mut dimensions, None,
+                                                                     &mut attachments_layers)));
             }
 
             match self.depth_stencil {
                 FramebufferDepthStencilAttachments::None => (),
                 FramebufferDepthStencilAttachments::DepthAttachment(ref a) => {
-                    raw_attachments.depth = Some(handle_attachment(a, &mut dimensions, Some(&mut depth_bits)));
+                    raw_attachments.depth = Some(handle_attachment(a, &mut dimensions, Some(&mut depth_bits),
+                                                                   &mut attachments_layers));
                 },
                 FramebufferDepthStencilAttachments::StencilAttachment(ref a) => {
-                    raw_attachments.stencil = Some(handle_attachment(a, &mut dimensions, Some(&mut stencil_bits)));
+                    raw_attachments.stencil = Some(handle_attachment(a, &mut dimensions, Some(&mut stencil_bits),
+                                                                     &mut attachments_layers));
                 },
                 FramebufferDepthStencilAttachments::DepthAndStencilAttachments(ref d, ref s) => {
-                    raw_attachments.depth = Some(handle_attachment(d, &mut dimensions, Some(&mut depth_bits)));
-                    raw_attachments.stencil = Some(handle_attachment(s, &mut dimensions, Some(&mut stencil_bits)));
+                    raw_attachments.depth = Some(handle_attachment(d, &mut dimensions, Some(&mut depth_bits),
+                                                                   &mut attachments_layers));
+                    raw_attachments.stencil = Some(handle_attachment(s, &mut dimensions, Some(&mut stencil_bits),
+                                                                     &mut attachments_layers));
                 },
                 FramebufferDepthStencilAttachments::DepthStencilAttachment(ref a) => {
-                    raw_attachments.depth_stencil = Some(handle_attachment(a, &mut dimensions, None));      // FIXME: bit counts
+                    raw_attachments.depth_stencil = Some(handle_attachment(a, &mut dimensions, None,
+                                                                           &mut attachments_layers));      // FIXME: bit counts
                 },
             }
 
@@ -236,13 +297,31 @@ impl<'a> FramebufferAttachments<'a> {
                 None => return Err(ValidationError::EmptyFramebufferObjectsNotSupported)
             };
 
-            (raw_attachments, dimensions, depth_bits, stencil_bits)
+            // either all the attachments are layered with the same number of layers, or none
+            // of them is layered
+            let layers = attachments_layers[0];
+            for &l in attachments_layers.iter() {
+                match (layers, l) {
+                    (Some(a), Some(b)) if a != b => return Err(ValidationError::LayersCountMismatch),
+                    (Some(_), None) | (None, Some(_)) => {
+                        return Err(ValidationError::NotAllAttachmentsLayered)
+                    },
+                    _ => ()
+                }
+            }
+
+            if layers.is_some() && !is_layered_rendering_supported(ctxt) {
+                return Err(ValidationError::LayeredAttachmentsNotSupported);
+            }
+
+            (raw_attachments, dimensions, layers, depth_bits, stencil_bits)
         };
 
         Ok(ValidatedAttachments {
             raw: raw_attachments,
             marker: PhantomData,
             dimensions: dimensions,
+            layers: layers,
             depth_buffer_bits: depth_bits,
             stencil_buffer_bits: stencil_bits,
         })
@@ -255,16 +334,75 @@ pub struct ValidatedAttachments<'a> {
     marker: PhantomData<&'a ()>,
     raw: RawAttachments,
     dimensions: (u32, u32),
+    layers: Option<u32>,
     depth_buffer_bits: Option<u16>,
     stencil_buffer_bits: Option<u16>,
 }
 
+impl ValidatedAttachments<'static> {
+    /// Builds the attachments of a framebuffer that doesn't have any attachment. Instead, its
+    /// dimensions, number of layers and number of samples are explicitly given.
+    pub fn empty(ctxt: &CommandContext, width: u32, height: u32, layers: Option<u32>,
+                 samples: Option<u32>) -> Result<ValidatedAttachments<'static>, ValidationError>
+    {
+        if !(ctxt.version >= &Version(Api::Gl, 4, 3)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 1)) &&
+           !ctxt.extensions.gl_arb_framebuffer_no_attachments
+        {
+            return Err(ValidationError::EmptyFramebufferObjectsNotSupported);
+        }
+
+        let max_width = ctxt.capabilities.max_framebuffer_width.unwrap_or(0) as u32;
+        let max_height = ctxt.capabilities.max_framebuffer_height.unwrap_or(0) as u32;
+        if width == 0 || height == 0 || width > max_width || height > max_height {
+            return Err(ValidationError::EmptyFramebufferUnsupportedDimensions);
+        }
+
+        if let Some(layers) = layers {
+            let max_layers = ctxt.capabilities.max_framebuffer_layers.unwrap_or(0) as u32;
+            if layers == 0 || layers > max_layers {
+                return Err(ValidationError::EmptyFramebufferUnsupportedLayers);
+            }
+        }
+
+        if let Some(samples) = samples {
+            let max_samples = ctxt.capabilities.max_framebuffer_samples.unwrap_or(0) as u32;
+            if samples == 0 || samples > max_samples {
+                return Err(ValidationError::EmptyFramebufferUnsupportedSamples);
+            }
+        }
+
+        Ok(ValidatedAttachments {
+            raw: RawAttachments {
+                color: Vec::new(),
+                depth: None,
+                stencil: None,
+                depth_stencil: None,
+                default_dimensions: Some((width, height)),
+                default_layers: layers,
+                default_samples: samples,
+            },
+            marker: PhantomData,
+            dimensions: (width, height),
+            layers: layers,
+            depth_buffer_bits: None,
+            stencil_buffer_bits: None,
+        })
+    }
+}
+
 impl<'a> ValidatedAttachments<'a> {
     /// Returns the dimensions that the framebuffer will have if you use these attachments.
     pub fn get_dimensions(&self) -> (u32, u32) {
         self.dimensions
     }
 
+    /// Returns the number of layers of the framebuffer, or `None` if the attachments are
+    /// not layered.
+    pub fn get_layers(&self) -> Option<u32> {
+        self.layers
+    }
+
     /// Returns the number of bits of precision of the depth buffer, or `None` if there is no
     /// depth buffer. Also works for depth-stencil buffers.
     pub fn get_depth_buffer_bits(&self) -> Option<u16> {
@@ -276,12 +414,55 @@ impl<'a> ValidatedAttachments<'a> {
     pub fn get_stencil_buffer_bits(&self) -> Option<u16> {
         self.stencil_buffer_bits
     }
+
+    /// Returns true if there is at least one color attachment.
+    pub fn has_color_attachments(&self) -> bool {
+        !self.raw.color.is_empty()
+    }
+
+    /// Returns the attachment points to pass to `invalidate_framebuffer` in order to invalidate
+    /// the color, depth and/or stencil attachments.
+    pub fn get_attachment_points(&self, color: bool, depth: bool, stencil: bool)
+                                 -> Vec<gl::types::GLenum>
+    {
+        let mut points = Vec::new();
+
+        if color {
+            for &(slot, _) in self.raw.color.iter() {
+                points.push(gl::COLOR_ATTACHMENT0 + slot);
+            }
+        }
+
+        // depth-stencil attachments can be invalidated separately
+        if depth && (self.raw.depth.is_some() || self.raw.depth_stencil.is_some()) {
+            points.push(gl::DEPTH_ATTACHMENT);
+        }
+        if stencil && (self.raw.stencil.is_some() || self.raw.depth_stencil.is_some()) {
+            points.push(gl::STENCIL_ATTACHMENT);
+        }
+
+        points
+    }
 }
 
 /// An error that can happen while validating attachments.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ValidationError {
+    /// There is no attachment, and the backend doesn't support framebuffers without
+    /// attachments.
     EmptyFramebufferObjectsNotSupported,
+    /// The dimensions of a framebuffer without attachments are zero or too large.
+    EmptyFramebufferUnsupportedDimensions,
+    /// The number of layers of a framebuffer without attachments is zero or too large.
+    EmptyFramebufferUnsupportedLayers,
+    /// The number of samples of a framebuffer without attachments is zero or too large.
+    EmptyFramebufferUnsupportedSamples,
+    /// Some attachments are layered and some others are not.
+    NotAllAttachmentsLayered,
+    /// The layered attachments don't have the same number of layers.
+    LayersCountMismatch,
+    /// The attachments are layered, and the backend doesn't support layered attachments.
+    LayeredAttachmentsNotSupported,
 }
 
 /// Data structure stored in the hashmap.
@@ -291,6 +472,26 @@ struct RawAttachments {
     depth: Option<RawAttachment>,
     stencil: Option<RawAttachment>,
     depth_stencil: Option<RawAttachment>,
+
+    // parameters of framebuffers without attachments
+    default_dimensions: Option<(u32, u32)>,
+    default_layers: Option<u32>,
+    default_samples: Option<u32>,
+}
+
+impl RawAttachments {
+    /// Returns true if the attachments are layered.
+    fn is_layered(&self) -> bool {
+        let is_layered = |a: &RawAttachment| match a {
+            &RawAttachment::Texture { layered, .. } => layered,
+            &RawAttachment::RenderBuffer(_) => false,
+        };
+
+        // since the attachments have been validated, checking one of them is enough
+        self.color.iter().map(|&(_, ref a)| a).chain(self.depth.iter())
+            .chain(self.stencil.iter()).chain(self.depth_stencil.iter())
+            .next().map(is_layered).unwrap_or(false)
+    }
 }
 
 /// Single attachment.
@@ -301,6 +502,7 @@ enum RawAttachment {
         texture: gl::types::GLuint,
         layer: u32,
         level: u32,
+        layered: bool,
     },
     RenderBuffer(gl::types::GLuint),
 }
@@ -346,6 +548,44 @@ impl FramebuffersContainer {
         });
     }
 
+    /// Invalidates the content of a texture through a framebuffer object that contains it.
+    ///
+    /// This is used when `glInvalidateTexImage` is not available. Does nothing if no framebuffer
+    /// object contains this layer and level of the texture.
+    pub fn invalidate_texture(ctxt: &mut CommandContext, texture: gl::types::GLuint,
+                              layer: u32, level: u32)
+    {
+        let matches = |a: &RawAttachment| match a {
+            &RawAttachment::Texture { texture: id, layer: l, level: lv, layered: false, .. } => {
+                id == texture && l == layer && lv == level
+            },
+            _ => false
+        };
+
+        let found = {
+            let framebuffers = ctxt.framebuffer_objects.framebuffers.borrow();
+            framebuffers.iter().filter_map(|(key, fbo)| {
+                if let Some(&(slot, _)) = key.color.iter().find(|&&(_, ref a)| matches(a)) {
+                    return Some((fbo.id, vec![gl::COLOR_ATTACHMENT0 + slot]));
+                }
+                if key.depth.as_ref().map(|a| matches(a)).unwrap_or(false) {
+                    return Some((fbo.id, vec![gl::DEPTH_ATTACHMENT]));
+                }
+                if key.stencil.as_ref().map(|a| matches(a)).unwrap_or(false) {
+                    return Some((fbo.id, vec![gl::STENCIL_ATTACHMENT]));
+                }
+                if key.depth_stencil.as_ref().map(|a| matches(a)).unwrap_or(false) {
+                    return Some((fbo.id, vec![gl::DEPTH_ATTACHMENT, gl::STENCIL_ATTACHMENT]));
+                }
+                None
+            }).next()
+        };
+
+        if let Some((id, points)) = found {
+            unsafe { invalidate_framebuffer(ctxt, id, &points, None) };
+        }
+    }
+
     /// Destroys all framebuffer objects that contain a precise renderbuffer.
     pub fn purge_renderbuffer(ctxt: &mut CommandContext, renderbuffer: gl::types::GLuint) {
         FramebuffersContainer::purge_if(ctxt, |a| a == &RawAttachment::RenderBuffer(renderbuffer));
@@ -450,7 +690,7 @@ impl FramebuffersContainer {
         let attachments = FramebufferAttachments {
             colors: vec![(0, attachment.clone())],
             depth_stencil: FramebufferDepthStencilAttachments::None,
-        }.validate().unwrap();
+        }.validate(ctxt).unwrap();
 
         let framebuffer = FramebuffersContainer::get_framebuffer_for_drawing(ctxt, Some(&attachments));
         bind_framebuffer(ctxt, framebuffer, false, true);
@@ -494,6 +734,9 @@ impl FrameBufferObject {
                    attachments.color.len(), ctxt.capabilities.max_draw_buffers);
         }
 
+        // checked by `validate`
+        debug_assert!(!attachments.is_layered() || is_layered_rendering_supported(ctxt));
+
         let id = unsafe {
             let mut id = mem::uninitialized();
 
@@ -528,12 +771,33 @@ impl FrameBufferObject {
                 attach(&mut ctxt, gl::DEPTH_STENCIL_ATTACHMENT, id, depth_stencil);
             }
 
+            if let Some((width, height)) = attachments.default_dimensions {
+                set_parameter(&mut ctxt, id, gl::FRAMEBUFFER_DEFAULT_WIDTH, width);
+                set_parameter(&mut ctxt, id, gl::FRAMEBUFFER_DEFAULT_HEIGHT, height);
+            }
+            if let Some(layers) = attachments.default_layers {
+                set_parameter(&mut ctxt, id, gl::FRAMEBUFFER_DEFAULT_LAYERS, layers);
+            }
+            if let Some(samples) = attachments.default_samples {
+                set_parameter(&mut ctxt, id, gl::FRAMEBUFFER_DEFAULT_SAMPLES, samples);
+            }
+
+            // depth-only and stencil-only framebuffers must not read from or write to a color
+            // buffer, otherwise they are incomplete on some implementations
+            let no_color = raw_attachments.is_empty();
+            if no_color {
+                raw_attachments.push(gl::NONE);
+            }
+
             if ctxt.version >= &Version(Api::Gl, 4, 5) ||
                ctxt.extensions.gl_arb_direct_state_access
             {
                 ctxt.gl.NamedFramebufferDrawBuffers(id, raw_attachments.len()
                                                     as gl::types::GLsizei,
                                                     raw_attachments.as_ptr());
+                if no_color {
+                    ctxt.gl.NamedFramebufferReadBuffer(id, gl::NONE);
+                }
 
             } else if ctxt.version >= &Version(Api::Gl, 2, 0) ||
                       ctxt.version >= &Version(Api::GlEs, 3, 0)
@@ -541,9 +805,13 @@ impl FrameBufferObject {
                 bind_framebuffer(&mut ctxt, id, true, false);
                 ctxt.gl.DrawBuffers(raw_attachments.len() as gl::types::GLsizei,
                                     raw_attachments.as_ptr());
+                if no_color {
+                    bind_framebuffer(&mut ctxt, id, false, true);
+                    ctxt.gl.ReadBuffer(gl::NONE);
+                }
 
             } else if ctxt.version >= &Version(Api::GlEs, 2, 0) {
-                assert_eq!(raw_attachments, &[gl::COLOR_ATTACHMENT0]);
+                assert!(no_color || raw_attachments == &[gl::COLOR_ATTACHMENT0]);
 
             } else {
                 unimplemented!();       // FIXME: use an extension
@@ -653,13 +921,95 @@ pub fn bind_framebuffer(ctxt: &mut CommandContext, fbo_id: gl::types::GLuint,
     }
 }
 
+/// Tells the backend that the content of some attachments of a framebuffer is no longer needed.
+///
+/// For the default framebuffer, `attachments` must contain `gl::COLOR`, `gl::DEPTH` or
+/// `gl::STENCIL`. This is only a hint, and does nothing if the backend supports neither
+/// `glInvalidateFramebuffer` nor `glDiscardFramebufferEXT`. The latter can't invalidate a
+/// sub-rectangle.
+pub unsafe fn invalidate_framebuffer(ctxt: &mut CommandContext, fbo_id: gl::types::GLuint,
+                                     attachments: &[gl::types::GLenum], rect: Option<&Rect>)
+{
+    if attachments.is_empty() {
+        return;
+    }
+
+    if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+       ctxt.extensions.gl_arb_invalidate_subdata
+    {
+        bind_framebuffer(ctxt, fbo_id, true, false);
+
+        let target = if ctxt.version >= &Version(Api::Gl, 3, 0) {
+            gl::DRAW_FRAMEBUFFER
+        } else {
+            gl::FRAMEBUFFER
+        };
+
+        if let Some(rect) = rect {
+            ctxt.gl.InvalidateSubFramebuffer(target, attachments.len() as gl::types::GLsizei,
+                                             attachments.as_ptr(),
+                                             rect.left as gl::types::GLint,
+                                             rect.bottom as gl::types::GLint,
+                                             rect.width as gl::types::GLsizei,
+                                             rect.height as gl::types::GLsizei);
+        } else {
+            ctxt.gl.InvalidateFramebuffer(target, attachments.len() as gl::types::GLsizei,
+                                          attachments.as_ptr());
+        }
+
+    } else if ctxt.extensions.gl_ext_discard_framebuffer && rect.is_none() {
+        bind_framebuffer(ctxt, fbo_id, true, false);
+        ctxt.gl.DiscardFramebufferEXT(gl::FRAMEBUFFER, attachments.len() as gl::types::GLsizei,
+                                      attachments.as_ptr());
+    }
+}
+
+/// Sets one of the parameters of a framebuffer without attachments.
+unsafe fn set_parameter(ctxt: &mut CommandContext, id: gl::types::GLuint,
+                        parameter: gl::types::GLenum, value: u32)
+{
+    if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+        ctxt.gl.NamedFramebufferParameteri(id, parameter, value as gl::types::GLint);
+
+    } else if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+              ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+              ctxt.extensions.gl_arb_framebuffer_no_attachments
+    {
+        bind_framebuffer(ctxt, id, true, false);
+        ctxt.gl.FramebufferParameteri(gl::DRAW_FRAMEBUFFER, parameter,
+                                      value as gl::types::GLint);
+
+    } else {
+        unreachable!();
+    }
+}
+
+/// Returns true if the backend supports attaching all the layers of a texture at once, which is
+/// done with `glFramebufferTexture` or one of its equivalents.
+fn is_layered_rendering_supported(ctxt: &CommandContext) -> bool {
+    ctxt.version >= &Version(Api::Gl, 3, 2) ||
+    ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+    ctxt.extensions.gl_arb_direct_state_access ||
+    (ctxt.extensions.gl_ext_direct_state_access && ctxt.extensions.gl_ext_geometry_shader4) ||
+    ctxt.extensions.gl_oes_geometry_shader
+}
+
+/// Returns true if textures of this kind contain multiple layers.
+fn has_layers(bind_point: gl::types::GLenum) -> bool {
+    match bind_point {
+        gl::TEXTURE_3D | gl::TEXTURE_1D_ARRAY | gl::TEXTURE_2D_ARRAY |
+        gl::TEXTURE_2D_MULTISAMPLE_ARRAY => true,
+        _ => false
+    }
+}
+
 unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
                  id: gl::types::GLuint, attachment: RawAttachment)
 {
     if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
         match attachment {
-            RawAttachment::Texture { texture: tex_id, level, layer, .. } => {
-                if layer == 0 {
+            RawAttachment::Texture { bind_point, texture: tex_id, level, layer, layered } => {
+                if layered || !has_layers(bind_point) {
                     ctxt.gl.NamedFramebufferTexture(id, slot, tex_id,
                                                     level as gl::types::GLint);
                 } else {
@@ -678,8 +1028,8 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
               ctxt.extensions.gl_ext_geometry_shader4
     {
         match attachment {
-            RawAttachment::Texture { texture: tex_id, level, layer, .. } => {
-                if layer == 0 {
+            RawAttachment::Texture { bind_point, texture: tex_id, level, layer, layered } => {
+                if layered || !has_layers(bind_point) {
                     ctxt.gl.NamedFramebufferTextureEXT(id, slot, tex_id,
                                                        level as gl::types::GLint);
                 } else {
@@ -698,8 +1048,8 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
         bind_framebuffer(ctxt, id, true, false);
 
         match attachment {
-            RawAttachment::Texture { texture: tex_id, level, layer, .. } => {
-                if layer == 0 {
+            RawAttachment::Texture { bind_point, texture: tex_id, level, layer, layered } => {
+                if layered || !has_layers(bind_point) {
                     ctxt.gl.FramebufferTexture(gl::DRAW_FRAMEBUFFER,
                                                slot, tex_id, level as gl::types::GLint);
                 } else {
@@ -719,7 +1069,7 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
         bind_framebuffer(ctxt, id, true, false);
 
         match attachment {
-            RawAttachment::Texture { bind_point, texture: tex_id, level, layer } => {
+            RawAttachment::Texture { bind_point, texture: tex_id, level, layer, .. } => {
                 match bind_point {
                     gl::TEXTURE_1D | gl::TEXTURE_RECTANGLE => {
                         assert!(layer == 0);
@@ -752,7 +1102,16 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
         bind_framebuffer(ctxt, id, true, true);
 
         match attachment {
-            RawAttachment::Texture { bind_point, texture: tex_id, level, layer } => {
+            RawAttachment::Texture { texture: tex_id, level, layered: true, .. } => {
+                if ctxt.version >= &Version(Api::GlEs, 3, 2) {
+                    ctxt.gl.FramebufferTexture(gl::FRAMEBUFFER, slot, tex_id,
+                                               level as gl::types::GLint);
+                } else {
+                    ctxt.gl.FramebufferTextureOES(gl::FRAMEBUFFER, slot, tex_id,
+                                                  level as gl::types::GLint);
+                }
+            },
+            RawAttachment::Texture { bind_point, texture: tex_id, level, layer, .. } => {
                 match bind_point {
                     gl::TEXTURE_2D => {
                         assert!(layer == 0);
@@ -773,7 +1132,7 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
         bind_framebuffer(ctxt, id, true, true);
 
         match attachment {
-            RawAttachment::Texture { bind_point, texture: tex_id, level, layer } => {
+            RawAttachment::Texture { bind_point, texture: tex_id, level, layer, .. } => {
                 match bind_point {
                     gl::TEXTURE_1D | gl::TEXTURE_RECTANGLE => {
                         assert!(layer == 0);
@@ -0,0 +1,130 @@
+use std::rc::Rc;
+
+use backend::Facade;
+use context::Context;
+
+use framebuffer::{SimpleFrameBuffer, MultiOutputFrameBuffer};
+
+use FboAttachments;
+use Rect;
+use BlitTarget;
+use ops;
+use uniforms;
+
+use Surface;
+use DrawError;
+
+use fbo;
+
+use fbo::ValidationError;
+
+/// A framebuffer without any attachment.
+///
+/// Drawing on it doesn't write anything, but fragment shaders are still executed. This is
+/// useful for shaders that only write to images or to shader storage buffers.
+///
+/// An empty framebuffer has no color buffer, so blitting colors from or to it does nothing.
+pub struct EmptyFrameBuffer {
+    context: Rc<Context>,
+    attachments: fbo::ValidatedAttachments<'static>,
+}
+
+impl EmptyFrameBuffer {
+    /// Returns true if empty framebuffers are supported by the backend.
+    pub fn is_supported<F>(facade: &F) -> bool where F: Facade {
+        let ctxt = facade.get_context().make_current();
+        fbo::ValidatedAttachments::empty(&ctxt, 1, 1, None, None).is_ok()
+    }
+
+    /// Creates an `EmptyFrameBuffer` with the given dimensions. If `layers` is `Some`, the
+    /// framebuffer is layered. If `samples` is `Some`, the framebuffer is multisampled.
+    ///
+    /// Returns an error if empty framebuffers are not supported by the backend, or if one of
+    /// the parameters is zero or too large.
+    pub fn new<F>(facade: &F, width: u32, height: u32, layers: Option<u32>,
+                  samples: Option<u32>) -> Result<EmptyFrameBuffer, ValidationError>
+                  where F: Facade
+    {
+        let attachments = {
+            let ctxt = facade.get_context().make_current();
+            try!(fbo::ValidatedAttachments::empty(&ctxt, width, height, layers, samples))
+        };
+
+        Ok(EmptyFrameBuffer {
+            context: facade.get_context().clone(),
+            attachments: attachments,
+        })
+    }
+
+    /// Returns the number of layers of this framebuffer, or `None` if it is not layered.
+    pub fn get_layers(&self) -> Option<u32> {
+        self.attachments.get_layers()
+    }
+}
+
+impl Surface for EmptyFrameBuffer {
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+             depth: Option<f32>, stencil: Option<i32>)
+    {
+        ops::clear(&self.context, Some(&self.attachments), rect, color, depth, stencil);
+    }
+
+    fn invalidate(&mut self, rect: Option<&Rect>, color: bool, depth: bool, stencil: bool) {
+        ops::invalidate(&self.context, Some(&self.attachments), rect, color, depth, stencil);
+    }
+
+    fn get_dimensions(&self) -> (u32, u32) {
+        self.attachments.get_dimensions()
+    }
+
+    fn get_depth_buffer_bits(&self) -> Option<u16> {
+        None
+    }
+
+    fn get_stencil_buffer_bits(&self) -> Option<u16> {
+        None
+    }
+
+    fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &::Program,
+        uniforms: &U, draw_parameters: &::DrawParameters) -> Result<(), DrawError>
+        where I: Into<::index::IndicesSource<'b>>, U: ::uniforms::Uniforms,
+        V: ::vertex::MultiVerticesSource<'v>
+    {
+        if draw_parameters.depth_test.requires_depth_buffer() || draw_parameters.depth_write {
+            return Err(DrawError::NoDepthBuffer);
+        }
+
+        try!(super::check_viewport(&self.context, draw_parameters));
+
+        ops::draw(&self.context, Some(&self.attachments), vb,
+                  ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
+    }
+
+    fn blit_color<S>(&self, _: &Rect, _: &S, _: &BlitTarget, _: uniforms::MagnifySamplerFilter)
+                     where S: Surface
+    {
+        // an empty framebuffer has no color buffer, so there is nothing to copy
+    }
+
+    fn blit_from_frame(&self, _: &Rect, _: &BlitTarget, _: uniforms::MagnifySamplerFilter) {
+        // an empty framebuffer has no color buffer to copy to
+    }
+
+    fn blit_from_simple_framebuffer(&self, _: &SimpleFrameBuffer, _: &Rect, _: &BlitTarget,
+                                    _: uniforms::MagnifySamplerFilter)
+    {
+        // an empty framebuffer has no color buffer to copy to
+    }
+
+    fn blit_from_multioutput_framebuffer(&self, _: &MultiOutputFrameBuffer, _: &Rect,
+                                         _: &BlitTarget, _: uniforms::MagnifySamplerFilter)
+    {
+        // an empty framebuffer has no color buffer to copy to
+    }
+}
+
+impl FboAttachments for EmptyFrameBuffer {
+    fn get_attachments(&self) -> Option<&fbo::ValidatedAttachments> {
+        Some(&self.attachments)
+    }
+}
@@ -7,7 +7,7 @@ shaders that write to `gl_FragColor`.
 ```no_run
 # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
 # let texture: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
-let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
 // framebuffer.draw(...);    // draws over `texture`
 ```
 
@@ -19,7 +19,7 @@ a `MultiOutputFrameBuffer`.
 # let texture1: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
 # let texture2: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
 let output = &[ ("output1", &texture1), ("output2", &texture2) ];
-let framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display, output);
+let framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display, output).unwrap();
 // framebuffer.draw(...);
 
 // example shader:
@@ -33,12 +33,74 @@ let framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display, outp
 //     }
 ```
 
+`Surface::clear` only works with floating-point and normalized color attachments, and clears
+all of them with the same value. Integral and unsigned attachments, or attachments that need
+different values, must be cleared with `clear_color_int`/`clear_color_uint` on a
+`SimpleFrameBuffer`, or with the `clear_color_attachment*` methods of a
+`MultiOutputFrameBuffer`.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+# let albedo: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
+# let ids: glium::texture::UnsignedTexture2d = unsafe { ::std::mem::uninitialized() };
+use glium::framebuffer::ToColorAttachment;
+
+// attachments of different types must be turned into `ColorAttachment`s first
+let albedo = albedo.to_color_attachment();
+let ids = ids.to_color_attachment();
+
+let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                         &[("albedo", &albedo), ("ids", &ids)]).unwrap();
+framebuffer.clear_color_attachment("albedo", (0.0, 0.0, 0.0, 1.0)).unwrap();
+framebuffer.clear_color_attachment_uint("ids", (0, 0, 0, 0)).unwrap();
+```
+
+Texture arrays and 3D textures are attached with all their layers. This is called layered
+rendering: a geometry shader chooses the layer that each primitive is drawn on by writing
+to `gl_Layer`. All the attachments of a framebuffer must then be layered and have the same
+number of layers, otherwise the constructors return a `ValidationError`. Layered rendering
+requires OpenGL 3.2, OpenGL ES 3.2 or `GL_OES_geometry_shader`, and the constructors return
+`ValidationError::LayeredAttachmentsNotSupported` on other backends.
+
+Only texture arrays and 3D textures can be attached, layered or one layer at a time. This
+version of glium doesn't have cubemap textures, so there is no way to attach a cube face or all
+the faces of a cubemap.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+# let texture: glium::texture::Texture2dArray = unsafe { ::std::mem::uninitialized() };
+# let depth: glium::texture::DepthTexture2dArray = unsafe { ::std::mem::uninitialized() };
+let framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display, &texture,
+                                                                          &depth).unwrap();
+// framebuffer.draw(...);    // draws over all the layers of `texture`
+```
+
+Shadow maps and other depth-only passes can use a `SimpleFrameBuffer` that only has a depth
+buffer (or only a stencil buffer). The program used to draw on it must not write any color
+output, otherwise drawing returns `DrawError::NoColorBuffer`. This is only checked if the backend
+can list the outputs of the program (see `Program::get_fragment_outputs_if_supported`), and the
+color outputs are silently discarded otherwise.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+# let shadow_map: glium::texture::DepthTexture2d = unsafe { ::std::mem::uninitialized() };
+let framebuffer = glium::framebuffer::SimpleFrameBuffer::depth_only(&display, &shadow_map).unwrap();
+// framebuffer.draw(...);    // only writes to `shadow_map`
+```
+
+Shaders that only write to images or to shader storage buffers can draw on an
+`EmptyFrameBuffer`, which doesn't have any attachment but has explicit dimensions.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+let framebuffer = glium::framebuffer::EmptyFrameBuffer::new(&display, 256, 256, None, None);
+```
+
 **Note**: depth-stencil attachments are not yet implemented.
 
 */
 use std::rc::Rc;
 
-use texture::Texture2d;
 use texture::TextureAnyMipmap;
 
 use backend::Facade;
@@ -57,12 +119,18 @@ use DrawError;
 
 use {fbo, gl};
 
+pub use self::empty::EmptyFrameBuffer;
 pub use self::render_buffer::{RenderBuffer, RenderBufferAny, DepthRenderBuffer};
 pub use self::render_buffer::{StencilRenderBuffer, DepthStencilRenderBuffer};
+pub use fbo::ValidationError;
 
+mod empty;
 mod render_buffer;
 
 /// A framebuffer which has only one color attachment.
+///
+/// The constructors return a `ValidationError` if the attachments can't be used together, for
+/// example if only some of them are layered.
 pub struct SimpleFrameBuffer<'a> {
     context: Rc<Context>,
     attachments: fbo::ValidatedAttachments<'a>,
@@ -71,31 +139,33 @@ pub struct SimpleFrameBuffer<'a> {
 impl<'a> SimpleFrameBuffer<'a> {
     /// Creates a `SimpleFrameBuffer` with a single color attachment and no depth
     /// nor stencil buffer.
-    pub fn new<F, C>(facade: &F, color: &'a C) -> SimpleFrameBuffer<'a>
-                  where C: ToColorAttachment, F: Facade
+    pub fn new<F, C>(facade: &F, color: &'a C) -> Result<SimpleFrameBuffer<'a>, ValidationError>
+                     where C: ToColorAttachment, F: Facade
     {
-        SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(), None, None, None)
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()), None, None, None)
     }
 
     /// Creates a `SimpleFrameBuffer` with a single color attachment and a depth
     /// buffer, but no stencil buffer.
     pub fn with_depth_buffer<F, C, D>(facade: &F, color: &'a C, depth: &'a D)
-                                      -> SimpleFrameBuffer<'a>
+                                      -> Result<SimpleFrameBuffer<'a>, ValidationError>
                                       where C: ToColorAttachment, D: ToDepthAttachment, F: Facade
     {
-        SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(),
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()),
                                     Some(depth.to_depth_attachment()), None, None)
     }
 
     /// Creates a `SimpleFrameBuffer` with a single color attachment, a depth
     /// buffer, and a stencil buffer.
     pub fn with_depth_and_stencil_buffer<F, C, D, S>(facade: &F, color: &'a C, depth: &'a D,
-                                                     stencil: &'a S) -> SimpleFrameBuffer<'a>
+                                                     stencil: &'a S)
+                                                     -> Result<SimpleFrameBuffer<'a>,
+                                                               ValidationError>
                                                      where C: ToColorAttachment,
                                                            D: ToDepthAttachment,
                                                            S: ToStencilAttachment, F: Facade
     {
-        SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(),
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()),
                                     Some(depth.to_depth_attachment()),
                                     Some(stencil.to_stencil_attachment()), None)
     }
@@ -103,41 +173,73 @@ impl<'a> SimpleFrameBuffer<'a> {
     /// Creates a `SimpleFrameBuffer` with a single color attachment and a stencil
     /// buffer, but no depth buffer.
     pub fn with_stencil_buffer<F, C, S>(facade: &F, color: &'a C, stencil: &'a S)
-                                        -> SimpleFrameBuffer<'a>
+                                        -> Result<SimpleFrameBuffer<'a>, ValidationError>
                                         where C: ToColorAttachment, S: ToStencilAttachment,
                                               F: Facade
     {
-        SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(), None,
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()), None,
                                     Some(stencil.to_stencil_attachment()), None)
     }
 
     /// Creates a `SimpleFrameBuffer` with a single color attachment and a depth-stencil buffer.
     pub fn with_depth_stencil_buffer<F, C, D>(facade: &F, color: &'a C, depthstencil: &'a D)
-                                              -> SimpleFrameBuffer<'a>
+                                              -> Result<SimpleFrameBuffer<'a>, ValidationError>
                                               where C: ToColorAttachment,
                                                     D: ToDepthStencilAttachment, F: Facade
     {
-        SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(), None, None,
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()), None, None,
                                     Some(depthstencil.to_depth_stencil_attachment()))
     }
 
+    /// Creates a `SimpleFrameBuffer` with a depth buffer but no color attachment.
+    ///
+    /// The program used to draw on this framebuffer must not write any color output. Drawing
+    /// returns `DrawError::NoColorBuffer` if it does, but only if the backend can list the
+    /// outputs of the program (see `Program::get_fragment_outputs_if_supported`). Otherwise the
+    /// color outputs are silently discarded.
+    pub fn depth_only<F, D>(facade: &F, depth: &'a D)
+                            -> Result<SimpleFrameBuffer<'a>, ValidationError>
+                            where D: ToDepthAttachment, F: Facade
+    {
+        SimpleFrameBuffer::new_impl(facade, None, Some(depth.to_depth_attachment()), None, None)
+    }
+
+    /// Creates a `SimpleFrameBuffer` with a stencil buffer but no color attachment.
+    ///
+    /// The program used to draw on this framebuffer must not write any color output. Drawing
+    /// returns `DrawError::NoColorBuffer` if it does, but only if the backend can list the
+    /// outputs of the program (see `Program::get_fragment_outputs_if_supported`). Otherwise the
+    /// color outputs are silently discarded.
+    pub fn stencil_only<F, S>(facade: &F, stencil: &'a S)
+                              -> Result<SimpleFrameBuffer<'a>, ValidationError>
+                              where S: ToStencilAttachment, F: Facade
+    {
+        SimpleFrameBuffer::new_impl(facade, None, None, Some(stencil.to_stencil_attachment()),
+                                    None)
+    }
 
-    fn new_impl<F>(facade: &F, color: ColorAttachment<'a>, depth: Option<DepthAttachment<'a>>,
-                   stencil: Option<StencilAttachment<'a>>,
+    fn new_impl<F>(facade: &F, color: Option<ColorAttachment<'a>>,
+                   depth: Option<DepthAttachment<'a>>, stencil: Option<StencilAttachment<'a>>,
                    depthstencil: Option<DepthStencilAttachment<'a>>)
-                   -> SimpleFrameBuffer<'a> where F: Facade
+                   -> Result<SimpleFrameBuffer<'a>, ValidationError> where F: Facade
     {
-        let color = match color {
+        let color = color.map(|color| match color {
             ColorAttachment::Texture(tex) => fbo::Attachment::TextureLayer {
                 texture: tex.get_texture(), layer: tex.get_layer(), level: tex.get_level()
             },
+            ColorAttachment::LayeredTexture(tex) => fbo::Attachment::TextureLayered {
+                texture: tex.get_texture(), level: tex.get_level()
+            },
             ColorAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
-        };
+        });
 
         let depth = depth.map(|depth| match depth {
             DepthAttachment::Texture(tex) => fbo::Attachment::TextureLayer {
                 texture: tex.get_texture(), layer: tex.get_layer(), level: tex.get_level()
             },
+            DepthAttachment::LayeredTexture(tex) => fbo::Attachment::TextureLayered {
+                texture: tex.get_texture(), level: tex.get_level()
+            },
             DepthAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
         });
 
@@ -145,6 +247,9 @@ impl<'a> SimpleFrameBuffer<'a> {
             StencilAttachment::Texture(tex) => fbo::Attachment::TextureLayer {
                 texture: tex.get_texture(), layer: tex.get_layer(), level: tex.get_level()
             },
+            StencilAttachment::LayeredTexture(tex) => fbo::Attachment::TextureLayered {
+                texture: tex.get_texture(), level: tex.get_level()
+            },
             StencilAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
         });
 
@@ -152,11 +257,14 @@ impl<'a> SimpleFrameBuffer<'a> {
             DepthStencilAttachment::Texture(tex) => fbo::Attachment::TextureLayer {
                 texture: tex.get_texture(), layer: tex.get_layer(), level: tex.get_level()
             },
+            DepthStencilAttachment::LayeredTexture(tex) => fbo::Attachment::TextureLayered {
+                texture: tex.get_texture(), level: tex.get_level()
+            },
             DepthStencilAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
         });
 
         let attachments = fbo::FramebufferAttachments {
-            colors: vec![(0, color)],
+            colors: color.into_iter().map(|color| (0, color)).collect(),
             depth_stencil: if let (Some(depth), Some(stencil)) = (depth, stencil) {
                 fbo::FramebufferDepthStencilAttachments::DepthAndStencilAttachments(depth, stencil)
             } else if let Some(depth) = depth {
@@ -170,12 +278,57 @@ impl<'a> SimpleFrameBuffer<'a> {
             }
         };
 
-        let attachments = attachments.validate().unwrap();
+        let attachments = {
+            let ctxt = facade.get_context().make_current();
+            try!(attachments.validate(&ctxt))
+        };
 
-        SimpleFrameBuffer {
+        Ok(SimpleFrameBuffer {
             context: facade.get_context().clone(),
             attachments: attachments,
-        }
+        })
+    }
+}
+
+impl<'a> SimpleFrameBuffer<'a> {
+    /// Clears the color attachment, which must be an integral texture.
+    ///
+    /// `clear_color` only works with floating-point and normalized attachments.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the backend doesn't support clearing a single color attachment.
+    pub fn clear_color_int(&mut self, red: i32, green: i32, blue: i32, alpha: i32) {
+        self.clear_color_int_if_supported(red, green, blue, alpha)
+            .expect("Clearing a single color attachment is not supported by the backend");
+    }
+
+    /// Same as `clear_color_int`, but returns an error instead of panicking.
+    pub fn clear_color_int_if_supported(&mut self, red: i32, green: i32, blue: i32, alpha: i32)
+                                        -> Result<(), ClearAttachmentError>
+    {
+        ops::clear_buffer(&self.context, Some(&self.attachments), None, 0,
+                          ops::ClearBufferData::Integral([red, green, blue, alpha]))
+    }
+
+    /// Clears the color attachment, which must be an unsigned texture.
+    ///
+    /// `clear_color` only works with floating-point and normalized attachments.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the backend doesn't support clearing a single color attachment.
+    pub fn clear_color_uint(&mut self, red: u32, green: u32, blue: u32, alpha: u32) {
+        self.clear_color_uint_if_supported(red, green, blue, alpha)
+            .expect("Clearing a single color attachment is not supported by the backend");
+    }
+
+    /// Same as `clear_color_uint`, but returns an error instead of panicking.
+    pub fn clear_color_uint_if_supported(&mut self, red: u32, green: u32, blue: u32, alpha: u32)
+                                         -> Result<(), ClearAttachmentError>
+    {
+        ops::clear_buffer(&self.context, Some(&self.attachments), None, 0,
+                          ops::ClearBufferData::Unsigned([red, green, blue, alpha]))
     }
 }
 
@@ -186,6 +339,10 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
         ops::clear(&self.context, Some(&self.attachments), rect, color, depth, stencil);
     }
 
+    fn invalidate(&mut self, rect: Option<&Rect>, color: bool, depth: bool, stencil: bool) {
+        ops::invalidate(&self.context, Some(&self.attachments), rect, color, depth, stencil);
+    }
+
     fn get_dimensions(&self) -> (u32, u32) {
         self.attachments.get_dimensions()
     }
@@ -209,19 +366,16 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
             return Err(DrawError::NoDepthBuffer);
         }
 
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
+        if !self.attachments.has_color_attachments() {
+            if let Some(outputs) = program.get_fragment_outputs_if_supported() {
+                if !outputs.is_empty() {
+                    return Err(DrawError::NoColorBuffer);
+                }
             }
         }
 
+        try!(check_viewport(&self.context, draw_parameters));
+
         ops::draw(&self.context, Some(&self.attachments), vb,
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
     }
@@ -274,11 +428,11 @@ pub struct MultiOutputFrameBuffer<'a> {
 impl<'a> MultiOutputFrameBuffer<'a> {
     /// Creates a new `MultiOutputFrameBuffer`.
     ///
-    /// # Panic
-    ///
-    /// Panics if all attachments don't have the same dimensions.
-    pub fn new<F>(facade: &F, color_attachments: &[(&str, &'a Texture2d)])
-                  -> MultiOutputFrameBuffer<'a> where F: Facade
+    /// Returns an error if some attachments are layered and others are not, or if the
+    /// attachments are layered and the backend doesn't support it.
+    pub fn new<F, C>(facade: &F, color_attachments: &[(&str, &'a C)])
+                     -> Result<MultiOutputFrameBuffer<'a>, ValidationError>
+                     where C: ToColorAttachment, F: Facade
     {
         MultiOutputFrameBuffer::new_impl(facade, color_attachments,
                                          None::<&DepthRenderBuffer>,
@@ -287,25 +441,34 @@ impl<'a> MultiOutputFrameBuffer<'a> {
 
     /// Creates a `MultiOutputFrameBuffer` with a depth buffer.
     ///
-    /// # Panic
-    ///
-    /// Panics if all attachments don't have the same dimensions.
-    pub fn with_depth_buffer<F, D>(facade: &F, color_attachments: &[(&str, &'a Texture2d)],
-                                   depth: &'a D) -> MultiOutputFrameBuffer<'a>
-                                   where D: ToDepthAttachment, F: Facade
+    /// Returns an error if some attachments are layered and others are not, or if the
+    /// attachments are layered and the backend doesn't support it.
+    pub fn with_depth_buffer<F, C, D>(facade: &F, color_attachments: &[(&str, &'a C)],
+                                      depth: &'a D)
+                                      -> Result<MultiOutputFrameBuffer<'a>, ValidationError>
+                                      where C: ToColorAttachment, D: ToDepthAttachment, F: Facade
     {
         MultiOutputFrameBuffer::new_impl(facade, color_attachments, Some(depth),
                                          None::<&StencilRenderBuffer>)
     }
 
-    fn new_impl<F, D, S>(facade: &F, color: &[(&str, &'a Texture2d)],
-                         depth: Option<&'a D>, stencil: Option<&'a S>)
-                         -> MultiOutputFrameBuffer<'a> where D: ToDepthAttachment, F: Facade
+    fn new_impl<F, C, D, S>(facade: &F, color: &[(&str, &'a C)],
+                            depth: Option<&'a D>, stencil: Option<&'a S>)
+                            -> Result<MultiOutputFrameBuffer<'a>, ValidationError>
+                            where C: ToColorAttachment, D: ToDepthAttachment, F: Facade
     {
-        let color = color.iter().map(|&(name, tex)| {
-            (name.to_string(), fbo::Attachment::TextureLayer {
-                texture: tex, layer: 0, level: 0
-            })
+        let color = color.iter().map(|&(name, color)| {
+            let attachment = match color.to_color_attachment() {
+                ColorAttachment::Texture(tex) => fbo::Attachment::TextureLayer {
+                    texture: tex.get_texture(), layer: tex.get_layer(), level: tex.get_level()
+                },
+                ColorAttachment::LayeredTexture(tex) => fbo::Attachment::TextureLayered {
+                    texture: tex.get_texture(), level: tex.get_level()
+                },
+                ColorAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
+            };
+
+            (name.to_string(), attachment)
         }).collect::<Vec<_>>();
 
         let example_color = color.iter().enumerate().map(|(index, &(_, tex))| {
@@ -316,6 +479,9 @@ impl<'a> MultiOutputFrameBuffer<'a> {
             DepthAttachment::Texture(tex) => fbo::Attachment::TextureLayer {
                 texture: tex.get_texture(), layer: tex.get_layer(), level: tex.get_level()
             },
+            DepthAttachment::LayeredTexture(tex) => fbo::Attachment::TextureLayered {
+                texture: tex.get_texture(), level: tex.get_level()
+            },
             DepthAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
         });
 
@@ -346,17 +512,121 @@ impl<'a> MultiOutputFrameBuffer<'a> {
             } else {
                 fbo::FramebufferDepthStencilAttachments::None
             }
-        }.validate().unwrap();
+        };
+
+        let example_attachments = {
+            let ctxt = facade.get_context().make_current();
+            try!(example_attachments.validate(&ctxt))
+        };
 
-        MultiOutputFrameBuffer {
+        Ok(MultiOutputFrameBuffer {
             context: facade.get_context().clone(),
             example_attachments: example_attachments,
             color_attachments: color,
             depth_attachment: depth,
             stencil_attachment: stencil,
+        })
+    }
+
+    /// Clears a single color attachment, which must be a floating-point or normalized texture.
+    ///
+    /// Returns `ClearAttachmentError::AttachmentNotFound` if there is no attachment with this
+    /// name.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the backend doesn't support clearing a single color attachment.
+    pub fn clear_color_attachment(&mut self, name: &str, color: (f32, f32, f32, f32))
+                                  -> Result<(), ClearAttachmentError>
+    {
+        let data = ops::ClearBufferData::Float([color.0, color.1, color.2, color.3]);
+        self.clear_color_attachment_expect(name, data)
+    }
+
+    /// Same as `clear_color_attachment`, but returns an error instead of panicking if the backend
+    /// doesn't support clearing a single color attachment.
+    pub fn clear_color_attachment_if_supported(&mut self, name: &str,
+                                               color: (f32, f32, f32, f32))
+                                               -> Result<(), ClearAttachmentError>
+    {
+        let data = ops::ClearBufferData::Float([color.0, color.1, color.2, color.3]);
+        self.clear_color_attachment_impl(name, data)
+    }
+
+    /// Clears a single color attachment, which must be an integral texture.
+    ///
+    /// Returns `ClearAttachmentError::AttachmentNotFound` if there is no attachment with this
+    /// name.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the backend doesn't support clearing a single color attachment.
+    pub fn clear_color_attachment_int(&mut self, name: &str, color: (i32, i32, i32, i32))
+                                      -> Result<(), ClearAttachmentError>
+    {
+        let data = ops::ClearBufferData::Integral([color.0, color.1, color.2, color.3]);
+        self.clear_color_attachment_expect(name, data)
+    }
+
+    /// Same as `clear_color_attachment_int`, but returns an error instead of panicking if the
+    /// backend doesn't support clearing a single color attachment.
+    pub fn clear_color_attachment_int_if_supported(&mut self, name: &str,
+                                                   color: (i32, i32, i32, i32))
+                                                   -> Result<(), ClearAttachmentError>
+    {
+        let data = ops::ClearBufferData::Integral([color.0, color.1, color.2, color.3]);
+        self.clear_color_attachment_impl(name, data)
+    }
+
+    /// Clears a single color attachment, which must be an unsigned texture.
+    ///
+    /// Returns `ClearAttachmentError::AttachmentNotFound` if there is no attachment with this
+    /// name.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the backend doesn't support clearing a single color attachment.
+    pub fn clear_color_attachment_uint(&mut self, name: &str, color: (u32, u32, u32, u32))
+                                       -> Result<(), ClearAttachmentError>
+    {
+        let data = ops::ClearBufferData::Unsigned([color.0, color.1, color.2, color.3]);
+        self.clear_color_attachment_expect(name, data)
+    }
+
+    /// Same as `clear_color_attachment_uint`, but returns an error instead of panicking if the
+    /// backend doesn't support clearing a single color attachment.
+    pub fn clear_color_attachment_uint_if_supported(&mut self, name: &str,
+                                                    color: (u32, u32, u32, u32))
+                                                    -> Result<(), ClearAttachmentError>
+    {
+        let data = ops::ClearBufferData::Unsigned([color.0, color.1, color.2, color.3]);
+        self.clear_color_attachment_impl(name, data)
+    }
+
+    fn clear_color_attachment_expect(&mut self, name: &str, data: ops::ClearBufferData)
+                                     -> Result<(), ClearAttachmentError>
+    {
+        match self.clear_color_attachment_impl(name, data) {
+            Err(ClearAttachmentError::NotSupported) => {
+                panic!("Clearing a single color attachment is not supported by the backend")
+            },
+            r => r
         }
     }
 
+    fn clear_color_attachment_impl(&mut self, name: &str, data: ops::ClearBufferData)
+                                   -> Result<(), ClearAttachmentError>
+    {
+        // in `example_attachments`, the draw buffers are in the same order as the attachments
+        let draw_buffer = match self.color_attachments.iter().position(|&(ref n, _)| n == name) {
+            Some(p) => p,
+            None => return Err(ClearAttachmentError::AttachmentNotFound),
+        };
+
+        ops::clear_buffer(&self.context, Some(&self.example_attachments), None,
+                          draw_buffer as u32, data)
+    }
+
     fn build_attachments(&self, program: &Program) -> fbo::ValidatedAttachments {
         let mut colors = Vec::new();
 
@@ -369,6 +639,8 @@ impl<'a> MultiOutputFrameBuffer<'a> {
             colors.push((location, attachment));
         }
 
+        // the same attachments have been validated when building the framebuffer
+        let ctxt = self.context.make_current();
         fbo::FramebufferAttachments {
             colors: colors,
             depth_stencil: if let Some(depth) = self.depth_attachment {
@@ -376,7 +648,7 @@ impl<'a> MultiOutputFrameBuffer<'a> {
             } else {        // FIXME: other cases
                 fbo::FramebufferDepthStencilAttachments::None
             },
-        }.validate().unwrap()
+        }.validate(&ctxt).unwrap()
     }
 }
 
@@ -388,6 +660,11 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
                    color, depth, stencil);
     }
 
+    fn invalidate(&mut self, rect: Option<&Rect>, color: bool, depth: bool, stencil: bool) {
+        ops::invalidate(&self.context, Some(&self.example_attachments), rect, color, depth,
+                        stencil);
+    }
+
     fn get_dimensions(&self) -> (u32, u32) {
         self.example_attachments.get_dimensions()
     }
@@ -411,18 +688,7 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
             return Err(DrawError::NoDepthBuffer);
         }
 
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
+        try!(check_viewport(&self.context, draw_parameters));
 
         ops::draw(&self.context, Some(&self.build_attachments(program)), vb,
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
@@ -464,11 +730,37 @@ impl<'a> FboAttachments for MultiOutputFrameBuffer<'a> {
     }
 }
 
+/// Returns `DrawError::ViewportTooLarge` if the viewport of the draw parameters is larger than
+/// what the backend supports.
+fn check_viewport(context: &Context, draw_parameters: &::DrawParameters)
+                  -> Result<(), DrawError>
+{
+    if let Some(viewport) = draw_parameters.viewport {
+        let max_dims = context.capabilities().max_viewport_dims;
+        if viewport.width > max_dims.0 as u32 || viewport.height > max_dims.1 as u32 {
+            return Err(DrawError::ViewportTooLarge);
+        }
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when clearing a single color attachment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClearAttachmentError {
+    /// There is no color attachment with this name.
+    AttachmentNotFound,
+    /// Clearing a single color attachment requires OpenGL 3.0 or OpenGL ES 3.0.
+    NotSupported,
+}
+
 /// Describes an attachment for a color buffer.
 #[derive(Copy, Clone)]
 pub enum ColorAttachment<'a> {
     /// A texture.
     Texture(TextureAnyMipmap<'a>),
+    /// All the layers of a texture array or of a 3D texture.
+    LayeredTexture(TextureAnyMipmap<'a>),
     /// A render buffer.
     RenderBuffer(&'a RenderBuffer),
 }
@@ -479,11 +771,19 @@ pub trait ToColorAttachment {
     fn to_color_attachment(&self) -> ColorAttachment;
 }
 
+impl<'a> ToColorAttachment for ColorAttachment<'a> {
+    fn to_color_attachment(&self) -> ColorAttachment {
+        *self
+    }
+}
+
 /// Describes an attachment for a depth buffer.
 #[derive(Copy, Clone)]
 pub enum DepthAttachment<'a> {
     /// A texture.
     Texture(TextureAnyMipmap<'a>),
+    /// All the layers of a texture array or of a 3D texture.
+    LayeredTexture(TextureAnyMipmap<'a>),
     /// A render buffer.
     RenderBuffer(&'a DepthRenderBuffer),
 }
@@ -499,6 +799,8 @@ pub trait ToDepthAttachment {
 pub enum StencilAttachment<'a> {
     /// A texture.
     Texture(TextureAnyMipmap<'a>),
+    /// All the layers of a texture array or of a 3D texture.
+    LayeredTexture(TextureAnyMipmap<'a>),
     /// A render buffer.
     RenderBuffer(&'a StencilRenderBuffer),
 }
@@ -514,6 +816,8 @@ pub trait ToStencilAttachment {
 pub enum DepthStencilAttachment<'a> {
     /// A texture.
     Texture(TextureAnyMipmap<'a>),
+    /// All the layers of a texture array or of a 3D texture.
+    LayeredTexture(TextureAnyMipmap<'a>),
     /// A render buffer.
     RenderBuffer(&'a DepthStencilRenderBuffer),
 }
@@ -1,4 +1,5 @@
-use buffer::{BufferView, BufferViewSlice, BufferViewAny, BufferType};
+use buffer::{BufferView, BufferViewSlice, BufferViewAny, BufferType, BufferMode};
+use buffer::BufferCreationError;
 use gl;
 use BufferViewExt;
 use GlObject;
@@ -27,7 +28,7 @@ impl<T> IndexBuffer<T> where T: Index {
     {
         IndexBuffer {
             buffer: BufferView::new(facade, data.as_ref(), BufferType::ElementArrayBuffer,
-                                    false).unwrap().into(),
+                                    BufferMode::Immutable).unwrap().into(),
             primitives: prim,
         }
     }
@@ -38,7 +39,7 @@ impl<T> IndexBuffer<T> where T: Index {
     {
         IndexBuffer {
             buffer: BufferView::new(facade, data.as_ref(), BufferType::ElementArrayBuffer,
-                                    true).unwrap().into(),
+                                    BufferMode::Dynamic).unwrap().into(),
             primitives: prim,
         }
     }
@@ -49,7 +50,7 @@ impl<T> IndexBuffer<T> where T: Index {
     {
         IndexBuffer {
             buffer: BufferView::empty(facade, BufferType::ElementArrayBuffer, len,
-                                      false).unwrap().into(),
+                                      BufferMode::Immutable).unwrap().into(),
             primitives: prim,
         }
     }
@@ -60,11 +61,41 @@ impl<T> IndexBuffer<T> where T: Index {
     {
         IndexBuffer {
             buffer: BufferView::empty(facade, BufferType::ElementArrayBuffer, len,
-                                      true).unwrap().into(),
+                                      BufferMode::Dynamic).unwrap().into(),
             primitives: prim,
         }
     }
 
+    /// Builds a new index buffer with the given mode.
+    ///
+    /// Returns an error if the mode is not supported by the backend.
+    pub fn with_mode<F, D>(facade: &F, prim: PrimitiveType, data: D, mode: BufferMode)
+                           -> Result<IndexBuffer<T>, BufferCreationError>
+                           where F: Facade, D: AsRef<[T]>
+    {
+        let buffer = try!(BufferView::new(facade, data.as_ref(),
+                                          BufferType::ElementArrayBuffer, mode));
+
+        Ok(IndexBuffer {
+            buffer: buffer,
+            primitives: prim,
+        })
+    }
+
+    /// Builds a new empty index buffer with the given mode.
+    ///
+    /// Returns an error if the mode is not supported by the backend.
+    pub fn empty_with_mode<F>(facade: &F, prim: PrimitiveType, len: usize, mode: BufferMode)
+                              -> Result<IndexBuffer<T>, BufferCreationError> where F: Facade
+    {
+        let buffer = try!(BufferView::empty(facade, BufferType::ElementArrayBuffer, len, mode));
+
+        Ok(IndexBuffer {
+            buffer: buffer,
+            primitives: prim,
+        })
+    }
+
     /// Returns the type of primitives associated with this index buffer.
     pub fn get_primitives_type(&self) -> PrimitiveType {
         self.primitives
@@ -0,0 +1,401 @@
+/*!
+Functions that convert index data between primitive types.
+
+Strips, fans and loops can be turned into lists, which is what geometry shaders and transform
+feedback sessions usually expect. Lists of lines and triangles can be turned into lists with
+adjacency information, which is required by geometry shaders that take `lines_adjacency` or
+`triangles_adjacency` as input.
+
+```
+use glium::index::PrimitiveType;
+use glium::index::convert;
+
+let strip = [0u16, 1, 2, 3];
+let list = convert::convert(&strip, PrimitiveType::TriangleStrip,
+                            PrimitiveType::TrianglesList).unwrap();
+assert_eq!(list, vec![0, 1, 2, 2, 1, 3]);
+
+let adjacency = convert::triangles_adjacency(&list);
+assert_eq!(adjacency, vec![0, 2, 1, 3, 2, 1, 2, 0, 1, 2, 3, 1]);
+```
+
+Adjacency is computed by comparing indices. If two triangles share an edge but their vertices
+are duplicated in the vertex buffer (for example because they have different normals), they
+are not considered adjacent. Use `optimize::deduplicate_vertices` or build the adjacency from a
+list of indices that only refers to the positions in that case.
+
+*/
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use index::{from_usize, to_usize, Index, PrimitiveType};
+
+/// Error that can happen when converting index data.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ConversionNotSupportedError {
+    /// The primitive type of the source data.
+    pub from: PrimitiveType,
+    /// The requested primitive type.
+    pub to: PrimitiveType,
+}
+
+impl fmt::Display for ConversionNotSupportedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "Converting from {:?} to {:?} is not supported", self.from, self.to)
+    }
+}
+
+impl Error for ConversionNotSupportedError {
+    fn description(&self) -> &str {
+        "The conversion between these primitive types is not supported"
+    }
+}
+
+/// Converts a list of indices from one primitive type to another.
+///
+/// The supported conversions are:
+///
+///  - Any type to itself.
+///  - Any type except patches to `Points`, which keeps each vertex once.
+///  - Any line or triangle type to `LinesList`. Triangles are turned into their edges, and each
+///    edge is kept once.
+///  - Any triangle type to `TrianglesList`. Degenerate triangles of strips are removed.
+///  - Any line type to `LinesListAdjacency`, and any triangle type to `TrianglesListAdjacency`.
+///    See `lines_adjacency` and `triangles_adjacency`.
+///
+/// The winding of the triangles is preserved.
+///
+/// # Panic
+///
+/// Panics if the number of indices is not valid for the source type, for example if a list of
+/// triangles doesn't contain a multiple of three indices.
+pub fn convert<I>(indices: &[I], from: PrimitiveType, to: PrimitiveType)
+                  -> Result<Vec<I>, ConversionNotSupportedError> where I: Index
+{
+    if from == to {
+        return Ok(indices.to_vec());
+    }
+
+    let result = match (from, to) {
+        (PrimitiveType::Patches { .. }, _) => None,
+        (_, PrimitiveType::Points) => Some(unique_vertices(indices)),
+        (_, PrimitiveType::LinesList) => {
+            lines_list(indices, from).or_else(|| {
+                triangles_list(indices, from).map(|triangles| triangles_edges(&triangles))
+            })
+        },
+        (_, PrimitiveType::LinesListAdjacency) => {
+            lines_list(indices, from).map(|lines| lines_adjacency(&lines))
+        },
+        (_, PrimitiveType::TrianglesList) => triangles_list(indices, from),
+        (_, PrimitiveType::TrianglesListAdjacency) => {
+            triangles_list(indices, from).map(|triangles| triangles_adjacency(&triangles))
+        },
+        _ => None,
+    };
+
+    result.ok_or(ConversionNotSupportedError { from: from, to: to })
+}
+
+/// Builds a `LinesListAdjacency` list from a `LinesList` list.
+///
+/// Each line `(a, b)` becomes `(p, a, b, n)`, where `p` is the start of another line that ends
+/// with `a` and `n` is the end of another line that starts with `b`. Lines with the opposite
+/// direction are used if there is none. At the ends of a line strip, `p` is `a` and `n` is `b`.
+///
+/// # Panic
+///
+/// Panics if the number of indices is not a multiple of 2.
+pub fn lines_adjacency<I>(indices: &[I]) -> Vec<I> where I: Index {
+    assert!(indices.len() % 2 == 0, "The number of indices must be a multiple of 2");
+
+    // for each vertex, the lines that start and end with it
+    let mut starts: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut ends: HashMap<usize, Vec<usize>> = HashMap::new();
+
+    for (line, chunk) in indices.chunks(2).enumerate() {
+        starts.entry(to_usize(chunk[0])).or_insert_with(Vec::new).push(line);
+        ends.entry(to_usize(chunk[1])).or_insert_with(Vec::new).push(line);
+    }
+
+    // finds the other vertex of a line that is not `line` and that contains `vertex` at
+    // position `position`, trying first with the lines of `preferred`
+    let find = |line: usize, vertex: I, preferred: &HashMap<usize, Vec<usize>>,
+                other: &HashMap<usize, Vec<usize>>, position: usize| -> Option<I>
+    {
+        let key = to_usize(vertex);
+
+        if let Some(lines) = preferred.get(&key) {
+            if let Some(&l) = lines.iter().find(|&&l| l != line) {
+                return Some(indices[l * 2 + 1 - position]);
+            }
+        }
+
+        if let Some(lines) = other.get(&key) {
+            if let Some(&l) = lines.iter().find(|&&l| l != line) {
+                return Some(indices[l * 2 + position]);
+            }
+        }
+
+        None
+    };
+
+    let mut result = Vec::with_capacity(indices.len() * 2);
+
+    for (line, chunk) in indices.chunks(2).enumerate() {
+        let (a, b) = (chunk[0], chunk[1]);
+
+        result.push(find(line, a, &ends, &starts, 1).unwrap_or(a));
+        result.push(a);
+        result.push(b);
+        result.push(find(line, b, &starts, &ends, 0).unwrap_or(b));
+    }
+
+    result
+}
+
+/// Builds a `TrianglesListAdjacency` list from a `TrianglesList` list.
+///
+/// Each triangle `(a, b, c)` becomes `(a, x, b, y, c, z)`, where `x`, `y` and `z` are the
+/// vertices opposite to the edges `ab`, `bc` and `ca` in the neighbouring triangles. Triangles
+/// with the same winding are used first. If an edge has no neighbour, the vertex of the triangle
+/// itself that is opposite to the edge is used.
+///
+/// # Panic
+///
+/// Panics if the number of indices is not a multiple of 3.
+pub fn triangles_adjacency<I>(indices: &[I]) -> Vec<I> where I: Index {
+    assert!(indices.len() % 3 == 0, "The number of indices must be a multiple of 3");
+
+    // for each directed edge, the triangles that contain it and the opposite vertex
+    let mut edges: HashMap<(usize, usize), Vec<(usize, I)>> = HashMap::new();
+
+    for (triangle, chunk) in indices.chunks(3).enumerate() {
+        for i in 0 .. 3 {
+            let (a, b, opposite) = (chunk[i], chunk[(i + 1) % 3], chunk[(i + 2) % 3]);
+            edges.entry((to_usize(a), to_usize(b))).or_insert_with(Vec::new)
+                 .push((triangle, opposite));
+        }
+    }
+
+    let neighbour = |triangle: usize, a: I, b: I| -> Option<I> {
+        let (a, b) = (to_usize(a), to_usize(b));
+
+        for edge in [(b, a), (a, b)].iter() {
+            if let Some(list) = edges.get(edge) {
+                if let Some(&(_, opposite)) = list.iter().find(|&&(t, _)| t != triangle) {
+                    return Some(opposite);
+                }
+            }
+        }
+
+        None
+    };
+
+    let mut result = Vec::with_capacity(indices.len() * 2);
+
+    for (triangle, chunk) in indices.chunks(3).enumerate() {
+        let (a, b, c) = (chunk[0], chunk[1], chunk[2]);
+
+        result.push(a);
+        result.push(neighbour(triangle, a, b).unwrap_or(c));
+        result.push(b);
+        result.push(neighbour(triangle, b, c).unwrap_or(a));
+        result.push(c);
+        result.push(neighbour(triangle, c, a).unwrap_or(b));
+    }
+
+    result
+}
+
+/// Builds a `TrianglesList` list from a list of quads, each quad being four indices in
+/// counter-clockwise order.
+///
+/// Each quad `(a, b, c, d)` becomes the triangles `(a, b, c)` and `(a, c, d)`.
+///
+/// # Panic
+///
+/// Panics if the number of indices is not a multiple of 4.
+pub fn quads_to_triangles<I>(indices: &[I]) -> Vec<I> where I: Index {
+    assert!(indices.len() % 4 == 0, "The number of indices must be a multiple of 4");
+
+    let mut result = Vec::with_capacity(indices.len() / 4 * 6);
+
+    for quad in indices.chunks(4) {
+        result.extend([quad[0], quad[1], quad[2], quad[0], quad[2], quad[3]].iter().cloned());
+    }
+
+    result
+}
+
+/// Builds a `TrianglesList` list for a grid of `columns * rows` quads.
+///
+/// The vertices of the grid are expected to be stored row by row, with `columns + 1` vertices
+/// per row and `rows + 1` rows. The triangles are counter-clockwise if the rows go up and the
+/// columns go right.
+///
+/// # Panic
+///
+/// Panics if an index is too large for `I`.
+pub fn grid_triangles<I>(columns: usize, rows: usize) -> Vec<I> where I: Index {
+    let mut result = Vec::with_capacity(columns * rows * 6);
+
+    for row in 0 .. rows {
+        for column in 0 .. columns {
+            let bottom_left = row * (columns + 1) + column;
+            let top_left = bottom_left + columns + 1;
+
+            let quad = [bottom_left, bottom_left + 1, top_left + 1, top_left];
+            for &corner in [0, 1, 2, 0, 2, 3].iter() {
+                result.push(from_usize(quad[corner]));
+            }
+        }
+    }
+
+    result
+}
+
+/// Returns each vertex once, in the order in which they first appear.
+fn unique_vertices<I>(indices: &[I]) -> Vec<I> where I: Index {
+    let mut seen = HashMap::new();
+    let mut result = Vec::new();
+
+    for &index in indices {
+        if seen.insert(to_usize(index), ()).is_none() {
+            result.push(index);
+        }
+    }
+
+    result
+}
+
+/// Converts line types to `LinesList`. Returns `None` if `from` is not a line type.
+fn lines_list<I>(indices: &[I], from: PrimitiveType) -> Option<Vec<I>> where I: Index {
+    let mut result = Vec::new();
+
+    match from {
+        PrimitiveType::LinesList => {
+            assert!(indices.len() % 2 == 0, "The number of indices must be a multiple of 2");
+            result.extend(indices.iter().cloned());
+        },
+
+        PrimitiveType::LinesListAdjacency => {
+            assert!(indices.len() % 4 == 0, "The number of indices must be a multiple of 4");
+            for line in indices.chunks(4) {
+                result.push(line[1]);
+                result.push(line[2]);
+            }
+        },
+
+        PrimitiveType::LineStrip | PrimitiveType::LineLoop => {
+            for line in indices.windows(2) {
+                result.push(line[0]);
+                result.push(line[1]);
+            }
+
+            if from == PrimitiveType::LineLoop && indices.len() >= 3 {
+                result.push(indices[indices.len() - 1]);
+                result.push(indices[0]);
+            }
+        },
+
+        PrimitiveType::LineStripAdjacency => {
+            if indices.len() >= 4 {
+                for line in indices[1 .. indices.len() - 1].windows(2) {
+                    result.push(line[0]);
+                    result.push(line[1]);
+                }
+            }
+        },
+
+        _ => return None
+    }
+
+    Some(result)
+}
+
+/// Converts triangle types to `TrianglesList`. Returns `None` if `from` is not a triangle type.
+fn triangles_list<I>(indices: &[I], from: PrimitiveType) -> Option<Vec<I>> where I: Index {
+    let mut result = Vec::new();
+
+    {
+        let mut push = |a: I, b: I, c: I| {
+            let (ua, ub, uc) = (to_usize(a), to_usize(b), to_usize(c));
+            if ua != ub && ub != uc && uc != ua {
+                result.push(a);
+                result.push(b);
+                result.push(c);
+            }
+        };
+
+        match from {
+            PrimitiveType::TrianglesList => {
+                assert!(indices.len() % 3 == 0, "The number of indices must be a multiple of 3");
+                return Some(indices.to_vec());
+            },
+
+            PrimitiveType::TrianglesListAdjacency => {
+                assert!(indices.len() % 6 == 0, "The number of indices must be a multiple of 6");
+                for triangle in indices.chunks(6) {
+                    push(triangle[0], triangle[2], triangle[4]);
+                }
+            },
+
+            PrimitiveType::TriangleStrip => {
+                for (i, triangle) in indices.windows(3).enumerate() {
+                    if i % 2 == 0 {
+                        push(triangle[0], triangle[1], triangle[2]);
+                    } else {
+                        push(triangle[1], triangle[0], triangle[2]);
+                    }
+                }
+            },
+
+            PrimitiveType::TriangleStripAdjacency => {
+                let triangles = if indices.len() >= 6 { (indices.len() - 4) / 2 } else { 0 };
+
+                for i in 0 .. triangles {
+                    if i % 2 == 0 {
+                        push(indices[2 * i], indices[2 * i + 2], indices[2 * i + 4]);
+                    } else {
+                        push(indices[2 * i + 2], indices[2 * i], indices[2 * i + 4]);
+                    }
+                }
+            },
+
+            PrimitiveType::TriangleFan => {
+                if indices.len() >= 3 {
+                    for triangle in indices[1 ..].windows(2) {
+                        push(indices[0], triangle[0], triangle[1]);
+                    }
+                }
+            },
+
+            _ => return None
+        }
+    }
+
+    Some(result)
+}
+
+/// Returns the edges of a list of triangles as a list of lines. Each edge is returned once.
+fn triangles_edges<I>(indices: &[I]) -> Vec<I> where I: Index {
+    let mut seen = HashMap::new();
+    let mut result = Vec::new();
+
+    for triangle in indices.chunks(3) {
+        for i in 0 .. 3 {
+            let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+            let (ua, ub) = (to_usize(a), to_usize(b));
+            let key = if ua < ub { (ua, ub) } else { (ub, ua) };
+
+            if seen.insert(key, ()).is_none() {
+                result.push(a);
+                result.push(b);
+            }
+        }
+    }
+
+    result
+}
@@ -42,6 +42,9 @@ use buffer::BufferViewAnySlice;
 pub use self::buffer::{IndexBuffer, IndexBufferSlice, IndexBufferAny};
 pub use self::multidraw::{DrawCommandsNoIndicesBuffer, DrawCommandNoIndices};
 
+pub mod convert;
+pub mod optimize;
+
 mod buffer;
 mod multidraw;
 
@@ -211,3 +214,38 @@ unsafe impl Index for u32 {
         IndexType::U32
     }
 }
+
+/// Turns an index into a `usize`.
+fn to_usize<I>(index: I) -> usize where I: Index {
+    // `Index` is an unsafe trait, so `get_type` is guaranteed to match the representation
+    unsafe {
+        match <I as Index>::get_type() {
+            IndexType::U8 => *(&index as *const I as *const u8) as usize,
+            IndexType::U16 => *(&index as *const I as *const u16) as usize,
+            IndexType::U32 => *(&index as *const I as *const u32) as usize,
+        }
+    }
+}
+
+/// Turns a `usize` into an index. Panics if the value is too large for the index type.
+fn from_usize<I>(value: usize) -> I where I: Index {
+    unsafe {
+        match <I as Index>::get_type() {
+            IndexType::U8 => {
+                assert!(value <= 0xff, "Index too large for `u8`");
+                let value = value as u8;
+                *(&value as *const u8 as *const I)
+            },
+            IndexType::U16 => {
+                assert!(value <= 0xffff, "Index too large for `u16`");
+                let value = value as u16;
+                *(&value as *const u16 as *const I)
+            },
+            IndexType::U32 => {
+                assert!(value <= 0xffffffff, "Index too large for `u32`");
+                let value = value as u32;
+                *(&value as *const u32 as *const I)
+            },
+        }
+    }
+}
@@ -6,7 +6,7 @@ use std::ops::Deref;
 use std::ops::DerefMut;
 
 use backend::Facade;
-use buffer::{BufferCreationError, BufferType, BufferView};
+use buffer::{BufferCreationError, BufferMode, BufferType, BufferView};
 use index::{IndicesSource, PrimitiveType};
 
 #[repr(C)]
@@ -48,7 +48,7 @@ impl DrawCommandsNoIndicesBuffer {
                                  where F: Facade
     {
         match BufferView::empty(facade, BufferType::DrawIndirectBuffer,
-                                elements, false)
+                                elements, BufferMode::Immutable)
         {
             Ok(buf) => Some(DrawCommandsNoIndicesBuffer { buffer: buf }),
             Err(BufferCreationError::BufferTypeNotSupported) => None,
@@ -64,7 +64,7 @@ impl DrawCommandsNoIndicesBuffer {
                                          where F: Facade
     {
         match BufferView::empty(facade, BufferType::DrawIndirectBuffer,
-                                elements, true)
+                                elements, BufferMode::Dynamic)
         {
             Ok(buf) => Some(DrawCommandsNoIndicesBuffer { buffer: buf }),
             Err(BufferCreationError::BufferTypeNotSupported) => None,
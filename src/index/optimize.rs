@@ -0,0 +1,455 @@
+/*!
+Functions that reorder vertices and indices in order to render them faster.
+
+All the functions operate on lists of triangles (`PrimitiveType::TrianglesList`), and return new
+data that can be passed to `VertexBuffer::new` and `IndexBuffer::new`.
+
+The recommended order is:
+
+ - `deduplicate_vertices`, to merge identical vertices.
+ - `optimize_vertex_cache`, to reuse the vertices that the GPU has already processed.
+ - `optimize_overdraw`, to draw the triangles that hide the others first.
+ - `optimize_vertex_fetch`, to read the vertex buffer sequentially.
+
+Use `analyze_vertex_cache` before and after the optimizations in order to measure the gains.
+
+```
+use glium::index::optimize;
+
+let vertices = [[0.0f32, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
+let indices = [0u16, 1, 2, 2, 1, 3];
+
+let indices = optimize::optimize_vertex_cache(&indices, vertices.len());
+let (vertices, indices, _) = optimize::optimize_vertex_fetch(&vertices, &indices);
+
+let stats = optimize::analyze_vertex_cache(&indices, 16);
+assert!(stats.acmr <= 2.0);
+```
+
+*/
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::mem;
+use std::slice;
+
+use index::{from_usize, to_usize, Index};
+use vertex::Vertex;
+
+/// Statistics about the efficiency of the post-transform vertex cache.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct VertexCacheStatistics {
+    /// Number of vertices that have to be processed by the GPU.
+    pub vertices_transformed: usize,
+
+    /// Average cache miss ratio: the average number of vertices processed per triangle.
+    ///
+    /// This is between `0.5` (in theory) and `3.0`. Lower is better.
+    pub acmr: f32,
+
+    /// Average transformed vertex ratio: the average number of times each vertex is processed.
+    ///
+    /// The best value is `1.0`. Lower is better.
+    pub atvr: f32,
+}
+
+/// Simulates a FIFO post-transform vertex cache of `cache_size` entries and returns statistics
+/// about the list of triangles.
+///
+/// Typical GPUs have a cache of 16 to 32 entries.
+pub fn analyze_vertex_cache<I>(indices: &[I], cache_size: usize) -> VertexCacheStatistics
+                               where I: Index
+{
+    assert!(indices.len() % 3 == 0, "The number of indices must be a multiple of 3");
+    assert!(cache_size >= 1);
+
+    let mut cache = FifoCache::new(cache_size);
+    let mut unique_vertices = HashMap::new();
+
+    for &index in indices.iter() {
+        let index = to_usize(index);
+        cache.access(index);
+        unique_vertices.insert(index, ());
+    }
+
+    let triangles = indices.len() / 3;
+
+    VertexCacheStatistics {
+        vertices_transformed: cache.misses,
+        acmr: if triangles == 0 { 0.0 } else { cache.misses as f32 / triangles as f32 },
+        atvr: if unique_vertices.is_empty() { 0.0 }
+              else { cache.misses as f32 / unique_vertices.len() as f32 },
+    }
+}
+
+/// Merges the vertices whose attributes are bitwise identical and updates the indices.
+///
+/// Only the attributes returned by `Vertex::build_bindings` are compared, so the padding between
+/// the fields of the vertex is ignored.
+///
+/// The vertices are kept in the order of their first occurrence in `vertices`. Vertices that are
+/// not referenced by `indices` are kept as well.
+///
+/// # Panic
+///
+/// Panics if the bindings of `T` describe attributes outside of `T`.
+pub fn deduplicate_vertices<T, I>(vertices: &[T], indices: &[I]) -> (Vec<T>, Vec<I>)
+                                  where T: Vertex, I: Index
+{
+    let bindings = <T as Vertex>::build_bindings();
+    let attributes = bindings.iter().map(|&(_, offset, ty, _)| {
+        let size = ty.get_size_bytes();
+        assert!(offset + size <= mem::size_of::<T>(),
+                "The bindings of the vertex describe an attribute outside of the vertex");
+        (offset, size)
+    }).collect::<Vec<_>>();
+
+    let mut known = HashMap::new();
+    let mut new_vertices = Vec::with_capacity(vertices.len());
+    let mut remap = Vec::with_capacity(vertices.len());
+
+    for vertex in vertices.iter() {
+        // the attributes are made of fields of the vertex, so they don't contain any padding
+        let key = attributes.iter().flat_map(|&(offset, size)| {
+            unsafe {
+                slice::from_raw_parts((vertex as *const T as *const u8).offset(offset as isize),
+                                      size)
+            }.iter().cloned()
+        }).collect::<Vec<u8>>();
+
+        let new_index = *known.entry(key).or_insert_with(|| {
+            new_vertices.push(*vertex);
+            new_vertices.len() - 1
+        });
+
+        remap.push(new_index);
+    }
+
+    let indices = indices.iter().map(|&i| from_usize(remap[to_usize(i)])).collect();
+    (new_vertices, indices)
+}
+
+/// Reorders the vertices in the order in which they are first used by the indices, and updates
+/// the indices.
+///
+/// This improves the locality of the accesses to the vertex buffer. Vertices that are not
+/// referenced by `indices` are removed.
+///
+/// Also returns, for each vertex of `vertices`, its new index or `None` if it has been removed.
+/// This can be used to reorder other buffers of per-vertex data in the same way.
+pub fn optimize_vertex_fetch<T, I>(vertices: &[T], indices: &[I])
+                                   -> (Vec<T>, Vec<I>, Vec<Option<usize>>)
+                                   where T: Copy, I: Index
+{
+    let mut remap = vec![None; vertices.len()];
+    let mut new_vertices = Vec::with_capacity(vertices.len());
+
+    let indices = indices.iter().map(|&index| {
+        let index = to_usize(index);
+
+        let new_index = match remap[index] {
+            Some(i) => i,
+            None => {
+                new_vertices.push(vertices[index]);
+                remap[index] = Some(new_vertices.len() - 1);
+                new_vertices.len() - 1
+            },
+        };
+
+        from_usize(new_index)
+    }).collect();
+
+    (new_vertices, indices, remap)
+}
+
+// constants of the vertex cache optimization
+const CACHE_SIZE: usize = 32;
+const CACHE_DECAY_POWER: f32 = 1.5;
+const LAST_TRIANGLE_SCORE: f32 = 0.75;
+const VALENCE_BOOST_SCALE: f32 = 2.0;
+const VALENCE_BOOST_POWER: f32 = 0.5;
+
+/// Reorders the triangles in order to reuse the vertices that are in the post-transform vertex
+/// cache of the GPU as much as possible.
+///
+/// This uses Tom Forsyth's linear-speed vertex cache optimization algorithm, which doesn't
+/// depend on the exact size of the cache. `vertices_count` is the number of vertices in the
+/// vertex buffer.
+///
+/// # Panic
+///
+/// Panics if the number of indices is not a multiple of 3, or if an index is superior or equal
+/// to `vertices_count`.
+pub fn optimize_vertex_cache<I>(indices: &[I], vertices_count: usize) -> Vec<I> where I: Index {
+    assert!(indices.len() % 3 == 0, "The number of indices must be a multiple of 3");
+
+    let triangles_count = indices.len() / 3;
+    let indices_usize = indices.iter().map(|&i| to_usize(i)).collect::<Vec<_>>();
+
+    if let Some(&index) = indices_usize.iter().find(|&&i| i >= vertices_count) {
+        panic!("The index {} is out of range for {} vertices", index, vertices_count);
+    }
+
+    // list of the triangles that use each vertex and that have not been added yet
+    let mut vertex_triangles: Vec<Vec<usize>> = vec![Vec::new(); vertices_count];
+    for (num, &index) in indices_usize.iter().enumerate() {
+        vertex_triangles[index].push(num / 3);
+    }
+
+    let mut vertex_score = (0 .. vertices_count).map(|v| {
+        forsyth_vertex_score(None, vertex_triangles[v].len())
+    }).collect::<Vec<_>>();
+
+    let mut triangle_added = vec![false; triangles_count];
+    let mut triangle_score = (0 .. triangles_count).map(|t| {
+        indices_usize[t * 3 .. t * 3 + 3].iter().map(|&v| vertex_score[v]).fold(0.0, |a, b| a + b)
+    }).collect::<Vec<_>>();
+
+    let mut cache: Vec<usize> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut result = Vec::with_capacity(indices.len());
+
+    // the triangles are searched linearly when the cache doesn't give any candidate
+    let mut next_linear_search = 0;
+    let mut best_triangle = None;
+
+    for _ in 0 .. triangles_count {
+        let triangle = match best_triangle {
+            Some(t) => t,
+            None => {
+                let mut best: Option<usize> = None;
+                for t in next_linear_search .. triangles_count {
+                    if triangle_added[t] {
+                        continue;
+                    }
+
+                    if best.map(|b| triangle_score[t] > triangle_score[b]).unwrap_or(true) {
+                        best = Some(t);
+                    }
+                }
+
+                best.unwrap()
+            },
+        };
+
+        // adding the triangle
+        triangle_added[triangle] = true;
+        let triangle_vertices = &indices_usize[triangle * 3 .. triangle * 3 + 3];
+        for &v in triangle_vertices.iter() {
+            result.push(v);
+            vertex_triangles[v].retain(|&t| t != triangle);
+        }
+
+        while next_linear_search < triangles_count && triangle_added[next_linear_search] {
+            next_linear_search += 1;
+        }
+
+        // moving the vertices of the triangle at the front of the cache
+        let mut new_cache = Vec::with_capacity(CACHE_SIZE + 3);
+        for &v in triangle_vertices.iter() {
+            if !new_cache.contains(&v) {
+                new_cache.push(v);
+            }
+        }
+        for &v in cache.iter() {
+            if !triangle_vertices.contains(&v) {
+                new_cache.push(v);
+            }
+        }
+
+        // updating the scores of the vertices that are or were in the cache
+        for (position, &v) in new_cache.iter().enumerate() {
+            let position = if position < CACHE_SIZE { Some(position) } else { None };
+            vertex_score[v] = forsyth_vertex_score(position, vertex_triangles[v].len());
+        }
+
+        // updating the scores of the triangles and finding the best one ; the triangles of the
+        // vertices that have just been evicted from the cache are updated as well, otherwise
+        // their score would be stale when the cache doesn't give any candidate
+        best_triangle = None;
+        let mut best_score = -1.0;
+
+        for &v in new_cache.iter() {
+            for &t in vertex_triangles[v].iter() {
+                let score = indices_usize[t * 3 .. t * 3 + 3].iter().map(|&v| vertex_score[v])
+                                                           .fold(0.0, |a, b| a + b);
+                triangle_score[t] = score;
+
+                if score > best_score {
+                    best_score = score;
+                    best_triangle = Some(t);
+                }
+            }
+        }
+
+        new_cache.truncate(CACHE_SIZE);
+        cache = new_cache;
+    }
+
+    result.into_iter().map(from_usize).collect()
+}
+
+/// Score of a vertex in Tom Forsyth's algorithm.
+fn forsyth_vertex_score(cache_position: Option<usize>, remaining_triangles: usize) -> f32 {
+    if remaining_triangles == 0 {
+        return -1.0;
+    }
+
+    let cache_score = match cache_position {
+        None => 0.0,
+        // the vertices of the last triangle are given a fixed score, so that the algorithm
+        // doesn't favor one of its edges
+        Some(p) if p < 3 => LAST_TRIANGLE_SCORE,
+        Some(p) => {
+            let scaler = 1.0 / (CACHE_SIZE - 3) as f32;
+            (1.0 - (p - 3) as f32 * scaler).powf(CACHE_DECAY_POWER)
+        },
+    };
+
+    // boosting the vertices that have few remaining triangles, in order to get rid of them
+    let valence_boost = VALENCE_BOOST_SCALE * (remaining_triangles as f32).powf(-VALENCE_BOOST_POWER);
+
+    cache_score + valence_boost
+}
+
+/// Reorders clusters of triangles so that the triangles that are likely to hide other triangles
+/// are drawn first. `position` must return the position of a vertex.
+///
+/// The indices should have been optimized with `optimize_vertex_cache` first. In order not to
+/// undo this optimization, the triangles are split in clusters that are reordered as a whole.
+/// `threshold` indicates how much the vertex cache efficiency is allowed to degrade: `1.05`
+/// allows the ACMR to be 5% worse.
+pub fn optimize_overdraw<T, I, F>(indices: &[I], vertices: &[T], position: F, threshold: f32)
+                                  -> Vec<I> where I: Index, F: Fn(&T) -> [f32; 3]
+{
+    assert!(indices.len() % 3 == 0, "The number of indices must be a multiple of 3");
+
+    let indices_usize = indices.iter().map(|&i| to_usize(i)).collect::<Vec<_>>();
+    let triangles_count = indices.len() / 3;
+
+    if triangles_count == 0 {
+        return Vec::new();
+    }
+
+    // the number of cache misses of each triangle
+    const OVERDRAW_CACHE_SIZE: usize = 16;
+    let mut cache = FifoCache::new(OVERDRAW_CACHE_SIZE);
+    let misses = (0 .. triangles_count).map(|t| {
+        let before = cache.misses;
+        for &v in indices_usize[t * 3 .. t * 3 + 3].iter() { cache.access(v); }
+        cache.misses - before
+    }).collect::<Vec<_>>();
+
+    // hard boundaries are the triangles whose vertices are all new to the cache; cutting
+    // there doesn't degrade the efficiency of the cache
+    let mut hard_boundaries = (0 .. triangles_count).filter(|&t| t == 0 || misses[t] == 3)
+                                                     .collect::<Vec<_>>();
+    hard_boundaries.push(triangles_count);
+
+    // splitting each cluster further when its ACMR is low enough
+    let mut clusters = Vec::new();
+    for bounds in hard_boundaries.windows(2) {
+        let (start, end) = (bounds[0], bounds[1]);
+
+        let cluster_misses = misses[start .. end].iter().fold(0, |a, &b| a + b);
+        let cluster_acmr = cluster_misses as f32 / (end - start) as f32;
+
+        let mut cache = FifoCache::new(OVERDRAW_CACHE_SIZE);
+        let mut cluster_start = start;
+
+        for t in start .. end {
+            for &v in indices_usize[t * 3 .. t * 3 + 3].iter() { cache.access(v); }
+
+            let acmr = cache.misses as f32 / (t + 1 - cluster_start) as f32;
+            if t + 1 < end && acmr <= cluster_acmr * threshold {
+                clusters.push((cluster_start, t + 1));
+                cluster_start = t + 1;
+                cache = FifoCache::new(OVERDRAW_CACHE_SIZE);
+            }
+        }
+
+        clusters.push((cluster_start, end));
+    }
+
+    // computing the centroid of the mesh
+    let mut mesh_centroid = [0.0f32; 3];
+    for &v in indices_usize.iter() {
+        let p = position(&vertices[v]);
+        for c in 0 .. 3 { mesh_centroid[c] += p[c] / indices_usize.len() as f32; }
+    }
+
+    // sorting the clusters so that the ones that face outwards are drawn first
+    let mut sort_keys = clusters.iter().map(|&(start, end)| {
+        let mut centroid = [0.0f32; 3];
+        let mut normal = [0.0f32; 3];
+        let mut total_area = 0.0f32;
+
+        for t in start .. end {
+            let p0 = position(&vertices[indices_usize[t * 3]]);
+            let p1 = position(&vertices[indices_usize[t * 3 + 1]]);
+            let p2 = position(&vertices[indices_usize[t * 3 + 2]]);
+
+            let e1 = [p1[0] - p0[0], p1[1] - p0[1], p1[2] - p0[2]];
+            let e2 = [p2[0] - p0[0], p2[1] - p0[1], p2[2] - p0[2]];
+            let n = [e1[1] * e2[2] - e1[2] * e2[1],
+                     e1[2] * e2[0] - e1[0] * e2[2],
+                     e1[0] * e2[1] - e1[1] * e2[0]];
+            let area = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+
+            for c in 0 .. 3 {
+                centroid[c] += (p0[c] + p1[c] + p2[c]) / 3.0 * area;
+                normal[c] += n[c];
+            }
+
+            total_area += area;
+        }
+
+        if total_area > 0.0 {
+            for c in 0 .. 3 { centroid[c] /= total_area; }
+        }
+
+        let length = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+        if length > 0.0 {
+            for c in 0 .. 3 { normal[c] /= length; }
+        }
+
+        (0 .. 3).map(|c| (centroid[c] - mesh_centroid[c]) * normal[c]).fold(0.0, |a, b| a + b)
+    }).zip(clusters.iter().cloned()).collect::<Vec<_>>();
+
+    sort_keys.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+
+    let mut result = Vec::with_capacity(indices.len());
+    for &(_, (start, end)) in sort_keys.iter() {
+        result.extend(indices[start * 3 .. end * 3].iter().cloned());
+    }
+
+    result
+}
+
+/// A FIFO post-transform vertex cache.
+struct FifoCache {
+    entries: Vec<usize>,
+    size: usize,
+    misses: usize,
+}
+
+impl FifoCache {
+    fn new(size: usize) -> FifoCache {
+        FifoCache {
+            entries: Vec::with_capacity(size),
+            size: size,
+            misses: 0,
+        }
+    }
+
+    fn access(&mut self, vertex: usize) {
+        if self.entries.contains(&vertex) {
+            return;
+        }
+
+        if self.entries.len() == self.size {
+            self.entries.remove(0);
+        }
+
+        self.entries.push(vertex);
+        self.misses += 1;
+    }
+}
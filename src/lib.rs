@@ -124,11 +124,14 @@ use context::Context;
 use context::CommandContext;
 
 pub mod backend;
+pub mod batch;
 pub mod buffer;
 pub mod debug;
 pub mod draw_parameters;
 pub mod framebuffer;
 pub mod index;
+#[cfg(feature = "mesh")]
+pub mod mesh;
 pub mod pixel_buffer;
 pub mod program;
 pub mod uniforms;
@@ -216,6 +219,12 @@ trait BufferViewExt {
     /// Makes sure that nothing is binded to `GL_PIXEL_UNPACK_BUFFER`.
     fn unbind_pixel_unpack(&mut CommandContext);
 
+    /// Makes sure that the buffer is binded to the `GL_QUERY_BUFFER`.
+    fn prepare_and_bind_for_query(&self, &mut CommandContext);
+
+    /// Makes sure that nothing is binded to `GL_QUERY_BUFFER`.
+    fn unbind_query(&mut CommandContext);
+
     /// Makes sure that the buffer is binded to the `GL_DRAW_INDIRECT_BUFFER` and calls
     /// `glMemoryBarrier(GL_COMMAND_BARRIER_BIT)` if necessary.
     fn prepare_and_bind_for_draw_indirect(&self, &mut CommandContext);
@@ -228,6 +237,10 @@ trait BufferViewExt {
     /// `glMemoryBarrier(GL_SHADER_STORAGE_BARRIER_BIT)` if necessary.
     fn prepare_and_bind_for_shared_storage(&self, &mut CommandContext, index: gl::types::GLuint);
 
+    /// Makes sure that the buffer is binded to the indexed `GL_ATOMIC_COUNTER_BUFFER` point and
+    /// calls `glMemoryBarrier(GL_ATOMIC_COUNTER_BARRIER_BIT)` if necessary.
+    fn prepare_and_bind_for_atomic_counter(&self, &mut CommandContext, index: gl::types::GLuint);
+
     /// Binds the buffer to `GL_TRANSFORM_FEEDBACk_BUFFER` regardless of the current transform
     /// feedback object.
     fn bind_to_transform_feedback(&self, &mut CommandContext, index: gl::types::GLuint);
@@ -265,6 +278,10 @@ trait ProgramExt {
     fn set_uniform(&self, ctxt: &mut context::CommandContext, uniform_location: gl::types::GLint,
                    value: &RawUniformValue);
 
+    /// Changes the values of an array uniform of the program.
+    fn set_uniform_array(&self, ctxt: &mut context::CommandContext,
+                         uniform_location: gl::types::GLint, value: &RawUniformArray);
+
     /// Changes the uniform block binding of the program.
     fn set_uniform_block_binding(&self, ctxt: &mut context::CommandContext,
                                  block_location: gl::types::GLuint, value: gl::types::GLuint);
@@ -279,6 +296,8 @@ trait ProgramExt {
     fn get_uniform_blocks(&self) -> &HashMap<String, program::UniformBlock>;
 
     fn get_shader_storage_blocks(&self) -> &HashMap<String, program::UniformBlock>;
+
+    fn get_atomic_counters(&self) -> &HashMap<String, program::AtomicCounter>;
 }
 
 /// Internal trait for queries.
@@ -301,14 +320,19 @@ trait TextureExt {
 
 /// Internal trait for transform feedback sessions.
 trait TransformFeedbackSessionExt {
-    /// Updates the state of OpenGL to make the transform feedback session current.
+    /// Updates the state of OpenGL to make the transform feedback session current, or resumes
+    /// it if it is already current.
     ///
-    /// The second parameter must be the primitive type of the input vertex data.
-    fn bind(&self, &mut CommandContext, index::PrimitiveType);
+    /// The second parameter must be the program used to draw, and the third parameter the
+    /// primitive type of the input vertex data.
+    fn bind(&self, &mut CommandContext, &Program, index::PrimitiveType) -> Result<(), DrawError>;
 
     /// Ensures that transform feedback is disabled.
     fn unbind(&mut CommandContext);
 
+    /// Ensures that transform feedback is paused if it is supported, or disabled otherwise.
+    fn pause(&mut CommandContext);
+
     /// Ensures that a buffer isn't used by transform feedback.
     fn ensure_buffer_out_of_transform_feedback(&mut CommandContext, gl::types::GLuint);
 }
@@ -342,6 +366,67 @@ enum RawUniformValue {
     Vec2([gl::types::GLfloat; 2]),
     Vec3([gl::types::GLfloat; 3]),
     Vec4([gl::types::GLfloat; 4]),
+    IntVec2([gl::types::GLint; 2]),
+    IntVec3([gl::types::GLint; 3]),
+    IntVec4([gl::types::GLint; 4]),
+    UnsignedIntVec2([gl::types::GLuint; 2]),
+    UnsignedIntVec3([gl::types::GLuint; 3]),
+    UnsignedIntVec4([gl::types::GLuint; 4]),
+    /// 2x3 column-major matrix.
+    Mat2x3([[gl::types::GLfloat; 3]; 2]),
+    /// 2x4 column-major matrix.
+    Mat2x4([[gl::types::GLfloat; 4]; 2]),
+    /// 3x2 column-major matrix.
+    Mat3x2([[gl::types::GLfloat; 2]; 3]),
+    /// 3x4 column-major matrix.
+    Mat3x4([[gl::types::GLfloat; 4]; 3]),
+    /// 4x2 column-major matrix.
+    Mat4x2([[gl::types::GLfloat; 2]; 4]),
+    /// 4x3 column-major matrix.
+    Mat4x3([[gl::types::GLfloat; 3]; 4]),
+    Double(gl::types::GLdouble),
+    DoubleVec2([gl::types::GLdouble; 2]),
+    DoubleVec3([gl::types::GLdouble; 3]),
+    DoubleVec4([gl::types::GLdouble; 4]),
+    /// 2x2 column-major matrix.
+    DoubleMat2([[gl::types::GLdouble; 2]; 2]),
+    /// 3x3 column-major matrix.
+    DoubleMat3([[gl::types::GLdouble; 3]; 3]),
+    /// 4x4 column-major matrix.
+    DoubleMat4([[gl::types::GLdouble; 4]; 4]),
+    /// 2x3 column-major matrix.
+    DoubleMat2x3([[gl::types::GLdouble; 3]; 2]),
+    /// 2x4 column-major matrix.
+    DoubleMat2x4([[gl::types::GLdouble; 4]; 2]),
+    /// 3x2 column-major matrix.
+    DoubleMat3x2([[gl::types::GLdouble; 2]; 3]),
+    /// 3x4 column-major matrix.
+    DoubleMat3x4([[gl::types::GLdouble; 4]; 3]),
+    /// 4x2 column-major matrix.
+    DoubleMat4x2([[gl::types::GLdouble; 2]; 4]),
+    /// 4x3 column-major matrix.
+    DoubleMat4x3([[gl::types::GLdouble; 3]; 4]),
+}
+
+/// The elements of an array uniform. They are passed with a single `glUniform*v` call, as the
+/// elements of an array are not guaranteed to have consecutive locations.
+#[derive(Copy, Clone, Debug)]
+enum RawUniformArray<'a> {
+    SignedInt(&'a [gl::types::GLint]),
+    UnsignedInt(&'a [gl::types::GLuint]),
+    Float(&'a [gl::types::GLfloat]),
+    Mat2(&'a [[[gl::types::GLfloat; 2]; 2]]),
+    Mat3(&'a [[[gl::types::GLfloat; 3]; 3]]),
+    Mat4(&'a [[[gl::types::GLfloat; 4]; 4]]),
+    Vec2(&'a [[gl::types::GLfloat; 2]]),
+    Vec3(&'a [[gl::types::GLfloat; 3]]),
+    Vec4(&'a [[gl::types::GLfloat; 4]]),
+    IntVec2(&'a [[gl::types::GLint; 2]]),
+    IntVec3(&'a [[gl::types::GLint; 3]]),
+    IntVec4(&'a [[gl::types::GLint; 4]]),
+    UnsignedIntVec2(&'a [[gl::types::GLuint; 2]]),
+    UnsignedIntVec3(&'a [[gl::types::GLuint; 3]]),
+    UnsignedIntVec4(&'a [[gl::types::GLuint; 4]]),
 }
 
 /// Area of a surface in pixels.
@@ -554,6 +639,9 @@ pub struct BlitTarget {
 ///
 pub trait Surface: Sized {
     /// Clears some attachments of the target.
+    ///
+    /// The color is only meaningful for floating-point and normalized color attachments. See
+    /// the `framebuffer` module for how to clear integral and unsigned attachments.
     fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
              depth: Option<f32>, stencil: Option<i32>);
 
@@ -592,6 +680,17 @@ pub trait Surface: Sized {
         self.clear(None, Some(color), Some(depth), Some(stencil));
     }
 
+    /// Tells the backend that the content of some attachments of the target is no longer
+    /// needed, which saves memory bandwidth on some hardware.
+    ///
+    /// After this call, the content of these attachments is undefined. This is only a hint and
+    /// may do nothing. If `rect` is `Some`, only this area is invalidated.
+    ///
+    /// The default implementation does nothing.
+    fn invalidate(&mut self, rect: Option<&Rect>, color: bool, depth: bool, stencil: bool) {
+        let _ = (rect, color, depth, stencil);
+    }
+
     /// Returns the dimensions in pixels of the target.
     fn get_dimensions(&self) -> (u32, u32);
 
@@ -696,13 +795,33 @@ pub enum DrawError {
 
     /// The type of a vertex attribute in the vertices source doesn't match what the
     /// program requires.
-    AttributeTypeMismatch,
+    AttributeTypeMismatch {
+        /// Name of the attribute.
+        name: String,
+        /// Type of the attribute in the program.
+        expected: vertex::AttributeType,
+        /// Type of the attribute in the vertices source.
+        obtained: vertex::AttributeType,
+        /// Index of the vertices source that contains the attribute.
+        source: usize,
+    },
 
     /// One of the attributes required by the program is missing from the vertex format.
     ///
     /// Note that it is perfectly valid to have an attribute in the vertex format that is
     /// not used by the program.
-    AttributeMissing,
+    AttributeMissing {
+        /// Name of the attribute.
+        name: String,
+    },
+
+    /// The type of a vertex attribute in the vertices source is not supported by the backend.
+    AttributeTypeNotSupported {
+        /// Name of the attribute.
+        name: String,
+        /// Type of the attribute in the vertices source.
+        ty: vertex::AttributeType,
+    },
 
     /// The viewport's dimensions are not supported by the backend.
     ViewportTooLarge,
@@ -734,6 +853,53 @@ pub enum DrawError {
     UniformBlockLayoutMismatch {
         /// Name of the block you are trying to bind.
         name: String,
+        /// The first difference between the layout of the content and the layout of the block.
+        err: uniforms::LayoutMismatchError,
+    },
+
+    /// The buffer bound to an atomic counter is smaller than the buffer required by the
+    /// program.
+    AtomicCounterBufferTooSmall {
+        /// Name of the atomic counter.
+        name: String,
+        /// Size in bytes of the buffer.
+        size: usize,
+        /// Minimum size in bytes required by the program.
+        required: usize,
+    },
+
+    /// `DrawParameters::base_vertex` is not `0`, but the backend or the indices source doesn't
+    /// support it.
+    BaseVertexNotSupported,
+
+    /// `DrawParameters::base_instance` is not `0`, but the backend or the indices source
+    /// doesn't support it.
+    BaseInstanceNotSupported,
+
+    /// When drawing without indices, `DrawParameters::base_vertex` is the first vertex to draw
+    /// and must be between `0` and the number of vertices.
+    BaseVertexOutOfRange {
+        /// The requested base vertex.
+        base_vertex: i32,
+        /// Number of vertices in the vertices sources.
+        vertices_count: usize,
+    },
+
+    /// The type of the value bound to a uniform is not supported by the backend. For example,
+    /// arrays of unsigned integers require OpenGL 3.0 or OpenGL ES 3.0.
+    UniformTypeNotSupported {
+        /// Name of the uniform you are trying to bind.
+        name: String,
+    },
+
+    /// Tried to bind an array with more elements than the uniform array of the program.
+    UniformArrayTooLong {
+        /// Name of the uniform you are trying to bind.
+        name: String,
+        /// Number of elements in the array you are trying to bind.
+        len: usize,
+        /// Number of elements in the uniform array of the program.
+        max_len: usize,
     },
 
     /// The number of vertices per patch that has been requested is not supported.
@@ -749,10 +915,25 @@ pub enum DrawError {
     SamplersNotSupported,
 
     /// When you use instancing, all vertices sources must have the same size.
-    InstancesCountMismatch,
+    InstancesCountMismatch {
+        /// Index of the per-instance vertices source whose length doesn't match.
+        source: usize,
+        /// Number of elements in this source.
+        len: usize,
+        /// Number of elements that this source must contain, including the elements skipped
+        /// because of `DrawParameters::base_instance`.
+        expected: usize,
+    },
 
     /// If you don't use indices, then all vertices sources must have the same size.
-    VerticesSourcesLengthMismatch,
+    VerticesSourcesLengthMismatch {
+        /// Index of the per-vertex vertices source whose length doesn't match.
+        source: usize,
+        /// Number of elements in this source.
+        len: usize,
+        /// Number of elements in the first per-vertex vertices source.
+        expected: usize,
+    },
 
     /// You requested not to draw primitives, but this is not supported by the backend.
     TransformFeedbackNotSupported,
@@ -762,6 +943,30 @@ pub enum DrawError {
 
     /// You requested smoothing, but this is not supported by the backend.
     SmoothingNotSupported,
+
+    /// You requested primitive restart, but this is not supported by the backend.
+    PrimitiveRestartNotSupported,
+
+    /// The primitive restart index can't be represented by the type of the indices.
+    PrimitiveRestartIndexOutOfRange,
+
+    /// The program used to draw is not the one that the transform feedback session was
+    /// created with.
+    TransformFeedbackProgramMismatch,
+
+    /// The transform feedback session was started with a different type of primitives.
+    TransformFeedbackPrimitivesMismatch,
+
+    /// Drawing instances of the vertices written by a transform feedback session is not
+    /// supported by the backend.
+    TransformFeedbackInstancingNotSupported,
+
+    /// The vertices written by a transform feedback session can only be drawn with
+    /// `NoIndices`.
+    TransformFeedbackVerticesWithIndices,
+
+    /// The program writes color outputs, but the framebuffer doesn't have any color buffer.
+    NoColorBuffer,
 }
 
 impl std::fmt::Display for DrawError {
@@ -769,12 +974,19 @@ impl std::fmt::Display for DrawError {
         match self {
             &DrawError::NoDepthBuffer => write!(fmt, "A depth function has been requested but no \
                                                       depth buffer is available."),
-            &DrawError::AttributeTypeMismatch => write!(fmt, "The type of a vertex attribute in \
-                                                              the vertices source doesn't match \
-                                                              what the program requires."),
-            &DrawError::AttributeMissing => write!(fmt, "One of the attributes required by the \
-                                                         program is missing from the vertex \
-                                                         format."),
+            &DrawError::AttributeTypeMismatch { ref name, expected, obtained, source } => {
+                write!(fmt, "The type of the vertex attribute `{}` in the vertices source {} \
+                             doesn't match what the program requires. Program expected {:?}, \
+                             got {:?}.", name, source, expected, obtained)
+            },
+            &DrawError::AttributeMissing { ref name } => {
+                write!(fmt, "The attribute `{}` required by the program is missing from the \
+                             vertex format.", name)
+            },
+            &DrawError::AttributeTypeNotSupported { ref name, ty } => {
+                write!(fmt, "The type {:?} of the vertex attribute `{}` is not supported by the \
+                             backend.", ty, name)
+            },
             &DrawError::ViewportTooLarge => write!(fmt, "The viewport's dimensions are not \
                                                          supported by the backend."),
             &DrawError::InvalidDepthRange => write!(fmt, "The depth range is outside of the \
@@ -788,9 +1000,33 @@ impl std::fmt::Display for DrawError {
             &DrawError::UniformValueToBlock { ref name } => {
                 write!(fmt, "Tried to bind a single uniform value to a uniform block.")
             },
-            &DrawError::UniformBlockLayoutMismatch { ref name } => {
+            &DrawError::UniformBlockLayoutMismatch { ref name, ref err } => {
                 write!(fmt, "The layout of the content of the uniform buffer does not match \
-                             the layout of the block.")
+                             the layout of the block `{}`: {}", name, err)
+            },
+            &DrawError::AtomicCounterBufferTooSmall { ref name, size, required } => {
+                write!(fmt, "The buffer bound to the atomic counter `{}` has a size of {} bytes, \
+                             but the program requires at least {} bytes.", name, size, required)
+            },
+            &DrawError::BaseVertexNotSupported => {
+                write!(fmt, "A base vertex has been requested, but this feature is not supported \
+                             by the backend.")
+            },
+            &DrawError::BaseInstanceNotSupported => {
+                write!(fmt, "A base instance has been requested, but this feature is not \
+                             supported by the backend.")
+            },
+            &DrawError::BaseVertexOutOfRange { base_vertex, vertices_count } => {
+                write!(fmt, "The base vertex {} is out of range when drawing {} vertices \
+                             without indices.", base_vertex, vertices_count)
+            },
+            &DrawError::UniformTypeNotSupported { ref name } => {
+                write!(fmt, "The type of the value bound to the uniform `{}` is not supported by \
+                             the backend.", name)
+            },
+            &DrawError::UniformArrayTooLong { ref name, len, max_len } => {
+                write!(fmt, "Tried to bind an array of {} elements to the uniform `{}`, which \
+                             only has {} elements.", len, name, max_len)
             },
             &DrawError::UnsupportedVerticesPerPatch => write!(fmt, "The number of vertices per \
                                                                     patch that has been requested \
@@ -803,18 +1039,48 @@ impl std::fmt::Display for DrawError {
                                                                    without submitting patches."),
             &DrawError::SamplersNotSupported => write!(fmt, "Trying to use a sampler, but they are \
                                                              not supported by the backend."),
-            &DrawError::InstancesCountMismatch => write!(fmt, "When you use instancing, all \
-                                                               vertices sources must have the \
-                                                               same size"),
-            &DrawError::VerticesSourcesLengthMismatch => write!(fmt, "If you don't use indices, \
-                                                                      then all vertices sources \
-                                                                      must have the same size."),
+            &DrawError::InstancesCountMismatch { source, len, expected } => {
+                write!(fmt, "When you use instancing, all vertices sources must have the same \
+                             size. The vertices source {} has {} elements instead of {}.",
+                       source, len, expected)
+            },
+            &DrawError::VerticesSourcesLengthMismatch { source, len, expected } => {
+                write!(fmt, "If you don't use indices, then all vertices sources must have the \
+                             same size. The vertices source {} has {} elements instead of {}.",
+                       source, len, expected)
+            },
             &DrawError::TransformFeedbackNotSupported => write!(fmt, "Requested not to draw \
                                                                       primitves, but this is not \
                                                                       supported by the backend."),
             &DrawError::WrongQueryOperation => write!(fmt, "Wrong query operation."),
             &DrawError::SmoothingNotSupported => write!(fmt, "Trying to use smoothing, but this is \
                                                               not supported by the backend."),
+            &DrawError::PrimitiveRestartNotSupported => write!(fmt, "Trying to use primitive \
+                                                                     restart, but this is not \
+                                                                     supported by the backend."),
+            &DrawError::PrimitiveRestartIndexOutOfRange => write!(fmt, "The primitive restart \
+                                                                        index can't be represented \
+                                                                        by the type of the indices."),
+            &DrawError::TransformFeedbackProgramMismatch => write!(fmt, "The program is not the \
+                                                                         one of the transform \
+                                                                         feedback session."),
+            &DrawError::TransformFeedbackPrimitivesMismatch => write!(fmt, "The transform \
+                                                                           feedback session was \
+                                                                           started with a \
+                                                                           different type of \
+                                                                           primitives."),
+            &DrawError::TransformFeedbackInstancingNotSupported => write!(fmt, "Drawing instances \
+                                                                               of transform \
+                                                                               feedback vertices \
+                                                                               is not supported."),
+            &DrawError::TransformFeedbackVerticesWithIndices => write!(fmt, "The vertices written \
+                                                                            by a transform \
+                                                                            feedback session \
+                                                                            can't be drawn with \
+                                                                            indices."),
+            &DrawError::NoColorBuffer => write!(fmt, "The program writes color outputs, but the \
+                                                      framebuffer doesn't have any color \
+                                                      buffer."),
         }
     }
 }
@@ -863,6 +1129,14 @@ impl Frame {
         self.destroyed = true;
         self.context.swap_buffers()
     }
+
+    /// Tells the backend that the content of the depth and stencil buffers is no longer needed.
+    ///
+    /// Call this before `finish` once you are done drawing, so that tiled GPUs don't have to
+    /// write these buffers back to memory.
+    pub fn discard_depth_stencil(&mut self) {
+        self.invalidate(None, false, true, true);
+    }
 }
 
 impl Surface for Frame {
@@ -872,6 +1146,10 @@ impl Surface for Frame {
         ops::clear(&self.context, None, None, color, depth, stencil);
     }
 
+    fn invalidate(&mut self, rect: Option<&Rect>, color: bool, depth: bool, stencil: bool) {
+        ops::invalidate(&self.context, None, rect, color, depth, stencil);
+    }
+
     fn get_dimensions(&self) -> (u32, u32) {
         self.dimensions
     }
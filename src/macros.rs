@@ -77,6 +77,9 @@ macro_rules! uniform {
 ///
 /// The parameters must be the name of the struct and the names of its fields.
 ///
+/// A field can be followed by `normalize(true)`, in which case its integer values are
+/// normalized when they are read as floats by the shader.
+///
 /// ## Example
 ///
 /// ```
@@ -87,15 +90,16 @@ macro_rules! uniform {
 /// struct Vertex {
 ///     position: [f32; 3],
 ///     tex_coords: [f32; 2],
+///     color: [u8; 4],
 /// }
 ///
-/// implement_vertex!(Vertex, position, tex_coords);
+/// implement_vertex!(Vertex, position, tex_coords, color normalize(true));
 /// # }
 /// ```
 ///
 #[macro_export]
 macro_rules! implement_vertex {
-    ($struct_name:ident, $($field_name:ident),+) => (
+    ($struct_name:ident, $($field_name:ident $(normalize($normalize:expr))*),+) => (
         impl $crate::vertex::Vertex for $struct_name {
             fn build_bindings() -> $crate::vertex::VertexFormat {
                 use std::borrow::Cow;
@@ -121,6 +125,7 @@ macro_rules! implement_vertex {
                                 let dummy: &$struct_name = unsafe { ::std::mem::transmute(0usize) };
                                 attr_type_of_val(&dummy.$field_name)
                             },
+                            false $(|| $normalize)*,
                         )
                     ),+
                 ])
@@ -128,8 +133,88 @@ macro_rules! implement_vertex {
         }
     );
 
+    ($struct_name:ident, $($field_name:ident $(normalize($normalize:expr))*),+,) => (
+        implement_vertex!($struct_name, $($field_name $(normalize($normalize))*),+);
+    );
+}
+
+/// Implements the `glium::uniforms::UniformBlock` trait for the given structure.
+///
+/// The structure should be `#[repr(C)]`. Each field must have the same name as the
+/// corresponding member of the block, and its type must implement `UniformBlockField`.
+///
+/// When the structure is binded to a block, the offset, type and number of elements of each
+/// field are compared with the members of the block. The stride of arrays and the columns of
+/// matrices are checked against the strides reported by the implementation, which means that
+/// `shared` and `packed` blocks are checked too. Under `std140`, a `float` or `vec3` array
+/// element occupies 16 bytes, and so does a `mat3` column, so you need to pad them in your
+/// structure, for example by using `[f32; 4]` instead of `[f32; 3]`.
+///
+/// Arrays are only supported with lengths from 1 to 32, 64, 128 and 256 (see
+/// `UniformBlockField`). A field with another length doesn't compile.
+///
+/// ## Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate glium;
+/// # fn main() {
+/// // uniform MyBlock {
+/// //     mat4 matrix;
+/// //     vec3 color;
+/// //     float intensity;
+/// // };
+/// #[derive(Copy, Clone)]
+/// #[repr(C)]
+/// struct MyBlock {
+///     matrix: [[f32; 4]; 4],
+///     color: [f32; 3],
+///     intensity: f32,
+/// }
+///
+/// implement_uniform_block!(MyBlock, matrix, color, intensity);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! implement_uniform_block {
+    ($struct_name:ident, $($field_name:ident),+) => (
+        impl $crate::uniforms::UniformBlock for $struct_name {
+            fn matches(block: &$crate::program::UniformBlock)
+                       -> ::std::result::Result<(), $crate::uniforms::LayoutMismatchError>
+            {
+                $(
+                    {
+                        let offset = {
+                            let dummy: &$struct_name = unsafe { ::std::mem::transmute(0usize) };
+                            let dummy_field = &dummy.$field_name;
+                            let dummy_field: usize = unsafe { ::std::mem::transmute(dummy_field) };
+                            dummy_field
+                        };
+
+                        let layout = {
+                            fn layout_of_val<T: $crate::uniforms::UniformBlockField>(_: &T)
+                                -> $crate::uniforms::MemberLayout
+                            {
+                                <T as $crate::uniforms::UniformBlockField>::get_layout()
+                            }
+                            let dummy: &$struct_name = unsafe { ::std::mem::transmute(0usize) };
+                            layout_of_val(&dummy.$field_name)
+                        };
+
+                        try!($crate::uniforms::check_block_field(block, stringify!($field_name),
+                                                                 offset, layout));
+                    }
+                )+
+
+                $crate::uniforms::check_block_fields_present(block,
+                                                             &[$(stringify!($field_name)),+])
+            }
+        }
+    );
+
     ($struct_name:ident, $($field_name:ident),+,) => (
-        implement_vertex!($struct_name, $($field_name),+);
+        implement_uniform_block!($struct_name, $($field_name),+);
     );
 }
 
@@ -309,6 +394,33 @@ mod tests {
         implement_vertex!(Foo, pos,);
     }
 
+    #[test]
+    fn normalize_impl_vertex() {
+        use vertex::Vertex;
+
+        #[derive(Copy, Clone)]
+        struct Foo {
+            pos: [f32; 2],
+            color: [u8; 4],
+        }
+
+        implement_vertex!(Foo, pos, color normalize(true),);
+
+        let bindings = <Foo as Vertex>::build_bindings();
+        assert!(!bindings[0].3);
+        assert!(bindings[1].3);
+    }
+
+    #[test]
+    fn trailing_comma_impl_uniform_block() {
+        #[derive(Copy, Clone)]
+        struct Foo {
+            pos: [f32; 2],
+        }
+
+        implement_uniform_block!(Foo, pos,);
+    }
+
     #[test]
     fn assert_no_error_macro() {
         struct Dummy;
@@ -0,0 +1,563 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str;
+
+use vertex::Vertex;
+
+use super::json::{self, Json};
+use super::{Material, MeshBuilder, MeshError, Scene, Semantic, TextureSource};
+
+/// Loads a glTF 2.0 file, either in the JSON format (`.gltf`) or in the binary format (`.glb`).
+///
+/// Buffers can be stored in separate files, in the binary chunk of a `.glb` file, or embedded
+/// in the document as base64 data URIs. The URIs of separate files are relative to the directory
+/// of the document and can be percent-encoded. Each primitive of each mesh of the file becomes a mesh of
+/// the scene. The node hierarchy is ignored, and no transformation is applied to the vertices.
+///
+/// Only triangle lists are supported.
+pub fn load_gltf<V, P>(path: P) -> Result<Scene<V>, MeshError> where V: Vertex, P: AsRef<Path> {
+    let path = path.as_ref();
+    let directory = path.parent().unwrap_or(Path::new(""));
+
+    let error = |message: String| {
+        MeshError::ParseError { path: path.to_path_buf(), message: message }
+    };
+
+    let unsupported = |feature: String| {
+        MeshError::UnsupportedFeature { path: path.to_path_buf(), feature: feature }
+    };
+
+    let content = try!(read_file(path));
+
+    // the binary format contains the JSON document and a binary buffer
+    let (document, binary_chunk) = if content.starts_with(b"glTF") {
+        try!(parse_glb(&content).map_err(&error))
+    } else {
+        (&content[..], None)
+    };
+
+    let document = try!(str::from_utf8(document).map_err(|_| error(format!("invalid UTF-8"))));
+    let document = try!(json::parse(document).map_err(&error));
+
+    // loading the buffers
+    let mut buffers = Vec::new();
+    for (num, buffer) in array(&document, "buffers").iter().enumerate() {
+        let data = match buffer.get("uri").and_then(|u| u.as_str()) {
+            Some(uri) => match try!(decode_data_uri(uri).map_err(&error)) {
+                Some((data, _)) => data,
+                None => {
+                    if uri.contains("://") {
+                        return Err(unsupported(format!("external buffer `{}`", uri)));
+                    }
+
+                    let uri = try!(decode_uri_path(uri).map_err(&error));
+                    try!(read_file(&directory.join(uri)))
+                },
+            },
+            None if num == 0 => match binary_chunk {
+                Some(data) => data.to_vec(),
+                None => return Err(error(format!("buffer 0 has no URI and there is no \
+                                                  binary chunk"))),
+            },
+            None => return Err(error(format!("buffer {} has no URI", num))),
+        };
+
+        buffers.push(data);
+    }
+
+    let accessors = Accessors { document: &document, buffers: &buffers };
+    let accessor_error = |err: AccessorError| {
+        match err {
+            AccessorError::Invalid(msg) => error(msg),
+            AccessorError::Unsupported(msg) => unsupported(msg),
+        }
+    };
+
+    // loading the materials
+    let mut materials = Vec::new();
+    for (num, material) in array(&document, "materials").iter().enumerate() {
+        let name = material.get("name").and_then(|n| n.as_str()).map(|n| n.to_string())
+                           .unwrap_or_else(|| format!("material{}", num));
+        let mut result = Material::new(name);
+
+        let pbr = material.get("pbrMetallicRoughness");
+
+        if let Some(factor) = pbr.and_then(|p| p.get("baseColorFactor")).and_then(|f| f.as_array()) {
+            for (d, s) in result.diffuse_color.iter_mut().zip(factor.iter()) {
+                *d = try!(s.as_f64().ok_or_else(|| error(format!("invalid baseColorFactor")))) as f32;
+            }
+        }
+
+        if let Some(texture) = pbr.and_then(|p| p.get("baseColorTexture")) {
+            result.diffuse_texture = Some(try!(texture_source(&document, &buffers, directory,
+                                                              texture).map_err(&error)));
+        }
+
+        if let Some(texture) = material.get("normalTexture") {
+            result.normal_texture = Some(try!(texture_source(&document, &buffers, directory,
+                                                             texture).map_err(&error)));
+        }
+
+        materials.push(result);
+    }
+
+    // loading the meshes
+    let mut meshes = Vec::new();
+    for mesh in array(&document, "meshes").iter() {
+        let name = mesh.get("name").and_then(|n| n.as_str()).map(|n| n.to_string());
+
+        for primitive in array(mesh, "primitives").iter() {
+            let mode = primitive.get("mode").and_then(|m| m.as_usize()).unwrap_or(4);
+            if mode != 4 {
+                return Err(unsupported(format!("primitive mode {}", mode)));
+            }
+
+            let mut attributes = HashMap::new();
+            let mut vertices_count = None;
+
+            if let Some(list) = primitive.get("attributes").and_then(|a| a.as_object()) {
+                for &(ref attr_name, ref accessor) in list.iter() {
+                    let semantic = match Semantic::from_name(attr_name) {
+                        Some(s) => s,
+                        None => continue,       // joints, weights, etc.
+                    };
+
+                    let accessor = try!(accessor.as_usize()
+                                                .ok_or_else(|| error(format!("invalid accessor"))));
+                    let data = try!(accessors.read(accessor, semantic).map_err(&accessor_error));
+
+                    if semantic == Semantic::Position {
+                        vertices_count = Some(data.len());
+                    }
+
+                    attributes.insert(semantic, data);
+                }
+            }
+
+            let vertices_count = match vertices_count {
+                Some(c) => c,
+                None => return Err(error(format!("primitive without a POSITION attribute"))),
+            };
+
+            let indices = match primitive.get("indices") {
+                Some(accessor) => {
+                    let accessor = try!(accessor.as_usize()
+                                                .ok_or_else(|| error(format!("invalid accessor"))));
+                    let indices = try!(accessors.read_indices(accessor)
+                                                .map_err(&accessor_error));
+
+                    if indices.iter().any(|&i| i as usize >= vertices_count) {
+                        return Err(error(format!("index out of range")));
+                    }
+
+                    indices
+                },
+                None => (0 .. vertices_count as u32).collect(),
+            };
+
+            let mut builder = try!(MeshBuilder::<V>::new());
+            for &index in indices.iter() {
+                builder.push(|semantic| {
+                    attributes.get(&semantic).and_then(|data| data.get(index as usize).cloned())
+                });
+            }
+
+            let material = primitive.get("material").and_then(|m| m.as_usize());
+            if let Some(material) = material {
+                if material >= materials.len() {
+                    return Err(error(format!("material {} doesn't exist", material)));
+                }
+            }
+
+            meshes.push(builder.take(name.clone(), material));
+        }
+    }
+
+    Ok(Scene {
+        meshes: meshes,
+        materials: materials,
+    })
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, MeshError> {
+    let mut content = Vec::new();
+
+    match File::open(path).and_then(|mut f| f.read_to_end(&mut content)) {
+        Ok(_) => Ok(content),
+        Err(err) => Err(MeshError::IoError(PathBuf::from(path), err)),
+    }
+}
+
+/// Returns the elements of an array member, or an empty list if it doesn't exist.
+fn array<'a>(value: &'a Json, key: &str) -> &'a [Json] {
+    value.get(key).and_then(|v| v.as_array()).unwrap_or(&[])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    data[offset] as u32 | (data[offset + 1] as u32) << 8 |
+        (data[offset + 2] as u32) << 16 | (data[offset + 3] as u32) << 24
+}
+
+/// Splits a `.glb` file into its JSON chunk and its optional binary chunk.
+fn parse_glb(content: &[u8]) -> Result<(&[u8], Option<&[u8]>), String> {
+    if content.len() < 12 {
+        return Err(format!("truncated header"));
+    }
+
+    if read_u32(content, 4) != 2 {
+        return Err(format!("unsupported glTF binary version {}", read_u32(content, 4)));
+    }
+
+    let length = read_u32(content, 8) as usize;
+    if length > content.len() {
+        return Err(format!("truncated file"));
+    }
+
+    let mut json = None;
+    let mut binary = None;
+    let mut offset = 12;
+
+    while offset + 8 <= length {
+        let chunk_length = read_u32(content, offset) as usize;
+        let chunk_type = read_u32(content, offset + 4);
+        let start = offset + 8;
+
+        let end = match start.checked_add(chunk_length) {
+            Some(end) if end <= length => end,
+            _ => return Err(format!("truncated chunk")),
+        };
+
+        let chunk = &content[start .. end];
+        match chunk_type {
+            0x4e4f534a if json.is_none() => json = Some(chunk),        // "JSON"
+            0x004e4942 if binary.is_none() => binary = Some(chunk),    // "BIN\0"
+            _ => ()
+        }
+
+        offset = end;
+    }
+
+    match json {
+        Some(json) => Ok((json, binary)),
+        None => Err(format!("missing JSON chunk")),
+    }
+}
+
+/// If `uri` is a base64 data URI, decodes it and returns the data and the MIME type.
+fn decode_data_uri(uri: &str) -> Result<Option<(Vec<u8>, Option<String>)>, String> {
+    if !uri.starts_with("data:") {
+        return Ok(None);
+    }
+
+    let comma = match uri.find(',') {
+        Some(c) => c,
+        None => return Err(format!("invalid data URI")),
+    };
+
+    let header = &uri[5 .. comma];
+    if !header.ends_with(";base64") {
+        return Err(format!("data URIs must be encoded in base64"));
+    }
+
+    let mime_type = &header[.. header.len() - 7];
+    let mime_type = if mime_type.is_empty() { None } else { Some(mime_type.to_string()) };
+
+    Ok(Some((try!(decode_base64(&uri[comma + 1 ..])), mime_type)))
+}
+
+/// Decodes the percent-encoded characters of a relative URI, for example `my%20mesh.bin`.
+fn decode_uri_path(uri: &str) -> Result<String, String> {
+    let bytes = uri.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        if bytes[pos] != b'%' {
+            output.push(bytes[pos]);
+            pos += 1;
+            continue;
+        }
+
+        let value = bytes.get(pos + 1 .. pos + 3)
+                         .and_then(|hex| str::from_utf8(hex).ok())
+                         .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match value {
+            Some(value) => output.push(value),
+            None => return Err(format!("invalid percent-encoding in URI `{}`", uri)),
+        }
+
+        pos += 3;
+    }
+
+    String::from_utf8(output).map_err(|_| format!("invalid UTF-8 in URI `{}`", uri))
+}
+
+fn decode_base64(input: &str) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    let mut accumulator = 0u32;
+    let mut bits = 0;
+
+    for c in input.bytes() {
+        let value = match c {
+            b'A' ... b'Z' => c - b'A',
+            b'a' ... b'z' => c - b'a' + 26,
+            b'0' ... b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => break,
+            _ => return Err(format!("invalid base64 character")),
+        };
+
+        accumulator = (accumulator << 6) | value as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            output.push((accumulator >> bits) as u8);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+
+    Ok(output)
+}
+
+/// Returns the location of the image of a `textureInfo` object.
+fn texture_source(document: &Json, buffers: &[Vec<u8>], directory: &Path, info: &Json)
+                  -> Result<TextureSource, String>
+{
+    let texture = try!(info.get("index").and_then(|i| i.as_usize())
+                           .and_then(|i| array(document, "textures").get(i))
+                           .ok_or_else(|| format!("invalid texture")));
+
+    let image = try!(texture.get("source").and_then(|i| i.as_usize())
+                            .and_then(|i| array(document, "images").get(i))
+                            .ok_or_else(|| format!("invalid image")));
+
+    let mime_type = image.get("mimeType").and_then(|m| m.as_str()).map(|m| m.to_string());
+
+    if let Some(uri) = image.get("uri").and_then(|u| u.as_str()) {
+        return match try!(decode_data_uri(uri)) {
+            Some((data, uri_mime_type)) => Ok(TextureSource::Embedded {
+                data: data,
+                mime_type: mime_type.or(uri_mime_type),
+            }),
+            None => Ok(TextureSource::File(directory.join(try!(decode_uri_path(uri))))),
+        };
+    }
+
+    let view = try!(image.get("bufferView").and_then(|v| v.as_usize())
+                         .ok_or_else(|| format!("image without URI or buffer view")));
+    let (data, _) = try!(buffer_view(document, buffers, view));
+
+    Ok(TextureSource::Embedded { data: data.to_vec(), mime_type: mime_type })
+}
+
+/// Returns the content of a buffer view and its stride.
+fn buffer_view<'a>(document: &Json, buffers: &'a [Vec<u8>], index: usize)
+                   -> Result<(&'a [u8], Option<usize>), String>
+{
+    let view = try!(array(document, "bufferViews").get(index)
+                        .ok_or_else(|| format!("buffer view {} doesn't exist", index)));
+
+    let buffer = try!(view.get("buffer").and_then(|b| b.as_usize())
+                          .and_then(|b| buffers.get(b))
+                          .ok_or_else(|| format!("invalid buffer in buffer view {}", index)));
+
+    let offset = view.get("byteOffset").and_then(|o| o.as_usize()).unwrap_or(0);
+    let length = try!(view.get("byteLength").and_then(|l| l.as_usize())
+                          .ok_or_else(|| format!("buffer view {} has no length", index)));
+
+    let data = try!(offset.checked_add(length).and_then(|end| buffer.get(offset .. end))
+                          .ok_or_else(|| format!("buffer view {} is out of range", index)));
+
+    let stride = view.get("byteStride").and_then(|s| s.as_usize());
+    Ok((data, stride))
+}
+
+enum AccessorError {
+    Invalid(String),
+    Unsupported(String),
+}
+
+impl From<String> for AccessorError {
+    fn from(msg: String) -> AccessorError {
+        AccessorError::Invalid(msg)
+    }
+}
+
+/// Reads the content of accessors.
+struct Accessors<'a> {
+    document: &'a Json,
+    buffers: &'a [Vec<u8>],
+}
+
+impl<'a> Accessors<'a> {
+    /// Reads an accessor of floats or normalized integers, and returns one value per element.
+    fn read(&self, index: usize, semantic: Semantic) -> Result<Vec<[f32; 4]>, AccessorError> {
+        let (elements, components) = try!(self.read_raw(index));
+
+        // missing components are taken from the default value
+        let default = semantic.default_value();
+        Ok(elements.chunks(components).map(|element| {
+            let mut value = default;
+            for (d, s) in value.iter_mut().zip(element.iter()) { *d = *s as f32; }
+            value
+        }).collect())
+    }
+
+    /// Reads an accessor of indices.
+    fn read_indices(&self, index: usize) -> Result<Vec<u32>, AccessorError> {
+        let (elements, components) = try!(self.read_raw(index));
+
+        if components != 1 {
+            return Err(AccessorError::Invalid(format!("indices must be scalars")));
+        }
+
+        Ok(elements.iter().map(|&i| i as u32).collect())
+    }
+
+    /// Reads all the components of an accessor as `f64`s, and returns them and the number of
+    /// components per element.
+    fn read_raw(&self, index: usize) -> Result<(Vec<f64>, usize), AccessorError> {
+        let accessor = try!(array(self.document, "accessors").get(index)
+                                .ok_or_else(|| format!("accessor {} doesn't exist", index)));
+
+        if accessor.get("sparse").is_some() {
+            return Err(AccessorError::Unsupported(format!("sparse accessors")));
+        }
+
+        let count = try!(accessor.get("count").and_then(|c| c.as_usize())
+                                 .ok_or_else(|| format!("accessor {} has no count", index)));
+
+        let components = match accessor.get("type").and_then(|t| t.as_str()) {
+            Some("SCALAR") => 1,
+            Some("VEC2") => 2,
+            Some("VEC3") => 3,
+            Some("VEC4") => 4,
+            Some(ty) => return Err(AccessorError::Unsupported(format!("accessors of type {}",
+                                                                      ty))),
+            None => return Err(AccessorError::Invalid(format!("accessor {} has no type",
+                                                              index))),
+        };
+
+        let component_type = accessor.get("componentType").and_then(|c| c.as_usize());
+        let normalized = accessor.get("normalized").and_then(|n| n.as_bool()).unwrap_or(false);
+
+        let component_size = match component_type {
+            Some(5120) | Some(5121) => 1,
+            Some(5122) | Some(5123) => 2,
+            Some(5125) | Some(5126) => 4,
+            _ => return Err(AccessorError::Invalid(format!("accessor {} has an invalid \
+                                                            component type", index))),
+        };
+
+        let out_of_range = || AccessorError::Invalid(format!("accessor {} is out of range",
+                                                             index));
+
+        let values_count = try!(count.checked_mul(components).ok_or_else(&out_of_range));
+
+        // accessors without a buffer view are filled with zeroes, and their size is not limited
+        // by the content of the file
+        let view = match accessor.get("bufferView").and_then(|v| v.as_usize()) {
+            Some(view) => view,
+            None => {
+                match values_count.checked_mul(8) {
+                    Some(size) if size <= ::std::isize::MAX as usize => (),
+                    _ => return Err(out_of_range()),
+                }
+
+                return Ok((vec![0.0; values_count], components));
+            },
+        };
+
+        let (data, stride) = try!(buffer_view(self.document, self.buffers, view));
+        let offset = accessor.get("byteOffset").and_then(|o| o.as_usize()).unwrap_or(0);
+        let element_size = component_size * components;
+        let stride = stride.unwrap_or(element_size);
+
+        // position of the end of the last element
+        let end = if count == 0 {
+            Some(0)
+        } else {
+            (count - 1).checked_mul(stride).and_then(|s| s.checked_add(offset))
+                       .and_then(|s| s.checked_add(element_size))
+        };
+
+        match end {
+            Some(end) if end <= data.len() => (),
+            _ => return Err(out_of_range()),
+        }
+
+        let mut result = Vec::with_capacity(values_count);
+
+        for element in 0 .. count {
+            for component in 0 .. components {
+                // can't overflow or be out of range because of the check above
+                let pos = offset + element * stride + component * component_size;
+                let bytes = &data[pos .. pos + component_size];
+
+                let value = match component_type.unwrap() {
+                    5120 => {
+                        let v = bytes[0] as i8 as f64;
+                        if normalized { (v / 127.0).max(-1.0) } else { v }
+                    },
+                    5121 => {
+                        let v = bytes[0] as f64;
+                        if normalized { v / 255.0 } else { v }
+                    },
+                    5122 => {
+                        let v = (bytes[0] as u16 | (bytes[1] as u16) << 8) as i16 as f64;
+                        if normalized { (v / 32767.0).max(-1.0) } else { v }
+                    },
+                    5123 => {
+                        let v = (bytes[0] as u16 | (bytes[1] as u16) << 8) as f64;
+                        if normalized { v / 65535.0 } else { v }
+                    },
+                    5125 => read_u32(bytes, 0) as f64,
+                    _ => {
+                        let v: f32 = unsafe { ::std::mem::transmute(read_u32(bytes, 0)) };
+                        v as f64
+                    },
+                };
+
+                result.push(value);
+            }
+        }
+
+        Ok((result, components))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_base64, decode_data_uri, decode_uri_path};
+
+    #[test]
+    fn base64() {
+        assert_eq!(decode_base64("").unwrap(), b"");
+        assert_eq!(decode_base64("Zg==").unwrap(), b"f");
+        assert_eq!(decode_base64("Zm9v").unwrap(), b"foo");
+        assert_eq!(decode_base64("Zm9vYmE=").unwrap(), b"fooba");
+        assert!(decode_base64("Zm9v!").is_err());
+    }
+
+    #[test]
+    fn data_uri() {
+        assert_eq!(decode_data_uri("file.bin").unwrap(), None);
+        assert_eq!(decode_data_uri("data:application/octet-stream;base64,Zm9v").unwrap(),
+                   Some((b"foo".to_vec(), Some("application/octet-stream".to_string()))));
+        assert!(decode_data_uri("data:text/plain,foo").is_err());
+    }
+
+    #[test]
+    fn uri_path() {
+        assert_eq!(decode_uri_path("file.bin").unwrap(), "file.bin");
+        assert_eq!(decode_uri_path("my%20mesh.bin").unwrap(), "my mesh.bin");
+        assert_eq!(decode_uri_path("%C3%A9.bin").unwrap(), "\u{e9}.bin");
+        assert!(decode_uri_path("file%2.bin").is_err());
+        assert!(decode_uri_path("file%").is_err());
+    }
+}
@@ -0,0 +1,340 @@
+//! Minimal JSON parser used to read glTF documents.
+
+use std::char;
+
+/// A JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// The members of the object, in the order in which they appear in the document.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// If this value is an object, returns the value of the member with the given name.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            &Json::Object(ref members) => members.iter().find(|m| m.0 == key).map(|m| &m.1),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            &Json::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a non-negative integer.
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            &Json::Number(n) if n >= 0.0 && n.fract() == 0.0 => Some(n as usize),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            &Json::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            &Json::String(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            &Json::Array(ref a) => Some(a),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Json)]> {
+        match self {
+            &Json::Object(ref o) => Some(o),
+            _ => None,
+        }
+    }
+}
+
+/// Maximum number of nested arrays and objects. Deeper documents are rejected instead of
+/// overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Parses a JSON document.
+pub fn parse(source: &str) -> Result<Json, String> {
+    let mut parser = Parser { data: source.as_bytes(), pos: 0, depth: 0 };
+
+    let value = try!(parser.parse_value());
+    parser.skip_whitespace();
+
+    if parser.pos != parser.data.len() {
+        return Err(format!("unexpected data after the JSON value at offset {}", parser.pos));
+    }
+
+    Ok(value)
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Number of arrays and objects that contain the current position.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).cloned()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                b' ' | b'\t' | b'\n' | b'\r' => self.pos += 1,
+                _ => break,
+            }
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), String> {
+        self.skip_whitespace();
+
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("expected `{}` at offset {}", c as char, self.pos))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str, value: Json) -> Result<Json, String> {
+        if self.data[self.pos..].starts_with(keyword.as_bytes()) {
+            self.pos += keyword.len();
+            Ok(value)
+        } else {
+            Err(format!("unexpected character at offset {}", self.pos))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(b'{') | Some(b'[') => {
+                if self.depth >= MAX_DEPTH {
+                    return Err(format!("nesting too deep at offset {}", self.pos));
+                }
+
+                self.depth += 1;
+                let value = if self.peek() == Some(b'{') {
+                    self.parse_object()
+                } else {
+                    self.parse_array()
+                };
+                self.depth -= 1;
+                value
+            },
+            Some(b'"') => self.parse_string().map(Json::String),
+            Some(b't') => self.expect_keyword("true", Json::Bool(true)),
+            Some(b'f') => self.expect_keyword("false", Json::Bool(false)),
+            Some(b'n') => self.expect_keyword("null", Json::Null),
+            Some(b'-') | Some(b'0' ... b'9') => self.parse_number(),
+            Some(_) => Err(format!("unexpected character at offset {}", self.pos)),
+            None => Err(format!("unexpected end of document")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, String> {
+        try!(self.expect(b'{'));
+        let mut members = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(format!("expected a member name at offset {}", self.pos));
+            }
+
+            let key = try!(self.parse_string());
+            try!(self.expect(b':'));
+            let value = try!(self.parse_value());
+            members.push((key, value));
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => { self.pos += 1; return Ok(Json::Object(members)); },
+                _ => return Err(format!("expected `,` or `}}` at offset {}", self.pos)),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Json, String> {
+        try!(self.expect(b'['));
+        let mut elements = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Json::Array(elements));
+        }
+
+        loop {
+            elements.push(try!(self.parse_value()));
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => { self.pos += 1; return Ok(Json::Array(elements)); },
+                _ => return Err(format!("expected `,` or `]` at offset {}", self.pos)),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Json, String> {
+        let start = self.pos;
+
+        while let Some(c) = self.peek() {
+            match c {
+                b'-' | b'+' | b'.' | b'e' | b'E' | b'0' ... b'9' => self.pos += 1,
+                _ => break,
+            }
+        }
+
+        // the bytes are all ASCII, so this can't fail
+        let text = ::std::str::from_utf8(&self.data[start .. self.pos]).unwrap();
+        text.parse().map(Json::Number)
+            .map_err(|_| format!("invalid number `{}` at offset {}", text, start))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        if self.pos + 4 > self.data.len() {
+            return Err(format!("unexpected end of document"));
+        }
+
+        let text = ::std::str::from_utf8(&self.data[self.pos .. self.pos + 4]).ok();
+        let value = text.and_then(|t| u32::from_str_radix(t, 16).ok());
+
+        match value {
+            Some(value) => {
+                self.pos += 4;
+                Ok(value)
+            },
+            None => Err(format!("invalid unicode escape at offset {}", self.pos)),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        try!(self.expect(b'"'));
+        let mut bytes = Vec::new();
+
+        loop {
+            let c = match self.peek() {
+                Some(c) => c,
+                None => return Err(format!("unterminated string")),
+            };
+            self.pos += 1;
+
+            match c {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = match self.peek() {
+                        Some(c) => c,
+                        None => return Err(format!("unterminated string")),
+                    };
+                    self.pos += 1;
+
+                    let c = match escaped {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = try!(self.parse_hex4());
+
+                            // surrogate pair
+                            if code >= 0xd800 && code < 0xdc00 &&
+                               self.data[self.pos..].starts_with(b"\\u")
+                            {
+                                self.pos += 2;
+                                let low = try!(self.parse_hex4());
+                                code = 0x10000 + ((code - 0xd800) << 10) +
+                                       (low.wrapping_sub(0xdc00) & 0x3ff);
+                            }
+
+                            char::from_u32(code).unwrap_or('\u{fffd}')
+                        },
+                        _ => return Err(format!("invalid escape sequence at offset {}",
+                                                self.pos - 2)),
+                    };
+
+                    bytes.extend(c.to_string().bytes());
+                },
+                c => bytes.push(c),
+            }
+        }
+
+        String::from_utf8(bytes).map_err(|_| format!("invalid UTF-8 in string"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Json};
+
+    #[test]
+    fn values() {
+        assert_eq!(parse("null"), Ok(Json::Null));
+        assert_eq!(parse(" true "), Ok(Json::Bool(true)));
+        assert_eq!(parse("-1.5e2"), Ok(Json::Number(-150.0)));
+        assert_eq!(parse(r#""a\né😀""#),
+                   Ok(Json::String("a\n\u{e9}\u{1f600}".to_string())));
+    }
+
+    #[test]
+    fn nested() {
+        let value = parse(r#"{ "a": [1, 2, { "b": false }], "c": {} }"#).unwrap();
+        assert_eq!(value.get("a").and_then(|a| a.as_array()).map(|a| a.len()), Some(3));
+        assert_eq!(value.get("c"), Some(&Json::Object(vec![])));
+        assert_eq!(value.get("d"), None);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse("[1, 2").is_err());
+        assert!(parse("{ 1: 2 }").is_err());
+        assert!(parse("[] []").is_err());
+        assert!(parse("\"abc").is_err());
+    }
+
+    #[test]
+    fn nesting_too_deep() {
+        use std::iter;
+
+        let nested = |depth: usize| -> String {
+            iter::repeat('[').take(depth).chain(iter::repeat(']').take(depth)).collect()
+        };
+
+        assert!(parse(&nested(100000)).unwrap_err().starts_with("nesting too deep"));
+        assert!(parse(&nested(super::MAX_DEPTH)).is_ok());
+    }
+}
@@ -0,0 +1,379 @@
+/*!
+Loading models from Wavefront OBJ and glTF 2.0 files.
+
+The vertices of the loaded meshes are stored in your own vertex type. Each attribute of the type
+is filled with the data that corresponds to its name:
+
+ - `position`
+ - `normal`
+ - `tangent`
+ - `tex_coords`, `tex_coords_1`, `tex_coords_2`, ...
+ - `color`, `color_1`, `color_2`, ...
+
+The names are case-insensitive, and the glTF names (`POSITION`, `TEXCOORD_0`, `COLOR_0`, ...)
+are accepted as well. All the attributes must contain one to four `f32`s. If the file doesn't
+contain any data for an attribute, it is filled with `0.0`, except for the alpha component of
+positions and colors which is `1.0`, and for colors which default to white.
+
+Identical vertices are merged, and each mesh has a list of indices describing a list of
+triangles.
+
+```no_run
+# #[macro_use] extern crate glium;
+# fn main() {
+# let display: glium::Display = unsafe { std::mem::uninitialized() };
+#[derive(Copy, Clone)]
+struct Vertex {
+    position: [f32; 3],
+    normal: [f32; 3],
+    tex_coords: [f32; 2],
+}
+
+implement_vertex!(Vertex, position, normal, tex_coords);
+
+let scene = glium::mesh::load_obj::<Vertex, _>("teapot.obj").unwrap();
+
+for mesh in scene.meshes.iter() {
+    let (vertex_buffer, index_buffer) = mesh.build_buffers(&display);
+    let material = mesh.material.map(|m| &scene.materials[m]);
+    // ...
+}
+# }
+```
+
+Textures are not loaded. Instead the materials contain the location of their textures so that you
+can load them with the library of your choice.
+
+This module requires the `mesh` feature.
+
+*/
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::mem;
+use std::path::PathBuf;
+use std::slice;
+
+use backend::Facade;
+use index::{IndexBuffer, IndexBufferAny, PrimitiveType};
+use vertex::{AttributeType, Vertex, VertexBuffer};
+
+pub use self::gltf::load_gltf;
+pub use self::obj::load_obj;
+
+mod gltf;
+mod json;
+mod obj;
+
+/// The content of a model file.
+#[derive(Debug, Clone)]
+pub struct Scene<V> {
+    /// List of meshes in the file.
+    pub meshes: Vec<Mesh<V>>,
+
+    /// List of materials in the file. Meshes refer to them by their index in this list.
+    pub materials: Vec<Material>,
+}
+
+/// A list of triangles that share the same material.
+#[derive(Debug, Clone)]
+pub struct Mesh<V> {
+    /// Name of the object in the file, if any.
+    pub name: Option<String>,
+
+    /// List of unique vertices.
+    pub vertices: Vec<V>,
+
+    /// List of indices within `vertices`. Each group of three indices is a triangle.
+    pub indices: Vec<u32>,
+
+    /// Index of the material within the `materials` of the scene.
+    pub material: Option<usize>,
+}
+
+impl<V> Mesh<V> where V: Vertex + Send + 'static {
+    /// Uploads the mesh to video memory.
+    ///
+    /// The indices are stored as `u16`s if possible, and as `u32`s otherwise.
+    pub fn build_buffers<F>(&self, facade: &F) -> (VertexBuffer<V>, IndexBufferAny)
+                            where F: Facade
+    {
+        let vertex_buffer = VertexBuffer::new(facade, &self.vertices);
+
+        let index_buffer = if self.vertices.len() <= 65536 {
+            let indices = self.indices.iter().map(|&i| i as u16).collect::<Vec<_>>();
+            IndexBuffer::new(facade, PrimitiveType::TrianglesList, indices).into()
+        } else {
+            IndexBuffer::new(facade, PrimitiveType::TrianglesList, &self.indices).into()
+        };
+
+        (vertex_buffer, index_buffer)
+    }
+}
+
+/// Describes the appearance of a mesh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Material {
+    /// Name of the material.
+    pub name: String,
+
+    /// The diffuse color (`Kd` and `d` in OBJ, `baseColorFactor` in glTF).
+    pub diffuse_color: [f32; 4],
+
+    /// The specular color (`Ks` in OBJ). Always black with glTF.
+    pub specular_color: [f32; 3],
+
+    /// The specular exponent (`Ns` in OBJ). Always `0.0` with glTF.
+    pub shininess: f32,
+
+    /// The diffuse texture (`map_Kd` in OBJ, `baseColorTexture` in glTF).
+    pub diffuse_texture: Option<TextureSource>,
+
+    /// The normal map (`map_Bump` or `norm` in OBJ, `normalTexture` in glTF).
+    pub normal_texture: Option<TextureSource>,
+
+    /// The specular texture (`map_Ks` in OBJ). Always `None` with glTF.
+    pub specular_texture: Option<TextureSource>,
+}
+
+impl Material {
+    fn new(name: String) -> Material {
+        Material {
+            name: name,
+            diffuse_color: [1.0, 1.0, 1.0, 1.0],
+            specular_color: [0.0, 0.0, 0.0],
+            shininess: 0.0,
+            diffuse_texture: None,
+            normal_texture: None,
+            specular_texture: None,
+        }
+    }
+}
+
+/// Location of the content of a texture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextureSource {
+    /// The texture is in a file. The path is relative to the current directory, not to the
+    /// model file.
+    File(PathBuf),
+
+    /// The content of the texture file is embedded in the model file.
+    Embedded {
+        /// Content of the image file.
+        data: Vec<u8>,
+        /// MIME type of the image, if known.
+        mime_type: Option<String>,
+    },
+}
+
+/// Kind of data that an attribute of a vertex contains.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Semantic {
+    /// The position of the vertex.
+    Position,
+    /// The normal of the vertex.
+    Normal,
+    /// The tangent of the vertex.
+    Tangent,
+    /// A set of texture coordinates.
+    TexCoords(u32),
+    /// A vertex color.
+    Color(u32),
+}
+
+impl Semantic {
+    /// Returns the semantic corresponding to the name of an attribute, or `None` if the name is
+    /// not recognized.
+    pub fn from_name(name: &str) -> Option<Semantic> {
+        let name = name.to_lowercase();
+
+        // splitting the optional `_N` suffix
+        let (base, set) = match name.rfind('_') {
+            Some(pos) => match name[pos + 1..].parse::<u32>() {
+                Ok(set) => (&name[..pos], set),
+                Err(_) => (&name[..], 0),
+            },
+            None => (&name[..], 0),
+        };
+
+        match (base, set) {
+            ("position", 0) => Some(Semantic::Position),
+            ("normal", 0) => Some(Semantic::Normal),
+            ("tangent", 0) => Some(Semantic::Tangent),
+            ("tex_coords", set) | ("texcoord", set) | ("texcoords", set) |
+            ("tex_coord", set) => Some(Semantic::TexCoords(set)),
+            ("color", set) | ("colour", set) => Some(Semantic::Color(set)),
+            _ => None,
+        }
+    }
+
+    /// Value of the attribute when the file doesn't contain it.
+    fn default_value(&self) -> [f32; 4] {
+        match *self {
+            Semantic::Position => [0.0, 0.0, 0.0, 1.0],
+            Semantic::Color(_) => [1.0, 1.0, 1.0, 1.0],
+            _ => [0.0, 0.0, 0.0, 0.0],
+        }
+    }
+}
+
+/// Error that can happen while loading a model.
+#[derive(Debug)]
+pub enum MeshError {
+    /// Error while reading one of the files.
+    IoError(PathBuf, io::Error),
+
+    /// One of the files is malformed.
+    ParseError {
+        /// The file.
+        path: PathBuf,
+        /// Description of the problem.
+        message: String,
+    },
+
+    /// The file uses a feature that is not supported by the loader.
+    UnsupportedFeature {
+        /// The file.
+        path: PathBuf,
+        /// Description of the feature.
+        feature: String,
+    },
+
+    /// The name of an attribute of the vertex type doesn't correspond to any known semantic.
+    UnknownAttribute(String),
+
+    /// An attribute of the vertex type doesn't contain between one and four `f32`s.
+    UnsupportedAttributeType(String),
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            &MeshError::IoError(ref path, ref err) =>
+                write!(formatter, "Error while reading `{}`: {}", path.display(), err),
+            &MeshError::ParseError { ref path, ref message } =>
+                write!(formatter, "Error while parsing `{}`: {}", path.display(), message),
+            &MeshError::UnsupportedFeature { ref path, ref feature } =>
+                write!(formatter, "`{}` uses an unsupported feature: {}", path.display(), feature),
+            &MeshError::UnknownAttribute(ref name) =>
+                write!(formatter, "The vertex attribute `{}` doesn't correspond to any known \
+                                   semantic", name),
+            &MeshError::UnsupportedAttributeType(ref name) =>
+                write!(formatter, "The vertex attribute `{}` must contain between one and four \
+                                   `f32`s", name),
+        }
+    }
+}
+
+impl Error for MeshError {
+    fn description(&self) -> &str {
+        match self {
+            &MeshError::IoError(_, _) => "Error while reading one of the files",
+            &MeshError::ParseError { .. } => "One of the files is malformed",
+            &MeshError::UnsupportedFeature { .. } => "The file uses an unsupported feature",
+            &MeshError::UnknownAttribute(_) => "Unknown vertex attribute name",
+            &MeshError::UnsupportedAttributeType(_) => "Unsupported vertex attribute type",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match self {
+            &MeshError::IoError(_, ref err) => Some(err as &Error),
+            _ => None,
+        }
+    }
+}
+
+/// Builds a mesh by adding vertices one by one and merging identical vertices.
+struct MeshBuilder<V> {
+    /// For each attribute of `V`, its semantic, its offset in bytes and its number of `f32`s.
+    attributes: Vec<(Semantic, usize, usize)>,
+    vertices: Vec<V>,
+    indices: Vec<u32>,
+    /// Content of each vertex of `vertices` as bytes, to find duplicates.
+    known_vertices: HashMap<Vec<u8>, u32>,
+}
+
+impl<V> MeshBuilder<V> where V: Vertex {
+    fn new() -> Result<MeshBuilder<V>, MeshError> {
+        let bindings = <V as Vertex>::build_bindings();
+        let mut attributes = Vec::with_capacity(bindings.len());
+
+        for &(ref name, offset, ty, _) in bindings.iter() {
+            let semantic = match Semantic::from_name(name) {
+                Some(s) => s,
+                None => return Err(MeshError::UnknownAttribute(name.to_string())),
+            };
+
+            let components = match ty {
+                AttributeType::F32 => 1,
+                AttributeType::F32F32 => 2,
+                AttributeType::F32F32F32 => 3,
+                AttributeType::F32F32F32F32 => 4,
+                _ => return Err(MeshError::UnsupportedAttributeType(name.to_string())),
+            };
+
+            assert!(offset + components * 4 <= mem::size_of::<V>());
+            attributes.push((semantic, offset, components));
+        }
+
+        Ok(MeshBuilder {
+            attributes: attributes,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            known_vertices: HashMap::new(),
+        })
+    }
+
+    /// Adds a vertex at the end of the list of indices. `data` must return the value of each
+    /// semantic, or `None` if the file doesn't contain it.
+    fn push<F>(&mut self, data: F) where F: Fn(Semantic) -> Option<[f32; 4]> {
+        let mut vertex: V = unsafe { mem::zeroed() };
+
+        let key = {
+            let bytes = unsafe {
+                slice::from_raw_parts_mut(&mut vertex as *mut V as *mut u8, mem::size_of::<V>())
+            };
+
+            for &(semantic, offset, components) in self.attributes.iter() {
+                let value = data(semantic).unwrap_or_else(|| semantic.default_value());
+
+                for (num, component) in value[.. components].iter().enumerate() {
+                    let component: [u8; 4] = unsafe { mem::transmute(*component) };
+                    let dest = &mut bytes[offset + num * 4 .. offset + num * 4 + 4];
+                    for (d, s) in dest.iter_mut().zip(component.iter()) {
+                        *d = *s;
+                    }
+                }
+            }
+
+            bytes.to_vec()
+        };
+
+        let vertices = &mut self.vertices;
+        let index = *self.known_vertices.entry(key).or_insert_with(|| {
+            vertices.push(vertex);
+            (vertices.len() - 1) as u32
+        });
+
+        self.indices.push(index);
+    }
+
+    /// Returns true if no vertex has been added.
+    fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Turns the builder into a mesh and resets it.
+    fn take(&mut self, name: Option<String>, material: Option<usize>) -> Mesh<V> {
+        self.known_vertices.clear();
+
+        Mesh {
+            name: name,
+            vertices: mem::replace(&mut self.vertices, Vec::new()),
+            indices: mem::replace(&mut self.indices, Vec::new()),
+            material: material,
+        }
+    }
+}
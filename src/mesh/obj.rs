@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use vertex::Vertex;
+
+use super::{Material, MeshBuilder, MeshError, Scene, Semantic, TextureSource};
+
+/// Loads a Wavefront OBJ file, and the MTL files that it references.
+///
+/// A new mesh is started each time the object, the group or the material changes. Polygons are
+/// split into triangles, and lines and points are ignored.
+///
+/// The position, normal, texture coordinates and the optional vertex color that follows the
+/// position are loaded.
+pub fn load_obj<V, P>(path: P) -> Result<Scene<V>, MeshError> where V: Vertex, P: AsRef<Path> {
+    let path = path.as_ref();
+    let source = try!(read_file(path));
+    let directory = path.parent().unwrap_or(Path::new(""));
+
+    let mut positions: Vec<[f32; 4]> = Vec::new();
+    let mut colors: Vec<Option<[f32; 4]>> = Vec::new();
+    let mut tex_coords: Vec<[f32; 4]> = Vec::new();
+    let mut normals: Vec<[f32; 4]> = Vec::new();
+
+    let mut materials = Vec::new();
+    let mut materials_by_name = HashMap::new();
+
+    let mut meshes = Vec::new();
+    let mut builder = try!(MeshBuilder::<V>::new());
+    let mut current_name = None;
+    let mut current_material = None;
+
+    for (line_num, line) in source.lines().enumerate() {
+        let error = |message: String| {
+            MeshError::ParseError {
+                path: path.to_path_buf(),
+                message: format!("line {}: {}", line_num + 1, message),
+            }
+        };
+
+        let line = match line.find('#') {
+            Some(pos) => &line[.. pos],
+            None => line,
+        };
+
+        let mut words = line.split_whitespace();
+        let keyword = match words.next() {
+            Some(k) => k,
+            None => continue,
+        };
+
+        match keyword {
+            "v" => {
+                let values = try!(parse_floats(words).map_err(&error));
+                match values.len() {
+                    3 | 4 => {
+                        positions.push([values[0], values[1], values[2],
+                                        values.get(3).cloned().unwrap_or(1.0)]);
+                        colors.push(None);
+                    },
+                    // position followed by a color
+                    6 => {
+                        positions.push([values[0], values[1], values[2], 1.0]);
+                        colors.push(Some([values[3], values[4], values[5], 1.0]));
+                    },
+                    _ => return Err(error(format!("wrong number of values for `v`"))),
+                }
+            },
+
+            "vt" => {
+                let values = try!(parse_floats(words).map_err(&error));
+                if values.len() < 1 || values.len() > 3 {
+                    return Err(error(format!("wrong number of values for `vt`")));
+                }
+
+                let mut value = [0.0; 4];
+                for (d, s) in value.iter_mut().zip(values.iter()) { *d = *s; }
+                tex_coords.push(value);
+            },
+
+            "vn" => {
+                let values = try!(parse_floats(words).map_err(&error));
+                if values.len() != 3 {
+                    return Err(error(format!("wrong number of values for `vn`")));
+                }
+
+                normals.push([values[0], values[1], values[2], 0.0]);
+            },
+
+            "f" => {
+                let mut corners = Vec::new();
+                for word in words {
+                    corners.push(try!(parse_face_vertex(word, positions.len(), tex_coords.len(),
+                                                        normals.len()).map_err(&error)));
+                }
+
+                if corners.len() < 3 {
+                    return Err(error(format!("a face must have at least three vertices")));
+                }
+
+                // splitting the polygon into a fan of triangles
+                for triangle in 1 .. corners.len() - 1 {
+                    for &(p, t, n) in [corners[0], corners[triangle], corners[triangle + 1]].iter() {
+                        builder.push(|semantic| {
+                            match semantic {
+                                Semantic::Position => Some(positions[p]),
+                                Semantic::TexCoords(0) => t.map(|t| tex_coords[t]),
+                                Semantic::Normal => n.map(|n| normals[n]),
+                                Semantic::Color(0) => colors[p],
+                                _ => None,
+                            }
+                        });
+                    }
+                }
+            },
+
+            "o" | "g" => {
+                let name = words.collect::<Vec<_>>().join(" ");
+                let name = if name.is_empty() { None } else { Some(name) };
+
+                if name != current_name {
+                    if !builder.is_empty() {
+                        meshes.push(builder.take(current_name.clone(), current_material));
+                    }
+                    current_name = name;
+                }
+            },
+
+            "usemtl" => {
+                let name = words.collect::<Vec<_>>().join(" ");
+                let material = materials_by_name.get(&name).cloned();
+
+                if material != current_material {
+                    if !builder.is_empty() {
+                        meshes.push(builder.take(current_name.clone(), current_material));
+                    }
+                    current_material = material;
+                }
+            },
+
+            "mtllib" => {
+                for file in words {
+                    for material in try!(load_mtl(&directory.join(file))) {
+                        materials_by_name.insert(material.name.clone(), materials.len());
+                        materials.push(material);
+                    }
+                }
+            },
+
+            // lines, points, smoothing groups, etc.
+            _ => ()
+        }
+    }
+
+    if !builder.is_empty() {
+        meshes.push(builder.take(current_name, current_material));
+    }
+
+    Ok(Scene {
+        meshes: meshes,
+        materials: materials,
+    })
+}
+
+/// Loads the materials of a MTL file.
+fn load_mtl(path: &Path) -> Result<Vec<Material>, MeshError> {
+    let source = try!(read_file(path));
+    let directory = path.parent().unwrap_or(Path::new(""));
+
+    let mut materials: Vec<Material> = Vec::new();
+
+    for (line_num, line) in source.lines().enumerate() {
+        let error = |message: String| {
+            MeshError::ParseError {
+                path: path.to_path_buf(),
+                message: format!("line {}: {}", line_num + 1, message),
+            }
+        };
+
+        let line = match line.find('#') {
+            Some(pos) => &line[.. pos],
+            None => line,
+        };
+
+        let mut words = line.split_whitespace();
+        let keyword = match words.next() {
+            Some(k) => k,
+            None => continue,
+        };
+
+        if keyword == "newmtl" {
+            let name = words.collect::<Vec<_>>().join(" ");
+            materials.push(Material::new(name));
+            continue;
+        }
+
+        let material = match materials.last_mut() {
+            Some(m) => m,
+            None => return Err(error(format!("`{}` before `newmtl`", keyword))),
+        };
+
+        match keyword {
+            "Kd" | "Ks" => {
+                let values = try!(parse_floats(words).map_err(&error));
+                if values.len() != 3 {
+                    return Err(error(format!("wrong number of values for `{}`", keyword)));
+                }
+
+                let dest = if keyword == "Kd" {
+                    &mut material.diffuse_color[.. 3]
+                } else {
+                    &mut material.specular_color[..]
+                };
+
+                for (d, s) in dest.iter_mut().zip(values.iter()) { *d = *s; }
+            },
+
+            "Ns" | "d" | "Tr" => {
+                let values = try!(parse_floats(words).map_err(&error));
+                if values.len() != 1 {
+                    return Err(error(format!("wrong number of values for `{}`", keyword)));
+                }
+
+                match keyword {
+                    "Ns" => material.shininess = values[0],
+                    "d" => material.diffuse_color[3] = values[0],
+                    _ => material.diffuse_color[3] = 1.0 - values[0],
+                }
+            },
+
+            "map_Kd" | "map_Ks" | "map_Bump" | "map_bump" | "bump" | "norm" => {
+                // the file name is the last word, after the options
+                let file = match words.last() {
+                    Some(f) => TextureSource::File(directory.join(f)),
+                    None => return Err(error(format!("missing file name for `{}`", keyword))),
+                };
+
+                match keyword {
+                    "map_Kd" => material.diffuse_texture = Some(file),
+                    "map_Ks" => material.specular_texture = Some(file),
+                    _ => material.normal_texture = Some(file),
+                }
+            },
+
+            _ => ()
+        }
+    }
+
+    Ok(materials)
+}
+
+fn read_file(path: &Path) -> Result<String, MeshError> {
+    let mut source = String::new();
+
+    match File::open(path).and_then(|mut f| f.read_to_string(&mut source)) {
+        Ok(_) => Ok(source),
+        Err(err) => Err(MeshError::IoError(PathBuf::from(path), err)),
+    }
+}
+
+fn parse_floats<'a, I>(words: I) -> Result<Vec<f32>, String> where I: Iterator<Item = &'a str> {
+    words.map(|w| w.parse().map_err(|_| format!("invalid number `{}`", w))).collect()
+}
+
+/// Parses a `v`, `v/vt`, `v//vn` or `v/vt/vn` element of a face, and turns the one-based or
+/// negative indices into indices within the lists.
+fn parse_face_vertex(word: &str, positions: usize, tex_coords: usize, normals: usize)
+                     -> Result<(usize, Option<usize>, Option<usize>), String>
+{
+    fn parse_index(index: &str, len: usize) -> Result<usize, String> {
+        let value: isize = try!(index.parse().map_err(|_| format!("invalid index `{}`", index)));
+
+        let result = if value < 0 {
+            len as isize + value
+        } else {
+            value - 1
+        };
+
+        if result < 0 || result >= len as isize {
+            return Err(format!("index `{}` is out of range", index));
+        }
+
+        Ok(result as usize)
+    }
+
+    let mut parts = word.split('/');
+
+    let position = try!(parse_index(parts.next().unwrap(), positions));
+
+    let tex_coords = match parts.next() {
+        Some("") | None => None,
+        Some(t) => Some(try!(parse_index(t, tex_coords))),
+    };
+
+    let normal = match parts.next() {
+        Some("") | None => None,
+        Some(n) => Some(try!(parse_index(n, normals))),
+    };
+
+    if parts.next().is_some() {
+        return Err(format!("invalid face element `{}`", word));
+    }
+
+    Ok((position, tex_coords, normal))
+}
@@ -1,68 +1,33 @@
 use fbo::{self, ValidatedAttachments};
 
 use context::Context;
+use context::CommandContext;
 use ContextExt;
 use Rect;
 
 use Surface;
+use framebuffer::ClearAttachmentError;
 
 use Api;
 use version::Version;
 use gl;
 
 
+/// Value to write in a single color buffer.
+#[derive(Copy, Clone, Debug)]
+pub enum ClearBufferData {
+    Float([f32; 4]),
+    Integral([i32; 4]),
+    Unsigned([u32; 4]),
+}
+
 pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments>,
              rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, depth: Option<f32>,
              stencil: Option<i32>)
 {
     unsafe {
         let mut ctxt = context.make_current();
-
-        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
-        fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
-
-        if ctxt.state.enabled_rasterizer_discard {
-            ctxt.gl.Disable(gl::RASTERIZER_DISCARD);
-            ctxt.state.enabled_rasterizer_discard = false;
-        }
-
-        if ctxt.state.color_mask != (1, 1, 1, 1) {
-            ctxt.state.color_mask = (1, 1, 1, 1);
-            ctxt.gl.ColorMask(1, 1, 1, 1);
-        }
-
-        if let Some(_) = ctxt.state.conditional_render {
-            if ctxt.version >= &Version(Api::Gl, 3, 0) {
-                ctxt.gl.EndConditionalRender();
-            } else if ctxt.extensions.gl_nv_conditional_render {
-                ctxt.gl.EndConditionalRenderNV();
-            } else {
-                unreachable!();
-            }
-
-            ctxt.state.conditional_render = None;
-        }
-
-        if let Some(rect) = rect {
-            let rect = (rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
-                        rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei);
-
-            if ctxt.state.scissor != Some(rect) {
-                ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
-                ctxt.state.scissor = Some(rect);
-            }
-
-            if !ctxt.state.enabled_scissor_test {
-                ctxt.gl.Enable(gl::SCISSOR_TEST);
-                ctxt.state.enabled_scissor_test = true;
-            }
-
-        } else {
-            if ctxt.state.enabled_scissor_test {
-                ctxt.gl.Disable(gl::SCISSOR_TEST);
-                ctxt.state.enabled_scissor_test = false;
-            }
-        }
+        prepare(&mut ctxt, framebuffer, rect);
 
         let mut flags = 0;
 
@@ -115,3 +80,92 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments>,
         ctxt.gl.Clear(flags);
     }
 }
+
+/// Clears a single draw buffer of the framebuffer with `glClearBuffer*`.
+///
+/// Contrary to `clear`, this works with integral and unsigned color buffers.
+///
+/// Returns an error if the backend doesn't support OpenGL 3.0 or OpenGL ES 3.0.
+pub fn clear_buffer(context: &Context, framebuffer: Option<&ValidatedAttachments>,
+                    rect: Option<&Rect>, draw_buffer: u32, data: ClearBufferData)
+                    -> Result<(), ClearAttachmentError>
+{
+    unsafe {
+        let mut ctxt = context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 0)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 0))
+        {
+            return Err(ClearAttachmentError::NotSupported);
+        }
+
+        prepare(&mut ctxt, framebuffer, rect);
+
+        let draw_buffer = draw_buffer as gl::types::GLint;
+
+        match data {
+            ClearBufferData::Float(value) => {
+                ctxt.gl.ClearBufferfv(gl::COLOR, draw_buffer, value.as_ptr());
+            },
+            ClearBufferData::Integral(value) => {
+                ctxt.gl.ClearBufferiv(gl::COLOR, draw_buffer, value.as_ptr());
+            },
+            ClearBufferData::Unsigned(value) => {
+                ctxt.gl.ClearBufferuiv(gl::COLOR, draw_buffer, value.as_ptr());
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Binds the framebuffer and sets the states that are taken into account when clearing.
+unsafe fn prepare(mut ctxt: &mut CommandContext, framebuffer: Option<&ValidatedAttachments>,
+                  rect: Option<&Rect>)
+{
+    let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
+    fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
+
+    if ctxt.state.enabled_rasterizer_discard {
+        ctxt.gl.Disable(gl::RASTERIZER_DISCARD);
+        ctxt.state.enabled_rasterizer_discard = false;
+    }
+
+    if ctxt.state.color_mask != (1, 1, 1, 1) {
+        ctxt.state.color_mask = (1, 1, 1, 1);
+        ctxt.gl.ColorMask(1, 1, 1, 1);
+    }
+
+    if let Some(_) = ctxt.state.conditional_render {
+        if ctxt.version >= &Version(Api::Gl, 3, 0) {
+            ctxt.gl.EndConditionalRender();
+        } else if ctxt.extensions.gl_nv_conditional_render {
+            ctxt.gl.EndConditionalRenderNV();
+        } else {
+            unreachable!();
+        }
+
+        ctxt.state.conditional_render = None;
+    }
+
+    if let Some(rect) = rect {
+        let rect = (rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
+                    rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei);
+
+        if ctxt.state.scissor != Some(rect) {
+            ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
+            ctxt.state.scissor = Some(rect);
+        }
+
+        if !ctxt.state.enabled_scissor_test {
+            ctxt.gl.Enable(gl::SCISSOR_TEST);
+            ctxt.state.enabled_scissor_test = true;
+        }
+
+    } else {
+        if ctxt.state.enabled_scissor_test {
+            ctxt.gl.Disable(gl::SCISSOR_TEST);
+            ctxt.state.enabled_scissor_test = false;
+        }
+    }
+}
@@ -26,7 +26,7 @@ use draw_parameters::{BlendingFunction, BackfaceCullingMode};
 use draw_parameters::{DepthTest, PolygonMode, StencilTest};
 use draw_parameters::{SamplesQueryParam, TransformFeedbackPrimitivesWrittenQuery};
 use draw_parameters::{PrimitivesGeneratedQuery, TimeElapsedQuery, ConditionalRendering};
-use draw_parameters::{Smooth};
+use draw_parameters::{Smooth, PrimitiveRestart};
 use Rect;
 
 use libc;
@@ -81,80 +81,138 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
     let mut ctxt = context.make_current();
 
     // handling vertices source
-    let (vertices_count, instances_count) = {
+    let (vertices_count, vertices_count_mismatch, instances_count, transform_feedback_vertices,
+         rebase) = {
         let index_buffer = match indices {
             IndicesSource::IndexBuffer { buffer, .. } => Some(buffer),
             IndicesSource::MultidrawArray { .. } => None,
             IndicesSource::NoIndices { .. } => None,
         };
 
+        let vertex_buffers = vertex_buffers.iter().collect::<Vec<_>>();
+        let rebase = get_rebase(&ctxt, &vertex_buffers, &indices, draw_parameters.base_vertex);
+
         // object that is used to build the bindings
         let mut binder = VertexAttributesSystem::start(&mut ctxt, program, index_buffer);
-        // number of vertices in the vertices sources, or `None` if there is a mismatch
+        // number of vertices in the first per-vertex source
         let mut vertices_count: Option<usize> = None;
-        // number of instances to draw
-        let mut instances_count: Option<usize> = None;
+        // error to return if the per-vertex sources are used without indices
+        let mut vertices_count_mismatch: Option<DrawError> = None;
+        // index, number of elements, divisor, and whether the first `base_instance` elements are
+        // skipped, of each per-instance source
+        let mut instances_sources: Vec<(usize, usize, usize, bool)> = Vec::new();
+        // transform feedback object whose written vertices are drawn, if any
+        let mut transform_feedback_vertices = None;
+
+        let base_instance = draw_parameters.base_instance as usize;
 
-        for src in vertex_buffers.iter() {
+        for (index, src) in vertex_buffers.into_iter().enumerate() {
             match src {
-                VerticesSource::VertexBuffer(buffer, format, per_instance) => {
+                VerticesSource::VertexBuffer(buffer, format, None) => {
+                    if let Some(fence) = buffer.add_fence() {
+                        fences.push(fence);
+                    }
+
+                    binder = binder.add(index, &buffer, rebase, format, None);
+                },
+                VerticesSource::VertexBuffer(buffer, format, divisor) => {
                     // TODO: assert!(buffer.get_elements_size() == total_size(format));
 
                     if let Some(fence) = buffer.add_fence() {
                         fences.push(fence);
                     }
 
-                    binder = binder.add(&buffer, format, if per_instance { Some(1) } else { None });
+                    binder = binder.add(index, &buffer, 0, format, divisor);
                 },
-                _ => {}
-            }
+                VerticesSource::TransformFeedback(buffer, format, vertices) => {
+                    // the number of vertices is only known by the GPU
+                    match indices {
+                        IndicesSource::NoIndices { .. } => (),
+                        _ => return Err(DrawError::TransformFeedbackVerticesWithIndices),
+                    }
 
-            match src {
-                VerticesSource::VertexBuffer(ref buffer, _, false) => {
-                    if let Some(curr) = vertices_count {
-                        if curr != buffer.get_elements_count() {
-                            vertices_count = None;
-                            break;
-                        }
-                    } else {
-                        vertices_count = Some(buffer.get_elements_count());
+                    if let Some(fence) = buffer.add_fence() {
+                        fences.push(fence);
                     }
+
+                    binder = binder.add(index, &buffer, 0, format, None);
+                    transform_feedback_vertices = Some(vertices.get_id());
                 },
-                VerticesSource::VertexBuffer(ref buffer, _, true) => {
-                    if let Some(curr) = instances_count {
-                        if curr != buffer.get_elements_count() {
-                            return Err(DrawError::InstancesCountMismatch);
-                        }
-                    } else {
-                        instances_count = Some(buffer.get_elements_count());
-                    }
+                _ => {}
+            }
+
+            let (per_vertex, per_instance) = match src {
+                VerticesSource::VertexBuffer(ref buffer, _, None) => {
+                    (Some(buffer.get_elements_count()), None)
                 },
-                VerticesSource::Marker { len, per_instance } if !per_instance => {
-                    if let Some(curr) = vertices_count {
-                        if curr != len {
-                            vertices_count = None;
-                            break;
-                        }
-                    } else {
-                        vertices_count = Some(len);
-                    }
+                VerticesSource::VertexBuffer(ref buffer, _, Some(divisor)) => {
+                    (None, Some((buffer.get_elements_count(), divisor as usize, true)))
                 },
-                VerticesSource::Marker { len, per_instance } if per_instance => {
-                    if let Some(curr) = instances_count {
-                        if curr != len {
-                            return Err(DrawError::InstancesCountMismatch);
+                VerticesSource::Marker { len, per_instance: false } => (Some(len), None),
+                VerticesSource::Marker { len, per_instance: true } => (None, Some((len, 1, false))),
+                VerticesSource::TransformFeedback(..) => (None, None),
+            };
+
+            if let Some(len) = per_vertex {
+                match vertices_count {
+                    Some(expected) if expected != len => {
+                        if vertices_count_mismatch.is_none() {
+                            vertices_count_mismatch = Some(DrawError::VerticesSourcesLengthMismatch {
+                                source: index,
+                                len: len,
+                                expected: expected,
+                            });
                         }
-                    } else {
-                        instances_count = Some(len);
-                    }
-                },
-                _ => ()
+                    },
+                    Some(_) => (),
+                    None => vertices_count = Some(len),
+                }
+            }
+
+            if let Some((len, divisor, skipped)) = per_instance {
+                instances_sources.push((index, len, divisor, skipped));
             }
         }
 
-        binder.bind();
+        // number of instances that a per-instance source can provide
+        let available = |&(_, len, divisor, skipped): &(usize, usize, usize, bool)| {
+            if skipped { len.saturating_sub(base_instance) * divisor } else { len * divisor }
+        };
+
+        // the sources whose divisor is 1 determine the number of instances, and the sources with
+        // another divisor must have enough elements for all the instances
+        let instances_count = match instances_sources.iter().find(|s| s.2 == 1) {
+            Some(s) => Some(available(s)),
+            None => instances_sources.iter().map(|s| available(s)).min(),
+        };
+
+        if let Some(count) = instances_count {
+            for s in instances_sources.iter() {
+                let &(index, len, divisor, skipped) = s;
+                let skipped = if skipped { base_instance } else { 0 };
+
+                let valid = if len < skipped {
+                    false
+                } else if divisor == 1 {
+                    available(s) == count
+                } else {
+                    available(s) >= count
+                };
+
+                if !valid {
+                    return Err(DrawError::InstancesCountMismatch {
+                        source: index,
+                        len: len,
+                        expected: (count + divisor - 1) / divisor + skipped,
+                    });
+                }
+            }
+        }
+
+        try!(binder.bind());
 
-        (vertices_count, instances_count)
+        (vertices_count, vertices_count_mismatch, instances_count, transform_feedback_vertices,
+         rebase)
     };
 
     // binding the FBO to draw upon
@@ -163,6 +221,11 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
         fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
     };
 
+    // the program can't be changed while transform feedback is active
+    if ctxt.state.program != program.get_id() {
+        TransformFeedbackSession::pause(&mut ctxt);
+    }
+
     // binding the program and uniforms
     program.use_program(&mut ctxt);
     try!(uniforms.bind_uniforms(&mut ctxt, program, &mut fences));
@@ -189,13 +252,14 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                           draw_parameters.transform_feedback_primitives_written_query));
         sync_conditional_render(&mut ctxt, draw_parameters.condition);
         try!(sync_smooth(&mut ctxt, draw_parameters.smooth, indices.get_primitives_type()));
+        try!(sync_primitive_restart(&mut ctxt, draw_parameters.primitive_restart, &indices));
 
         // TODO: make sure that the program is the right one
         // TODO: changing the current transform feedback requires pausing/unbinding before changing the program
         if let Some(ref tf) = draw_parameters.transform_feedback {
-            tf.bind(&mut ctxt, indices.get_primitives_type());
+            try!(tf.bind(&mut ctxt, program, indices.get_primitives_type()));
         } else {
-            TransformFeedbackSession::unbind(&mut ctxt);
+            TransformFeedbackSession::pause(&mut ctxt);
         }
 
         if !program.has_srgb_output() {
@@ -221,13 +285,38 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                     fences.push(fence);
                 }
 
+                let base_vertex = draw_parameters.base_vertex + rebase as gl::types::GLint;
+                let base_instance = draw_parameters.base_instance;
+
                 unsafe {
-                    if let Some(instances_count) = instances_count {
-                        ctxt.gl.DrawElementsInstanced(primitives.to_glenum(),
+                    if base_instance != 0 {
+                        ctxt.gl.DrawElementsInstancedBaseVertexBaseInstance(primitives.to_glenum(),
+                                                      buffer.get_elements_count() as gl::types::GLsizei,
+                                                      data_type.to_glenum(),
+                                                      ptr as *const libc::c_void,
+                                                      instances_count.unwrap_or(1) as gl::types::GLsizei,
+                                                      base_vertex, base_instance);
+                    } else if let Some(instances_count) = instances_count {
+                        if base_vertex != 0 {
+                            ctxt.gl.DrawElementsInstancedBaseVertex(primitives.to_glenum(),
+                                                      buffer.get_elements_count() as gl::types::GLsizei,
+                                                      data_type.to_glenum(),
+                                                      ptr as *const libc::c_void,
+                                                      instances_count as gl::types::GLsizei,
+                                                      base_vertex);
+                        } else {
+                            ctxt.gl.DrawElementsInstanced(primitives.to_glenum(),
                                                       buffer.get_elements_count() as gl::types::GLsizei,
                                                       data_type.to_glenum(),
                                                       ptr as *const libc::c_void,
                                                       instances_count as gl::types::GLsizei);
+                        }
+                    } else if base_vertex != 0 {
+                        ctxt.gl.DrawElementsBaseVertex(primitives.to_glenum(),
+                                                       buffer.get_elements_count() as gl::types::GLsizei,
+                                                       data_type.to_glenum(),
+                                                       ptr as *const libc::c_void,
+                                                       base_vertex);
                     } else {
                         ctxt.gl.DrawElements(primitives.to_glenum(),
                                              buffer.get_elements_count() as gl::types::GLsizei,
@@ -241,6 +330,15 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                 let ptr: *const u8 = ptr::null_mut();
                 let ptr = unsafe { ptr.offset(buffer.get_offset_bytes() as isize) };
 
+                // the commands contain their own first vertex and base instance
+                if draw_parameters.base_vertex != 0 {
+                    return Err(DrawError::BaseVertexNotSupported);
+                }
+
+                if draw_parameters.base_instance != 0 {
+                    return Err(DrawError::BaseInstanceNotSupported);
+                }
+
                 if let Some(fence) = buffer.add_fence() {
                     fences.push(fence);
                 }
@@ -253,19 +351,64 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                 }
             },
 
-            &IndicesSource::NoIndices { primitives } => {
-                let vertices_count = match vertices_count {
-                    Some(c) => c,
-                    None => return Err(DrawError::VerticesSourcesLengthMismatch)
-                };
+            &IndicesSource::NoIndices { primitives } if transform_feedback_vertices.is_some() => {
+                let id = transform_feedback_vertices.unwrap();
+
+                if draw_parameters.base_vertex != 0 {
+                    return Err(DrawError::BaseVertexNotSupported);
+                }
+
+                if draw_parameters.base_instance != 0 {
+                    return Err(DrawError::BaseInstanceNotSupported);
+                }
 
                 unsafe {
                     if let Some(instances_count) = instances_count {
-                        ctxt.gl.DrawArraysInstanced(primitives.to_glenum(), 0,
+                        if !(ctxt.version >= &Version(Api::Gl, 4, 2)) &&
+                           !ctxt.extensions.gl_arb_transform_feedback_instanced
+                        {
+                            return Err(DrawError::TransformFeedbackInstancingNotSupported);
+                        }
+
+                        ctxt.gl.DrawTransformFeedbackInstanced(primitives.to_glenum(), id,
+                                                    instances_count as gl::types::GLsizei);
+                    } else {
+                        ctxt.gl.DrawTransformFeedback(primitives.to_glenum(), id);
+                    }
+                }
+            },
+
+            &IndicesSource::NoIndices { primitives } => {
+                if let Some(err) = vertices_count_mismatch {
+                    return Err(err);
+                }
+
+                let vertices_count = vertices_count.unwrap_or(0);
+
+                // without indices, the base vertex is the first vertex to draw
+                let first = draw_parameters.base_vertex;
+                if first < 0 || first as usize > vertices_count {
+                    return Err(DrawError::BaseVertexOutOfRange {
+                        base_vertex: first,
+                        vertices_count: vertices_count,
+                    });
+                }
+                let vertices_count = vertices_count - first as usize;
+                let first = first + rebase as gl::types::GLint;
+                let base_instance = draw_parameters.base_instance;
+
+                unsafe {
+                    if base_instance != 0 {
+                        ctxt.gl.DrawArraysInstancedBaseInstance(primitives.to_glenum(), first,
+                                                    vertices_count as gl::types::GLsizei,
+                                                    instances_count.unwrap_or(1) as gl::types::GLsizei,
+                                                    base_instance);
+                    } else if let Some(instances_count) = instances_count {
+                        ctxt.gl.DrawArraysInstanced(primitives.to_glenum(), first,
                                                     vertices_count as gl::types::GLsizei,
                                                     instances_count as gl::types::GLsizei);
                     } else {
-                        ctxt.gl.DrawArrays(primitives.to_glenum(), 0,
+                        ctxt.gl.DrawArrays(primitives.to_glenum(), first,
                                            vertices_count as gl::types::GLsizei);
                     }
                 }
@@ -291,6 +434,59 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
     Ok(())
 }
 
+/// Returns the number of elements between the start of the buffers and the start of the per-vertex
+/// sources.
+///
+/// The per-vertex sources are bound at this number of elements before their start, and the value
+/// is added to the first vertex or to the base vertex of the draw command. This way, all the
+/// slices of a buffer (for example the slices allocated from a `BufferPool`) share the same vertex
+/// array object. Returns 0 if the sources don't have the same offset or if the draw command
+/// can't use it.
+fn get_rebase(ctxt: &context::CommandContext, vertex_buffers: &[VerticesSource],
+              indices: &IndicesSource, base_vertex: gl::types::GLint) -> usize
+{
+    match indices {
+        &IndicesSource::NoIndices { .. } => (),
+        &IndicesSource::IndexBuffer { .. } => {
+            if !(ctxt.version >= &Version(Api::Gl, 3, 2)) &&
+               !(ctxt.version >= &Version(Api::GlEs, 3, 2)) &&
+               !ctxt.extensions.gl_arb_draw_elements_base_vertex
+            {
+                return 0;
+            }
+        },
+        &IndicesSource::MultidrawArray { .. } => return 0,
+    }
+
+    let mut rebase = None;
+
+    for src in vertex_buffers.iter() {
+        match src {
+            &VerticesSource::VertexBuffer(ref buffer, _, None) => {
+                let offset = buffer.get_offset_bytes();
+                let size = buffer.get_elements_size();
+
+                if size == 0 || offset % size != 0 {
+                    return 0;
+                }
+
+                match rebase {
+                    Some(r) if r != offset / size => return 0,
+                    _ => rebase = Some(offset / size),
+                }
+            },
+            &VerticesSource::TransformFeedback(..) => return 0,
+            _ => (),
+        }
+    }
+
+    // the first vertex and the base vertex are `GLint`s
+    match rebase {
+        Some(r) if base_vertex as i64 + r as i64 <= gl::types::GLint::max_value() as i64 => r,
+        _ => 0,
+    }
+}
+
 fn sync_depth(ctxt: &mut context::CommandContext, depth_test: DepthTest, depth_write: bool,
               depth_range: (f32, f32))
 {
@@ -996,3 +1192,68 @@ fn sync_smooth(ctxt: &mut context::CommandContext,
 
     Ok(())
 }
+
+fn sync_primitive_restart(ctxt: &mut context::CommandContext,
+                          primitive_restart: Option<PrimitiveRestart>,
+                          indices: &IndicesSource) -> Result<(), DrawError>
+{
+    // primitive restart only applies to the indices of an index buffer
+    let data_type = match indices {
+        &IndicesSource::IndexBuffer { data_type, .. } => Some(data_type),
+        _ => None,
+    };
+
+    let (fixed_index, restart_index) = match (primitive_restart, data_type) {
+        (Some(PrimitiveRestart::FixedIndex), Some(_)) => {
+            if !(ctxt.version >= &Version(Api::Gl, 4, 3)) &&
+               !(ctxt.version >= &Version(Api::GlEs, 3, 0)) &&
+               !ctxt.extensions.gl_arb_es3_compatibility
+            {
+                return Err(DrawError::PrimitiveRestartNotSupported);
+            }
+
+            (true, None)
+        },
+        (Some(restart @ PrimitiveRestart::Index(_)), Some(data_type)) => {
+            if !(ctxt.version >= &Version(Api::Gl, 3, 1)) {
+                return Err(DrawError::PrimitiveRestartNotSupported);
+            }
+
+            match restart.get_index(data_type) {
+                Some(index) => (false, Some(index)),
+                None => return Err(DrawError::PrimitiveRestartIndexOutOfRange),
+            }
+        },
+        _ => (false, None),
+    };
+
+    unsafe {
+        if ctxt.state.enabled_primitive_restart_fixed_index != fixed_index {
+            if fixed_index {
+                ctxt.gl.Enable(gl::PRIMITIVE_RESTART_FIXED_INDEX);
+            } else {
+                ctxt.gl.Disable(gl::PRIMITIVE_RESTART_FIXED_INDEX);
+            }
+
+            ctxt.state.enabled_primitive_restart_fixed_index = fixed_index;
+        }
+
+        if let Some(index) = restart_index {
+            if !ctxt.state.enabled_primitive_restart {
+                ctxt.gl.Enable(gl::PRIMITIVE_RESTART);
+                ctxt.state.enabled_primitive_restart = true;
+            }
+
+            if ctxt.state.primitive_restart_index != index {
+                ctxt.gl.PrimitiveRestartIndex(index);
+                ctxt.state.primitive_restart_index = index;
+            }
+
+        } else if ctxt.state.enabled_primitive_restart {
+            ctxt.gl.Disable(gl::PRIMITIVE_RESTART);
+            ctxt.state.enabled_primitive_restart = false;
+        }
+    }
+
+    Ok(())
+}
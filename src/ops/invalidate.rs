@@ -0,0 +1,31 @@
+use fbo::{self, ValidatedAttachments};
+
+use context::Context;
+use ContextExt;
+use Rect;
+
+use gl;
+
+/// Tells the backend that the content of some attachments of the framebuffer is no longer
+/// needed. This is only a hint.
+pub fn invalidate(context: &Context, framebuffer: Option<&ValidatedAttachments>,
+                  rect: Option<&Rect>, color: bool, depth: bool, stencil: bool)
+{
+    let attachments = match framebuffer {
+        Some(framebuffer) => framebuffer.get_attachment_points(color, depth, stencil),
+        None => {
+            let mut attachments = Vec::with_capacity(3);
+            if color { attachments.push(gl::COLOR); }
+            if depth { attachments.push(gl::DEPTH); }
+            if stencil { attachments.push(gl::STENCIL); }
+            attachments
+        }
+    };
+
+    unsafe {
+        let mut ctxt = context.make_current();
+        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt,
+                                                                              framebuffer);
+        fbo::invalidate_framebuffer(&mut ctxt, fbo_id, &attachments, rect);
+    }
+}
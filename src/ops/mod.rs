@@ -1,9 +1,11 @@
 pub use self::blit::blit;
-pub use self::clear::clear;
+pub use self::clear::{clear, clear_buffer, ClearBufferData};
 pub use self::draw::draw;
+pub use self::invalidate::invalidate;
 pub use self::read::{read, read_if_supported, Source, Destination};
 
 mod blit;
 mod clear;
 mod draw;
+mod invalidate;
 mod read;
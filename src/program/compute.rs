@@ -11,10 +11,11 @@ use GlObject;
 use ProgramExt;
 use Handle;
 use RawUniformValue;
+use RawUniformArray;
 
 use program::{COMPILER_GLOBAL_LOCK, ProgramCreationError, Binary};
 
-use program::reflection::{Uniform, UniformBlock};
+use program::reflection::{Uniform, UniformBlock, AtomicCounter};
 use program::shader::build_shader;
 
 use program::raw::RawProgram;
@@ -127,6 +128,20 @@ impl ComputeShader {
     pub fn get_shader_storage_blocks(&self) -> &HashMap<String, UniformBlock> {
         self.raw.get_shader_storage_blocks()
     }
+
+    /// Returns the list of atomic counters.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # let program: glium::Program = unsafe { std::mem::uninitialized() };
+    /// for (name, counter) in program.get_atomic_counters() {
+    ///     println!("Name: {} - Binding: {}", name, counter.binding);
+    /// }
+    /// ```
+    pub fn get_atomic_counters(&self) -> &HashMap<String, AtomicCounter> {
+        self.raw.get_atomic_counters()
+    }
 }
 
 impl fmt::Debug for ComputeShader {
@@ -154,6 +169,12 @@ impl ProgramExt for ComputeShader {
         self.raw.set_uniform(ctxt, uniform_location, value)
     }
 
+    fn set_uniform_array(&self, ctxt: &mut CommandContext, uniform_location: gl::types::GLint,
+                         value: &RawUniformArray)
+    {
+        self.raw.set_uniform_array(ctxt, uniform_location, value)
+    }
+
     fn set_uniform_block_binding(&self, ctxt: &mut CommandContext, block_location: gl::types::GLuint,
                                  value: gl::types::GLuint)
     {
@@ -178,4 +199,8 @@ impl ProgramExt for ComputeShader {
     fn get_shader_storage_blocks(&self) -> &HashMap<String, UniformBlock> {
         self.raw.get_shader_storage_blocks()
     }
+
+    fn get_atomic_counters(&self) -> &HashMap<String, AtomicCounter> {
+        self.raw.get_atomic_counters()
+    }
 }
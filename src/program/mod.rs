@@ -4,14 +4,19 @@ use std::sync::Mutex;
 
 pub use self::compute::ComputeShader;
 pub use self::program::Program;
+pub use self::reloadable::{ReloadableProgram, ReloadError, SourcePaths};
 pub use self::reflection::{Uniform, UniformBlock, UniformBlockMember, OutputPrimitives};
+pub use self::reflection::AtomicCounter;
 pub use self::reflection::{Attribute, TransformFeedbackVarying, TransformFeedbackBuffer, TransformFeedbackMode};
+pub use self::translate::{translate_shader, ShaderStage};
 
 mod compute;
 mod program;
 mod raw;
 mod reflection;
+mod reloadable;
 mod shader;
+mod translate;
 mod uniforms_storage;
 
 /// Some shader compilers have race-condition issues, so we lock this mutex
@@ -41,6 +46,9 @@ pub enum ProgramCreationError {
     /// You have requested transform feedback varyings, but transform feedback is not supported
     /// by the backend.
     TransformFeedbackNotSupported,
+
+    /// Error while translating the source code to the GLSL version supported by the backend.
+    TranslationError(String),
 }
 
 impl fmt::Display for ProgramCreationError {
@@ -58,6 +66,8 @@ impl fmt::Display for ProgramCreationError {
             &ProgramCreationError::TransformFeedbackNotSupported => 
                 formatter.write_str("You requested transform feedback, but this feature is not \
                                      supported by the backend"),
+            &ProgramCreationError::TranslationError(ref s) =>
+                formatter.write_fmt(format_args!("Error while translating the shaders: {}", s)),
         }
     }
 }
@@ -74,6 +84,7 @@ impl Error for ProgramCreationError {
                                                                shaders compilation",
             &ProgramCreationError::TransformFeedbackNotSupported => "Transform feedback is not \
                                                                      supported by the backend.",
+            &ProgramCreationError::TranslationError(_) => "Error while translating the shaders",
         }
     }
 
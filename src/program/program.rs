@@ -15,12 +15,14 @@ use GlObject;
 use ProgramExt;
 use Handle;
 use RawUniformValue;
+use RawUniformArray;
 
 use program::{COMPILER_GLOBAL_LOCK, ProgramCreationInput, ProgramCreationError, Binary};
 
-use program::reflection::{Uniform, UniformBlock, OutputPrimitives};
+use program::reflection::{Uniform, UniformBlock, AtomicCounter, OutputPrimitives};
 use program::reflection::{Attribute, TransformFeedbackBuffer};
 use program::shader::build_shader;
+use program::translate::{translate_shader, ShaderStage};
 
 use program::raw::RawProgram;
 
@@ -129,6 +131,42 @@ impl Program {
         })
     }
 
+    /// Builds a new program from GLSL source code written for desktop GLSL, after translating
+    /// it to the GLSL version supported by the context.
+    ///
+    /// The sources must start with a `#version` directive, for example `#version 330 core`.
+    /// If the context supports this version, the sources are used unchanged. Otherwise they are
+    /// translated with `translate_shader`, which returns a `TranslationError` if a construct
+    /// can't be expressed with the version of the context.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+    /// # let vertex_source = ""; let fragment_source = "";
+    /// let program = glium::Program::from_source_translated(&display, vertex_source,
+    ///                                                      fragment_source, None);
+    /// ```
+    ///
+    pub fn from_source_translated<'a, F>(facade: &F, vertex_shader: &'a str,
+                                         fragment_shader: &'a str,
+                                         geometry_shader: Option<&'a str>)
+                                         -> Result<Program, ProgramCreationError> where F: Facade
+    {
+        let target = facade.get_context().get_supported_glsl_version();
+
+        let vertex_shader = try!(translate_shader(vertex_shader, ShaderStage::Vertex, &target));
+        let fragment_shader = try!(translate_shader(fragment_shader, ShaderStage::Fragment,
+                                                    &target));
+        let geometry_shader = match geometry_shader {
+            Some(gs) => Some(try!(translate_shader(gs, ShaderStage::Geometry, &target))),
+            None => None,
+        };
+
+        Program::from_source(facade, &vertex_shader, &fragment_shader,
+                             geometry_shader.as_ref().map(|s| &s[..]))
+    }
+
     /// Returns the program's compiled binary.
     ///
     /// You can store the result in a file, then reload it later. This avoids having to compile
@@ -211,6 +249,16 @@ impl Program {
         self.raw.transform_feedback_matches(format, stride)
     }
 
+    /// True if the transform feedback buffer `index` of this program matches the specified
+    /// `VertexFormat` and `stride`.
+    ///
+    /// Programs that use `TransformFeedbackMode::Separate` write to one buffer per varying.
+    pub fn transform_feedback_buffer_matches(&self, index: usize, format: &VertexFormat,
+                                             stride: usize) -> bool
+    {
+        self.raw.transform_feedback_buffer_matches(index, format, stride)
+    }
+
     /// Returns the type of geometry that transform feedback would generate, or `None` if it
     /// depends on the vertex/index data passed when drawing.
     ///
@@ -262,6 +310,29 @@ impl Program {
     pub fn get_shader_storage_blocks(&self) -> &HashMap<String, UniformBlock> {
         self.raw.get_shader_storage_blocks()
     }
+
+    /// Returns the list of atomic counters.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # let program: glium::Program = unsafe { std::mem::uninitialized() };
+    /// for (name, counter) in program.get_atomic_counters() {
+    ///     println!("Name: {} - Binding: {}", name, counter.binding);
+    /// }
+    /// ```
+    pub fn get_atomic_counters(&self) -> &HashMap<String, AtomicCounter> {
+        self.raw.get_atomic_counters()
+    }
+
+    /// Returns the names of the color outputs of the fragment shader, or `None` if the backend
+    /// doesn't support querying them.
+    ///
+    /// Built-in outputs that don't write to a color buffer, like `gl_FragDepth`, are not
+    /// included.
+    pub fn get_fragment_outputs_if_supported(&self) -> Option<&[String]> {
+        self.raw.get_fragment_outputs_if_supported()
+    }
 }
 
 impl fmt::Debug for Program {
@@ -289,6 +360,12 @@ impl ProgramExt for Program {
         self.raw.set_uniform(ctxt, uniform_location, value)
     }
 
+    fn set_uniform_array(&self, ctxt: &mut CommandContext, uniform_location: gl::types::GLint,
+                         value: &RawUniformArray)
+    {
+        self.raw.set_uniform_array(ctxt, uniform_location, value)
+    }
+
     fn set_uniform_block_binding(&self, ctxt: &mut CommandContext, block_location: gl::types::GLuint,
                                  value: gl::types::GLuint)
     {
@@ -313,4 +390,8 @@ impl ProgramExt for Program {
     fn get_shader_storage_blocks(&self) -> &HashMap<String, UniformBlock> {
         self.raw.get_shader_storage_blocks()
     }
+
+    fn get_atomic_counters(&self) -> &HashMap<String, AtomicCounter> {
+        self.raw.get_atomic_counters()
+    }
 }
@@ -21,17 +21,19 @@ use GlObject;
 use ProgramExt;
 use Handle;
 use RawUniformValue;
+use RawUniformArray;
 
 use sync;
 
 use program::{ProgramCreationError, Binary};
 use program::uniforms_storage::UniformsStorage;
 
-use program::reflection::{Uniform, UniformBlock, OutputPrimitives};
+use program::reflection::{Uniform, UniformBlock, AtomicCounter, OutputPrimitives};
 use program::reflection::{Attribute, TransformFeedbackMode, TransformFeedbackBuffer};
 use program::reflection::{reflect_uniforms, reflect_attributes, reflect_uniform_blocks};
 use program::reflection::{reflect_transform_feedback, reflect_geometry_output_type};
 use program::reflection::{reflect_tess_eval_output_type, reflect_shader_storage_blocks};
+use program::reflection::{reflect_atomic_counters, reflect_fragment_outputs};
 use program::shader::Shader;
 
 use uniforms::Uniforms;
@@ -50,6 +52,8 @@ pub struct RawProgram {
     frag_data_locations: RefCell<HashMap<String, Option<u32>>>,
     tf_buffers: Vec<TransformFeedbackBuffer>,
     ssbos: HashMap<String, UniformBlock>,
+    atomic_counters: HashMap<String, AtomicCounter>,
+    fragment_outputs: Option<Vec<String>>,
     output_primitives: Option<OutputPrimitives>,
     has_tessellation_shaders: bool,
 }
@@ -153,6 +157,8 @@ impl RawProgram {
         let blocks = unsafe { reflect_uniform_blocks(&mut ctxt, id) };
         let tf_buffers = unsafe { reflect_transform_feedback(&mut ctxt, id) };
         let ssbos = unsafe { reflect_shader_storage_blocks(&mut ctxt, id) };
+        let atomic_counters = unsafe { reflect_atomic_counters(&mut ctxt, id) };
+        let fragment_outputs = unsafe { reflect_fragment_outputs(&mut ctxt, id) };
 
         let output_primitives = if has_geometry_shader {
             Some(unsafe { reflect_geometry_output_type(&mut ctxt, id) })
@@ -172,6 +178,8 @@ impl RawProgram {
             frag_data_locations: RefCell::new(HashMap::new()),
             tf_buffers: tf_buffers,
             ssbos: ssbos,
+            atomic_counters: atomic_counters,
+            fragment_outputs: fragment_outputs,
             output_primitives: output_primitives,
             has_tessellation_shaders: has_tessellation_shaders,
         })
@@ -202,13 +210,16 @@ impl RawProgram {
             id
         };
 
-        let (uniforms, attributes, blocks, tf_buffers, ssbos) = unsafe {
+        let (uniforms, attributes, blocks, tf_buffers, ssbos, atomic_counters,
+             fragment_outputs) = unsafe {
             (
                 reflect_uniforms(&mut ctxt, id),
                 reflect_attributes(&mut ctxt, id),
                 reflect_uniform_blocks(&mut ctxt, id),
                 reflect_transform_feedback(&mut ctxt, id),
                 reflect_shader_storage_blocks(&mut ctxt, id),
+                reflect_atomic_counters(&mut ctxt, id),
+                reflect_fragment_outputs(&mut ctxt, id),
             )
         };
 
@@ -222,6 +233,8 @@ impl RawProgram {
             frag_data_locations: RefCell::new(HashMap::new()),
             tf_buffers: tf_buffers,
             ssbos: ssbos,
+            atomic_counters: atomic_counters,
+            fragment_outputs: fragment_outputs,
             output_primitives: None,            // FIXME: 
             has_tessellation_shaders: true,     // FIXME: 
         })
@@ -364,20 +377,27 @@ impl RawProgram {
     ///
     /// The `stride` is the number of bytes between two vertices.
     pub fn transform_feedback_matches(&self, format: &VertexFormat, stride: usize) -> bool {
-        // TODO: doesn't support multiple buffers
-
-        if self.get_transform_feedback_buffers().len() != 1 {
-            return false;
-        }
+        self.get_transform_feedback_buffers().len() == 1 &&
+            self.transform_feedback_buffer_matches(0, format, stride)
+    }
 
-        let buf = &self.get_transform_feedback_buffers()[0];
+    /// True if the transform feedback buffer `index` of this program matches the specified
+    /// `VertexFormat` and `stride`.
+    pub fn transform_feedback_buffer_matches(&self, index: usize, format: &VertexFormat,
+                                             stride: usize) -> bool
+    {
+        let buf = match self.get_transform_feedback_buffers().get(index) {
+            Some(buf) => buf,
+            None => return false,
+        };
 
         if buf.stride != stride {
             return false;
         }
 
         for elem in buf.elements.iter() {
-            if format.iter().find(|e| &e.0 == &*elem.name && e.1 == elem.offset && e.2 == elem.ty)
+            if format.iter().find(|e| &e.0 == &*elem.name && e.1 == elem.offset && e.2 == elem.ty &&
+                                      !e.3)
                             .is_none()
             {
                 return false;
@@ -439,6 +459,26 @@ impl RawProgram {
         &self.ssbos
     }
 
+    /// Returns the list of atomic counters.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # let program: glium::Program = unsafe { std::mem::uninitialized() };
+    /// for (name, counter) in program.get_atomic_counters() {
+    ///     println!("Name: {} - Binding: {}", name, counter.binding);
+    /// }
+    /// ```
+    pub fn get_atomic_counters(&self) -> &HashMap<String, AtomicCounter> {
+        &self.atomic_counters
+    }
+
+    /// Returns the names of the color outputs of the fragment shader, or `None` if the backend
+    /// doesn't support querying them.
+    pub fn get_fragment_outputs_if_supported(&self) -> Option<&[String]> {
+        self.fragment_outputs.as_ref().map(|o| &o[..])
+    }
+
     /// Assumes that the program contains a compute shader and executes it.
     ///
     /// # Safety
@@ -522,6 +562,12 @@ impl ProgramExt for RawProgram {
         self.uniform_values.set_uniform_value(ctxt, self.id, uniform_location, value);
     }
 
+    fn set_uniform_array(&self, ctxt: &mut CommandContext, uniform_location: gl::types::GLint,
+                         value: &RawUniformArray)
+    {
+        self.uniform_values.set_uniform_array_value(ctxt, self.id, uniform_location, value);
+    }
+
     fn set_uniform_block_binding(&self, ctxt: &mut CommandContext, block_location: gl::types::GLuint,
                                  value: gl::types::GLuint)
     {
@@ -546,6 +592,10 @@ impl ProgramExt for RawProgram {
     fn get_shader_storage_blocks(&self) -> &HashMap<String, UniformBlock> {
         &self.ssbos
     }
+
+    fn get_atomic_counters(&self) -> &HashMap<String, AtomicCounter> {
+        &self.atomic_counters
+    }
 }
 
 impl Drop for RawProgram {
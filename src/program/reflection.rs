@@ -10,7 +10,7 @@ use context::CommandContext;
 use version::Version;
 use version::Api;
 
-use uniforms::UniformType;
+use uniforms::{UniformType, BlockLayout};
 use vertex::AttributeType;
 
 use Handle;
@@ -27,6 +27,9 @@ pub struct Uniform {
     pub ty: UniformType,
 
     /// If it is an array, the number of elements.
+    ///
+    /// Arrays are named without their `[0]` suffix, so `uniform vec3 lights[16];` is
+    /// reported as `lights`.
     pub size: Option<usize>,
 }
 
@@ -43,6 +46,10 @@ pub struct UniformBlock {
 
     /// List of elements in the block.
     pub members: Vec<UniformBlockMember>,
+
+    /// Rules used to check the layout of arrays and matrices when the implementation doesn't
+    /// report their strides. `Std140` for uniform blocks and `Std430` for shader storage blocks.
+    pub layout: BlockLayout,
 }
 
 /// Information about a uniform inside a block.
@@ -59,6 +66,31 @@ pub struct UniformBlockMember {
 
     /// If it is an array, the number of elements.
     pub size: Option<usize>,
+
+    /// If it is an array, the distance in bytes between two elements, as reported by the
+    /// implementation.
+    pub array_stride: Option<usize>,
+
+    /// If it is a matrix, the distance in bytes between two columns, as reported by the
+    /// implementation.
+    pub matrix_stride: Option<usize>,
+}
+
+/// Information about an atomic counter of a program (except its name).
+#[derive(Debug, Copy, Clone)]
+pub struct AtomicCounter {
+    /// Index of the `GL_ATOMIC_COUNTER_BUFFER` binding point of the buffer that contains this
+    /// counter. It is set with `layout(binding = ...)` in the shader.
+    pub binding: u32,
+
+    /// Offset in bytes of the counter in the buffer.
+    pub offset: usize,
+
+    /// If it is an array, the number of counters.
+    pub size: Option<usize>,
+
+    /// Minimum size in bytes of the buffer that contains this counter.
+    pub buffer_size: usize,
 }
 
 /// Information about an attribute of a program (except its name).
@@ -185,6 +217,15 @@ pub unsafe fn reflect_uniforms(ctxt: &mut CommandContext, program: Handle)
         uniform_name_tmp.set_len(uniform_name_tmp_len as usize);
 
         let uniform_name = String::from_utf8(uniform_name_tmp).unwrap();
+
+        // arrays are reported as `name[0]`, while the user refers to them as `name`
+        let uniform_name = if uniform_name.ends_with("[0]") {
+            let len = uniform_name.len();
+            uniform_name[.. len - 3].to_string()
+        } else {
+            uniform_name
+        };
+
         let location = match program {
             Handle::Id(program) => {
                 assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
@@ -369,6 +410,18 @@ pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext, program: Handle)
         ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
                                     gl::UNIFORM_SIZE, member_size.as_mut_ptr());
 
+        // getting the array strides of the members
+        let mut member_array_stride = ::std::iter::repeat(0).take(num_members as usize)
+                                                            .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
+                                    gl::UNIFORM_ARRAY_STRIDE, member_array_stride.as_mut_ptr());
+
+        // getting the matrix strides of the members
+        let mut member_matrix_stride = ::std::iter::repeat(0).take(num_members as usize)
+                                                             .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
+                                    gl::UNIFORM_MATRIX_STRIDE, member_matrix_stride.as_mut_ptr());
+
         // getting the length of the names of the members
         let mut member_name_len = ::std::iter::repeat(0).take(num_members as usize)
                                                          .collect::<Vec<gl::types::GLint>>();
@@ -398,6 +451,14 @@ pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext, program: Handle)
                     1 => None,
                     a => Some(a as usize),
                 },
+                array_stride: match member_array_stride[index] {
+                    0 => None,
+                    a => Some(a as usize),
+                },
+                matrix_stride: match member_matrix_stride[index] {
+                    0 => None,
+                    a => Some(a as usize),
+                },
             }
         }).collect::<Vec<_>>();
 
@@ -406,6 +467,7 @@ pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext, program: Handle)
             binding: binding as i32,
             size: block_size as usize,
             members: members,
+            layout: BlockLayout::Std140,
         });
     }
 
@@ -669,15 +731,16 @@ pub unsafe fn reflect_shader_storage_blocks(ctxt: &mut CommandContext, program:
 
         // iterating over variables
         for variable in active_variables {
-            let (ty, array_size, offset, _array_stride, name_len) = {
-                let mut output: [gl::types::GLint; 5] = mem::uninitialized();
+            let (ty, array_size, offset, array_stride, matrix_stride, name_len) = {
+                let mut output: [gl::types::GLint; 6] = mem::uninitialized();
                 ctxt.gl.GetProgramResourceiv(program, gl::BUFFER_VARIABLE,
-                                             variable as gl::types::GLuint, 5,
+                                             variable as gl::types::GLuint, 6,
                                              [gl::TYPE, gl::ARRAY_SIZE, gl::OFFSET,
-                                              gl::ARRAY_STRIDE, gl::NAME_LENGTH].as_ptr(), 5,
+                                              gl::ARRAY_STRIDE, gl::MATRIX_STRIDE,
+                                              gl::NAME_LENGTH].as_ptr(), 6,
                                              ptr::null_mut(), output.as_mut_ptr() as *mut _);
                 (glenum_to_uniform_type(output[0] as gl::types::GLenum), output[1] as usize,
-                 output[2] as usize, output[3] as usize, output[4] as usize)
+                 output[2] as usize, output[3] as usize, output[4] as usize, output[5] as usize)
             };
 
             let name = {
@@ -700,6 +763,14 @@ pub unsafe fn reflect_shader_storage_blocks(ctxt: &mut CommandContext, program:
                     1 => None,
                     a => Some(a as usize),
                 },
+                array_stride: match array_stride {
+                    0 => None,
+                    a => Some(a),
+                },
+                matrix_stride: match matrix_stride {
+                    0 => None,
+                    a => Some(a),
+                },
             });
         }
 
@@ -708,12 +779,175 @@ pub unsafe fn reflect_shader_storage_blocks(ctxt: &mut CommandContext, program:
             binding: binding as i32,
             size: total_size,
             members: members,
+            layout: BlockLayout::Std430,
         });
     }
 
     blocks
 }
 
+/// Returns the list of atomic counters of a program.
+pub unsafe fn reflect_atomic_counters(ctxt: &mut CommandContext, program: Handle)
+                                      -> HashMap<String, AtomicCounter>
+{
+    if !(ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+         ctxt.extensions.gl_arb_shader_atomic_counters)
+    {
+        // not supported
+        return HashMap::with_capacity(0);
+    }
+
+    let program = match program {
+        Handle::Id(program) => program,
+        Handle::Handle(_) => return HashMap::with_capacity(0)
+    };
+
+    let mut active_buffers: gl::types::GLint = mem::uninitialized();
+    ctxt.gl.GetProgramiv(program, gl::ACTIVE_ATOMIC_COUNTER_BUFFERS, &mut active_buffers);
+
+    // the result of this function
+    let mut counters = HashMap::new();
+
+    for buffer_id in (0 .. active_buffers as gl::types::GLuint) {
+        // binding point of the buffer
+        let mut binding: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetActiveAtomicCounterBufferiv(program, buffer_id,
+                                               gl::ATOMIC_COUNTER_BUFFER_BINDING, &mut binding);
+
+        // number of bytes
+        let mut buffer_size: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetActiveAtomicCounterBufferiv(program, buffer_id,
+                                               gl::ATOMIC_COUNTER_BUFFER_DATA_SIZE,
+                                               &mut buffer_size);
+
+        // number of counters
+        let mut num_counters: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetActiveAtomicCounterBufferiv(program, buffer_id,
+                                               gl::ATOMIC_COUNTER_BUFFER_ACTIVE_ATOMIC_COUNTERS,
+                                               &mut num_counters);
+
+        // indices of the counters in the list of uniforms
+        let mut counters_indices = ::std::iter::repeat(0).take(num_counters as usize)
+                                                         .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveAtomicCounterBufferiv(program, buffer_id,
+                                               gl::ATOMIC_COUNTER_BUFFER_ACTIVE_ATOMIC_COUNTER_INDICES,
+                                               counters_indices.as_mut_ptr());
+        let counters_indices = counters_indices.into_iter().map(|i| i as gl::types::GLuint)
+                                               .collect::<Vec<_>>();
+
+        // getting the offsets of the counters
+        let mut counter_offsets = ::std::iter::repeat(0).take(num_counters as usize)
+                                                        .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_counters, counters_indices.as_ptr(),
+                                    gl::UNIFORM_OFFSET, counter_offsets.as_mut_ptr());
+
+        // getting the array sizes of the counters
+        let mut counter_size = ::std::iter::repeat(0).take(num_counters as usize)
+                                                     .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_counters, counters_indices.as_ptr(),
+                                    gl::UNIFORM_SIZE, counter_size.as_mut_ptr());
+
+        // getting the length of the names of the counters
+        let mut counter_name_len = ::std::iter::repeat(0).take(num_counters as usize)
+                                                          .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_counters, counters_indices.as_ptr(),
+                                    gl::UNIFORM_NAME_LENGTH, counter_name_len.as_mut_ptr());
+
+        for (index, &uniform_index) in counters_indices.iter().enumerate() {
+            let name = {
+                let name_len = counter_name_len[index];
+                let mut name_tmp: Vec<u8> = Vec::with_capacity(1 + name_len as usize);
+                let mut name_len_tmp = name_len;
+                ctxt.gl.GetActiveUniformName(program, uniform_index, name_len, &mut name_len_tmp,
+                                             name_tmp.as_mut_ptr() as *mut gl::types::GLchar);
+                name_tmp.set_len(name_len_tmp as usize);
+                String::from_utf8(name_tmp).unwrap()
+            };
+
+            // arrays are reported as `name[0]`, while the user refers to them as `name`
+            let name = if name.ends_with("[0]") {
+                let len = name.len();
+                name[.. len - 3].to_string()
+            } else {
+                name
+            };
+
+            counters.insert(name, AtomicCounter {
+                binding: binding as u32,
+                offset: counter_offsets[index] as usize,
+                size: match counter_size[index] {
+                    1 => None,
+                    a => Some(a as usize),
+                },
+                buffer_size: buffer_size as usize,
+            });
+        }
+    }
+
+    counters
+}
+
+/// Returns the names of the color outputs of the fragment shader of a program, or `None` if
+/// the backend doesn't support querying them.
+///
+/// Built-in outputs that don't write to a color buffer, like `gl_FragDepth`, are not included.
+pub unsafe fn reflect_fragment_outputs(ctxt: &mut CommandContext, program: Handle)
+                                       -> Option<Vec<String>>
+{
+    if !(ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+         ctxt.extensions.gl_arb_program_interface_query)
+    {
+        // not supported
+        return None;
+    }
+
+    let program = match program {
+        Handle::Id(program) => program,
+        Handle::Handle(_) => return None
+    };
+
+    // number of active outputs
+    let active_outputs = {
+        let mut active_outputs: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetProgramInterfaceiv(program, gl::PROGRAM_OUTPUT,
+                                      gl::ACTIVE_RESOURCES, &mut active_outputs);
+        active_outputs as gl::types::GLuint
+    };
+
+    // the result of this function
+    let mut outputs = Vec::with_capacity(active_outputs as usize);
+
+    for output_id in (0 .. active_outputs) {
+        let name_len = {
+            let mut name_len: gl::types::GLint = mem::uninitialized();
+            ctxt.gl.GetProgramResourceiv(program, gl::PROGRAM_OUTPUT, output_id, 1,
+                                         [gl::NAME_LENGTH].as_ptr(), 1, ptr::null_mut(),
+                                         &mut name_len);
+            name_len as usize
+        };
+
+        let name = {
+            let mut name_tmp: Vec<u8> = Vec::with_capacity(1 + name_len);
+            let mut name_tmp_len = name_len as gl::types::GLsizei;
+
+            ctxt.gl.GetProgramResourceName(program, gl::PROGRAM_OUTPUT, output_id,
+                                           name_tmp_len, &mut name_tmp_len,
+                                           name_tmp.as_mut_ptr() as *mut _);
+            name_tmp.set_len(name_tmp_len as usize);
+            String::from_utf8(name_tmp).unwrap()
+        };
+
+        // these built-ins don't write to color buffers
+        if name.starts_with("gl_FragDepth") || name.starts_with("gl_SampleMask") {
+            continue;
+        }
+
+        outputs.push(name);
+    }
+
+    Some(outputs)
+}
+
 fn glenum_to_uniform_type(ty: gl::types::GLenum) -> UniformType {
     match ty {
         gl::FLOAT => UniformType::Float,
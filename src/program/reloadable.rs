@@ -0,0 +1,368 @@
+use std::fmt;
+use std::error::Error;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+use backend::Facade;
+use context::Context;
+
+use program::{Program, ProgramCreationInput, ProgramCreationError, TransformFeedbackMode};
+
+/// Paths of the files that contain the source code of a `ReloadableProgram`.
+#[derive(Debug, Clone)]
+pub struct SourcePaths {
+    /// Path to the source code of the vertex shader.
+    pub vertex_shader: PathBuf,
+
+    /// Path to the source code of the optional tessellation control shader.
+    pub tessellation_control_shader: Option<PathBuf>,
+
+    /// Path to the source code of the optional tessellation evaluation shader.
+    pub tessellation_evaluation_shader: Option<PathBuf>,
+
+    /// Path to the source code of the optional geometry shader.
+    pub geometry_shader: Option<PathBuf>,
+
+    /// Path to the source code of the fragment shader.
+    pub fragment_shader: PathBuf,
+
+    /// The list of variables and mode to use for transform feedback.
+    pub transform_feedback_varyings: Option<(Vec<String>, TransformFeedbackMode)>,
+}
+
+/// Error that can happen when building or reloading a `ReloadableProgram`.
+#[derive(Debug)]
+pub enum ReloadError {
+    /// Error while reading one of the source files.
+    IoError(PathBuf, io::Error),
+
+    /// Error while building the program.
+    ProgramCreationError(ProgramCreationError),
+}
+
+impl fmt::Display for ReloadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            &ReloadError::IoError(ref path, ref err) =>
+                write!(formatter, "Error while reading `{}`: {}", path.display(), err),
+            &ReloadError::ProgramCreationError(ref err) => fmt::Display::fmt(err, formatter),
+        }
+    }
+}
+
+impl Error for ReloadError {
+    fn description(&self) -> &str {
+        match self {
+            &ReloadError::IoError(_, _) => "Error while reading one of the source files",
+            &ReloadError::ProgramCreationError(_) => "Error while building the program",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match self {
+            &ReloadError::IoError(_, ref err) => Some(err as &Error),
+            &ReloadError::ProgramCreationError(ref err) => Some(err as &Error),
+        }
+    }
+}
+
+impl From<ProgramCreationError> for ReloadError {
+    fn from(err: ProgramCreationError) -> ReloadError {
+        ReloadError::ProgramCreationError(err)
+    }
+}
+
+/// A program whose source code is read from files, and that is rebuilt when these files are
+/// modified.
+///
+/// Call `poll` regularly, for example once per frame, to check whether the files have changed.
+/// If the new source code fails to build, the previous program is kept and the error is
+/// available with `last_error`.
+///
+/// Dereferences to the current `Program`.
+///
+/// # Example
+///
+/// ```no_run
+/// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+/// let mut program = glium::program::ReloadableProgram::new(&display,
+///     glium::program::SourcePaths {
+///         vertex_shader: "shaders/main.vert".into(),
+///         tessellation_control_shader: None,
+///         tessellation_evaluation_shader: None,
+///         geometry_shader: None,
+///         fragment_shader: "shaders/main.frag".into(),
+///         transform_feedback_varyings: None,
+///     }).unwrap();
+///
+/// loop {
+///     if program.poll() {
+///         println!("shaders reloaded");
+///     } else if let Some(err) = program.last_error() {
+///         println!("{}", err);
+///     }
+///
+///     // draw with `&*program`
+/// #   break;
+/// }
+/// ```
+pub struct ReloadableProgram {
+    context: Rc<Context>,
+    paths: SourcePaths,
+    program: Program,
+
+    /// State of each source file when it was last read, in the same order as `list_paths`.
+    files: Vec<FileState>,
+
+    /// Hash of each source file of the current program, in the same order as `list_paths`.
+    program_hashes: Vec<u64>,
+
+    last_error: Option<ReloadError>,
+}
+
+/// State of a source file when it was last read.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct FileState {
+    /// Modification time of the file, if the platform provides it.
+    modified: Option<SystemTime>,
+    /// Time when the file was read.
+    read_at: SystemTime,
+    hash: u64,
+}
+
+/// Minimum duration between the modification time of a file and the time when it was read for
+/// the modification time to be trusted. Some file systems only store modification times with a
+/// precision of two seconds, and a file modified again within this interval keeps the same
+/// modification time.
+const MODIFICATION_TIME_PRECISION_SECS: u64 = 2;
+
+impl ReloadableProgram {
+    /// Reads the source files and builds the program.
+    pub fn new<F>(facade: &F, paths: SourcePaths) -> Result<ReloadableProgram, ReloadError>
+                  where F: Facade
+    {
+        let (program, files) = try!(build(facade.get_context(), &paths));
+
+        Ok(ReloadableProgram {
+            context: facade.get_context().clone(),
+            paths: paths,
+            program: program,
+            program_hashes: files.iter().map(|f| f.hash).collect(),
+            files: files,
+            last_error: None,
+        })
+    }
+
+    /// Checks whether the content of the source files has changed since they were last read,
+    /// and rebuilds the program if that's the case.
+    ///
+    /// The modification times of the files are compared first, and the files are only read and
+    /// hashed if one of them has changed. Files that were modified less than two seconds before
+    /// they were last read are always read again, as modification times are not precise enough
+    /// to detect edits that happen in quick succession. Saving a file without modifying its
+    /// content doesn't rebuild the program.
+    ///
+    /// Returns `true` if the program has been replaced by a new one. If the files have been
+    /// modified but reading them or building the program failed, the previous program is kept,
+    /// the error is stored in `last_error` and `false` is returned. The error is cleared the
+    /// next time the program is successfully rebuilt, or when the files are read back with the
+    /// content of the current program.
+    pub fn poll(&mut self) -> bool {
+        match build_if_modified(&self.context, &self.paths, &self.files) {
+            Ok((Some(program), files)) => {
+                self.program = program;
+                self.program_hashes = files.iter().map(|f| f.hash).collect();
+                self.files = files;
+                self.last_error = None;
+                true
+            },
+            Ok((None, files)) => {
+                // the error no longer applies if the files contain the sources of the current
+                // program, for example after a failed read while an editor was saving them
+                let hashes: Vec<u64> = files.iter().map(|f| f.hash).collect();
+                if hashes == self.program_hashes {
+                    self.last_error = None;
+                }
+
+                self.files = files;
+                false
+            },
+            Err((err, files)) => {
+                // remembering the new state, so that the same broken sources are not rebuilt
+                // every time `poll` is called
+                if let Some(files) = files {
+                    self.files = files;
+                }
+                self.last_error = Some(err);
+                false
+            },
+        }
+    }
+
+    /// Returns the error that happened during the last attempt to rebuild the program, if any.
+    pub fn last_error(&self) -> Option<&ReloadError> {
+        self.last_error.as_ref()
+    }
+
+    /// Returns the paths of the source files.
+    pub fn get_paths(&self) -> &SourcePaths {
+        &self.paths
+    }
+}
+
+impl Deref for ReloadableProgram {
+    type Target = Program;
+
+    fn deref(&self) -> &Program {
+        &self.program
+    }
+}
+
+/// Returns the list of the paths that are used, in the order in which they are read.
+fn list_paths(paths: &SourcePaths) -> Vec<&Path> {
+    let mut list = vec![&paths.vertex_shader as &Path];
+    if let Some(ref p) = paths.tessellation_control_shader { list.push(p); }
+    if let Some(ref p) = paths.tessellation_evaluation_shader { list.push(p); }
+    if let Some(ref p) = paths.geometry_shader { list.push(p); }
+    list.push(&paths.fragment_shader);
+    list
+}
+
+/// Reads a source file.
+fn read_source(path: &Path) -> Result<(String, FileState), ReloadError> {
+    let read = || -> io::Result<(String, Option<SystemTime>, SystemTime)> {
+        let mut file = try!(File::open(path));
+        // the modification time is queried before reading, so that a modification that happens
+        // while reading is detected by the next call to `poll`
+        let modified = try!(file.metadata()).modified().ok();
+        let read_at = SystemTime::now();
+        let mut source = String::new();
+        try!(file.read_to_string(&mut source));
+        Ok((source, modified, read_at))
+    };
+
+    let (source, modified, read_at) = match read() {
+        Ok(r) => r,
+        Err(err) => return Err(ReloadError::IoError(path.to_path_buf(), err)),
+    };
+
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+
+    Ok((source, FileState { modified: modified, read_at: read_at, hash: hasher.finish() }))
+}
+
+/// Returns true if the modification time of one of the files is different from `previous`, or
+/// can't be trusted.
+fn is_modified(paths: &SourcePaths, previous: &[FileState]) -> Result<bool, ReloadError> {
+    let paths = list_paths(paths);
+
+    if paths.len() != previous.len() {
+        return Ok(true);
+    }
+
+    for (path, previous) in paths.into_iter().zip(previous.iter()) {
+        let modified = match fs::metadata(path) {
+            Ok(m) => m.modified().ok(),
+            Err(err) => return Err(ReloadError::IoError(path.to_path_buf(), err)),
+        };
+
+        let precision = Duration::from_secs(MODIFICATION_TIME_PRECISION_SECS);
+        let trusted = match previous.modified {
+            Some(m) => previous.read_at.duration_since(m).map(|d| d >= precision).unwrap_or(false),
+            None => false,
+        };
+
+        if !trusted || modified != previous.modified {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Reads all the source files.
+fn read_sources(paths: &SourcePaths) -> Result<(Vec<String>, Vec<FileState>), ReloadError> {
+    let mut sources = Vec::new();
+    let mut files = Vec::new();
+
+    for path in list_paths(paths) {
+        let (source, state) = try!(read_source(path));
+        sources.push(source);
+        files.push(state);
+    }
+
+    Ok((sources, files))
+}
+
+/// Builds a program from sources that have been read with `read_sources`.
+fn build_from_sources<F>(facade: &F, paths: &SourcePaths, sources: &[String])
+                         -> Result<Program, ProgramCreationError> where F: Facade
+{
+    let mut sources = sources.iter().map(|s| &s[..]);
+
+    let vertex_shader = sources.next().unwrap();
+    let tessellation_control_shader = paths.tessellation_control_shader.as_ref()
+                                           .map(|_| sources.next().unwrap());
+    let tessellation_evaluation_shader = paths.tessellation_evaluation_shader.as_ref()
+                                              .map(|_| sources.next().unwrap());
+    let geometry_shader = paths.geometry_shader.as_ref().map(|_| sources.next().unwrap());
+    let fragment_shader = sources.next().unwrap();
+
+    Program::new(facade, ProgramCreationInput::SourceCode {
+        vertex_shader: vertex_shader,
+        tessellation_control_shader: tessellation_control_shader,
+        tessellation_evaluation_shader: tessellation_evaluation_shader,
+        geometry_shader: geometry_shader,
+        fragment_shader: fragment_shader,
+        transform_feedback_varyings: paths.transform_feedback_varyings.clone(),
+    })
+}
+
+/// Reads the sources and builds the program.
+fn build<F>(facade: &F, paths: &SourcePaths) -> Result<(Program, Vec<FileState>), ReloadError>
+            where F: Facade
+{
+    let (sources, files) = try!(read_sources(paths));
+    let program = try!(build_from_sources(facade, paths, &sources));
+    Ok((program, files))
+}
+
+/// Reads the sources and builds the program if the content of one of the files is different
+/// from `previous`. Returns the new state of the files alongside the program.
+///
+/// The files are only read if their modification time is different from `previous`.
+///
+/// In case of a compilation error, the new state of the files is returned alongside the error.
+fn build_if_modified<F>(facade: &F, paths: &SourcePaths, previous: &[FileState])
+                        -> Result<(Option<Program>, Vec<FileState>),
+                                  (ReloadError, Option<Vec<FileState>>)>
+                        where F: Facade
+{
+    match is_modified(paths, previous) {
+        Ok(true) => (),
+        Ok(false) => return Ok((None, previous.to_vec())),
+        Err(err) => return Err((err, None)),
+    }
+
+    let (sources, files) = match read_sources(paths) {
+        Ok(r) => r,
+        Err(err) => return Err((err, None)),
+    };
+
+    let same_content = files.iter().zip(previous.iter()).all(|(a, b)| a.hash == b.hash);
+    if same_content && files.len() == previous.len() {
+        return Ok((None, files));
+    }
+
+    match build_from_sources(facade, paths, &sources) {
+        Ok(program) => Ok((Some(program), files)),
+        Err(err) => Err((ReloadError::from(err), Some(files))),
+    }
+}
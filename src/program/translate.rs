@@ -0,0 +1,797 @@
+/*!
+Translation of GLSL source code between desktop GLSL and GLSL ES.
+
+The input is a canonical desktop source, for example GLSL 330 core, and the output is a source
+that uses the dialect of another GLSL version.
+
+*/
+use std::collections::HashMap;
+
+use version::Version;
+use version::Api;
+
+use program::ProgramCreationError;
+
+/// Stage of the pipeline a shader belongs to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderStage {
+    /// Vertex shader.
+    Vertex,
+    /// Tessellation control shader.
+    TessellationControl,
+    /// Tessellation evaluation shader.
+    TessellationEvaluation,
+    /// Geometry shader.
+    Geometry,
+    /// Fragment shader.
+    Fragment,
+}
+
+/// Translates the source code of a shader to the given GLSL version.
+///
+/// The source must start with a `#version` directive of desktop GLSL, for example
+/// `#version 330 core`. If the target version is equal or more recent than the version of the
+/// source, the source is returned unchanged.
+///
+/// When translating to GLSL 1.10, GLSL 1.20 or GLSL ES 1.00:
+///
+///  - Global `in` and `out` variables become `attribute` and `varying`.
+///  - `texture()` becomes `texture2D()`, `textureCube()`, `texture3D()`, `texture1D()` or
+///    `shadow2D()` depending on the type of the sampler.
+///  - The outputs of the fragment shader are replaced by `gl_FragColor` or `gl_FragData`.
+///
+/// In addition to this, `layout(location = ...)` qualifiers are removed when the target
+/// doesn't support them, and a default precision is added when translating to GLSL ES.
+///
+/// Returns a `ProgramCreationError::TranslationError` describing the problem if the source
+/// uses a construct that the target doesn't support.
+pub fn translate_shader(source: &str, stage: ShaderStage, target: &Version)
+                        -> Result<String, ProgramCreationError>
+{
+    let tokens = tokenize(source);
+
+    // finding the `#version` directive
+    let (version_index, source_version) = {
+        let directive = tokens.iter().enumerate().filter_map(|(index, token)| {
+            match token {
+                &Token::Directive(d) => Some((index, d)),
+                _ => None,
+            }
+        }).next();
+
+        match directive {
+            Some((index, directive)) if directive.trim_left_matches('#').trim_left()
+                                                 .starts_with("version") =>
+            {
+                (index, try!(parse_version_directive(directive)))
+            },
+            _ => return Err(error("the source must start with a `#version` directive")),
+        }
+    };
+
+    if source_version.0 != Api::Gl {
+        return Err(error("only desktop GLSL sources can be translated"));
+    }
+
+    if target.0 == Api::Gl && *target >= source_version {
+        return Ok(source.to_string());
+    }
+
+    let legacy = match *target {
+        Version(Api::Gl, 1, _) => target.2 < 3,
+        Version(Api::GlEs, 1, _) => true,
+        _ => false,
+    };
+
+    // checking that the stage is supported by the target
+    match stage {
+        ShaderStage::Vertex | ShaderStage::Fragment => (),
+        ShaderStage::Geometry => {
+            if !(*target >= Version(Api::Gl, 1, 5) || *target >= Version(Api::GlEs, 3, 2)) {
+                return Err(error(&format!("geometry shaders are not supported by {}",
+                                          describe(target))));
+            }
+        },
+        ShaderStage::TessellationControl | ShaderStage::TessellationEvaluation => {
+            if !(*target >= Version(Api::Gl, 4, 0) || *target >= Version(Api::GlEs, 3, 2)) {
+                return Err(error(&format!("tessellation shaders are not supported by {}",
+                                          describe(target))));
+            }
+        },
+    }
+
+    let mut translator = Translator {
+        tokens: &tokens,
+        stage: stage,
+        target: *target,
+        legacy: legacy,
+        samplers: find_samplers(&tokens),
+        outputs: HashMap::new(),
+    };
+
+    let body = try!(translator.translate(version_index + 1));
+
+    // `#extension` directives must come before any other token, so the default precision is
+    // inserted after the preprocessor lines that directly follow `#version`, which are copied
+    // as-is at the start of `body`
+    let header_len = {
+        let mut len = 0;
+        let mut header_len = 0;
+        for token in &tokens[version_index + 1 ..] {
+            match token {
+                &Token::Directive(_) => {
+                    len += token.as_str().len();
+                    header_len = len;
+                },
+                t if t.is_blank() => len += t.as_str().len(),
+                _ => break,
+            }
+        }
+        header_len
+    };
+
+    let mut result = String::with_capacity(source.len() + 64);
+    for token in &tokens[.. version_index] {
+        result.push_str(token.as_str());
+    }
+
+    result.push_str(&version_directive(target));
+    result.push('\n');
+
+    if header_len != 0 {
+        result.push_str(&body[.. header_len]);
+        result.push('\n');
+    }
+
+    if target.0 == Api::GlEs {
+        match stage {
+            ShaderStage::Fragment => result.push_str("precision mediump float;\n"),
+            _ => result.push_str("precision highp float;\n"),
+        }
+    }
+
+    result.push_str(&body[header_len ..]);
+    Ok(result)
+}
+
+/// Builds a `TranslationError`.
+fn error(msg: &str) -> ProgramCreationError {
+    ProgramCreationError::TranslationError(msg.to_string())
+}
+
+/// Returns a human-readable name for a GLSL version.
+fn describe(version: &Version) -> String {
+    match version.0 {
+        Api::Gl => format!("GLSL {}.{}", version.1, version.2),
+        Api::GlEs => format!("GLSL ES {}.{}", version.1, version.2),
+    }
+}
+
+/// Returns the `#version` directive corresponding to a GLSL version.
+fn version_directive(version: &Version) -> String {
+    let num = version.1 as u32 * 100 + version.2 as u32 * 10;
+
+    match *version {
+        Version(Api::GlEs, 1, 0) => format!("#version 100"),
+        Version(Api::GlEs, _, _) => format!("#version {} es", num),
+        Version(Api::Gl, _, _) => format!("#version {}", num),
+    }
+}
+
+/// Parses a directive like `#version 330 core`.
+fn parse_version_directive(directive: &str) -> Result<Version, ProgramCreationError> {
+    let mut words = directive.trim_left_matches('#').split(|c: char| c.is_whitespace())
+                             .filter(|w| !w.is_empty()).skip(1);
+
+    let num: u32 = match words.next().and_then(|n| n.parse().ok()) {
+        Some(n) => n,
+        None => return Err(error("invalid `#version` directive")),
+    };
+
+    let es = words.next() == Some("es") || num == 100;
+    let api = if es { Api::GlEs } else { Api::Gl };
+
+    Ok(Version(api, (num / 100) as u8, ((num % 100) / 10) as u8))
+}
+
+/// A token of GLSL source code.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    /// An identifier or a keyword.
+    Ident(&'a str),
+    /// A single punctuation character.
+    Punct(&'a str),
+    /// A preprocessor directive, without the line break.
+    Directive(&'a str),
+    /// Whitespace, comments or a number. Copied as-is.
+    Other(&'a str),
+}
+
+impl<'a> Token<'a> {
+    fn as_str(&self) -> &'a str {
+        match *self {
+            Token::Ident(s) => s,
+            Token::Punct(s) => s,
+            Token::Directive(s) => s,
+            Token::Other(s) => s,
+        }
+    }
+
+    /// Returns true if the token is whitespace or a comment.
+    fn is_blank(&self) -> bool {
+        match *self {
+            Token::Other(s) => s.starts_with("//") || s.starts_with("/*") ||
+                               s.chars().all(|c| c.is_whitespace()),
+            _ => false,
+        }
+    }
+}
+
+/// Splits the source code into tokens. Concatenating the tokens gives back the source.
+fn tokenize(source: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let bytes = source.as_bytes();
+    let mut start_of_line = true;
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        let start = pos;
+
+        if c == b'#' && start_of_line {
+            while pos < bytes.len() && bytes[pos] != b'\n' { pos += 1; }
+            tokens.push(Token::Directive(&source[start .. pos]));
+            continue;
+
+        } else if c == b'/' && pos + 1 < bytes.len() && bytes[pos + 1] == b'/' {
+            while pos < bytes.len() && bytes[pos] != b'\n' { pos += 1; }
+
+        } else if c == b'/' && pos + 1 < bytes.len() && bytes[pos + 1] == b'*' {
+            pos += 2;
+            while pos < bytes.len() && !(bytes[pos - 1] == b'*' && bytes[pos] == b'/') {
+                pos += 1;
+            }
+            pos = if pos < bytes.len() { pos + 1 } else { pos };
+
+        } else if is_ascii_space(c) {
+            while pos < bytes.len() && is_ascii_space(bytes[pos]) {
+                if bytes[pos] == b'\n' { start_of_line = true; }
+                pos += 1;
+            }
+            tokens.push(Token::Other(&source[start .. pos]));
+            continue;
+
+        } else if is_ident_char(c) && !(c as char).is_digit(10) {
+            while pos < bytes.len() && is_ident_char(bytes[pos]) { pos += 1; }
+            tokens.push(Token::Ident(&source[start .. pos]));
+            start_of_line = false;
+            continue;
+
+        } else if (c as char).is_digit(10) {
+            while pos < bytes.len() && (is_ident_char(bytes[pos]) || bytes[pos] == b'.') {
+                pos += 1;
+            }
+
+        } else {
+            // the source is valid UTF-8, so we must not split a multi-byte character
+            pos += 1;
+            while pos < bytes.len() && (bytes[pos] & 0xc0) == 0x80 { pos += 1; }
+            tokens.push(Token::Punct(&source[start .. pos]));
+            start_of_line = false;
+            continue;
+        }
+
+        tokens.push(Token::Other(&source[start .. pos]));
+        start_of_line = false;
+    }
+
+    tokens
+}
+
+fn is_ascii_space(c: u8) -> bool {
+    c == b' ' || c == b'\t' || c == b'\n' || c == b'\r' || c == 0x0b || c == 0x0c
+}
+
+fn is_ident_char(c: u8) -> bool {
+    (c >= b'a' && c <= b'z') || (c >= b'A' && c <= b'Z') || (c >= b'0' && c <= b'9') || c == b'_'
+}
+
+/// Returns the list of variables of a sampler type, associated with the name of the type.
+fn find_samplers<'a>(tokens: &[Token<'a>]) -> HashMap<&'a str, &'a str> {
+    let mut samplers = HashMap::new();
+
+    let idents = tokens.iter().filter_map(|t| match t {
+        &Token::Ident(s) => Some(s),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    for pair in idents.windows(2) {
+        if pair[0].starts_with("sampler") {
+            samplers.insert(pair[1], pair[0]);
+        }
+    }
+
+    samplers
+}
+
+struct Translator<'t, 'a: 't> {
+    tokens: &'t [Token<'a>],
+    stage: ShaderStage,
+    target: Version,
+    legacy: bool,
+    samplers: HashMap<&'a str, &'a str>,
+    /// Outputs of the fragment shader, with their replacement.
+    outputs: HashMap<&'a str, String>,
+}
+
+impl<'t, 'a> Translator<'t, 'a> {
+    /// Returns the index of the next token that is not blank, starting at `index`.
+    fn next_non_blank(&self, mut index: usize) -> usize {
+        while index < self.tokens.len() && self.tokens[index].is_blank() {
+            index += 1;
+        }
+        index
+    }
+
+    fn translate(&mut self, start: usize) -> Result<String, ProgramCreationError> {
+        let mut output = String::new();
+        let mut brace_depth = 0u32;
+        let mut paren_depth = 0u32;
+        let mut num_fragment_outputs = 0u32;
+
+        // location specified by the last `layout` qualifier, if any
+        let mut pending_location: Option<u32> = None;
+
+        // last token that is not blank
+        let mut last_non_blank: Option<Token<'a>> = None;
+        // true inside the body of a struct or of an interface block
+        let mut in_member_list = false;
+        // local variables and parameters that have the same name as a fragment shader output,
+        // with the brace depth at which they go out of scope
+        let mut shadowed: Vec<(&'a str, u32)> = Vec::new();
+
+        let mut index = start;
+        while index < self.tokens.len() {
+            let token = self.tokens[index];
+            let global = brace_depth == 0 && paren_depth == 0;
+
+            let prev = last_non_blank;
+            if !token.is_blank() {
+                last_non_blank = Some(token);
+            }
+
+            match token {
+                Token::Punct("{") => {
+                    brace_depth += 1;
+
+                    // function bodies are the only global blocks that follow a `)`
+                    if brace_depth == 1 && prev != Some(Token::Punct(")")) {
+                        in_member_list = true;
+                    }
+                },
+                Token::Punct("}") => {
+                    brace_depth = brace_depth.saturating_sub(1);
+                    if brace_depth == 0 {
+                        in_member_list = false;
+                    }
+                    shadowed.retain(|&(_, depth)| depth <= brace_depth);
+                },
+                Token::Punct("(") => paren_depth += 1,
+                Token::Punct(")") => paren_depth = paren_depth.saturating_sub(1),
+                Token::Punct(";") => {
+                    pending_location = None;
+
+                    // parameters of a function prototype
+                    if global {
+                        shadowed.clear();
+                    }
+                },
+                _ => ()
+            }
+
+            match token {
+                Token::Directive(d) if d.trim_left_matches('#').trim_left()
+                                        .starts_with("version") =>
+                {
+                    return Err(error("found multiple `#version` directives"));
+                },
+
+                Token::Ident("layout") if global => {
+                    let (end, entries) = try!(self.parse_layout(index + 1));
+                    let only_location = entries.iter().all(|&(name, _)| name == "location");
+
+                    if let Some(&(_, value)) = entries.iter().find(|&&(name, _)| name == "location") {
+                        pending_location = value;
+                    }
+
+                    let keep = if !only_location {
+                        if self.legacy {
+                            return Err(error(&format!("layout qualifiers are not supported by \
+                                                       {}", describe(&self.target))));
+                        }
+                        true
+                    } else {
+                        // `layout(location)` on vertex inputs and fragment outputs is
+                        // supported by GLSL 3.30 and GLSL ES 3.00
+                        self.target >= Version(Api::Gl, 3, 3) ||
+                        self.target >= Version(Api::GlEs, 3, 0)
+                    };
+
+                    if keep {
+                        for t in &self.tokens[index .. end] {
+                            output.push_str(t.as_str());
+                        }
+                        index = end;
+                    } else {
+                        index = self.next_non_blank(end);
+                    }
+
+                    continue;
+                },
+
+                Token::Ident("in") | Token::Ident("out") if global && self.legacy => {
+                    let is_out = token == Token::Ident("out");
+                    let next = self.next_non_blank(index + 1);
+
+                    // interface blocks
+                    if let Some(&Token::Ident(_)) = self.tokens.get(next) {
+                        let after = self.next_non_blank(next + 1);
+                        if self.tokens.get(after) == Some(&Token::Punct("{")) {
+                            return Err(error(&format!("interface blocks are not supported by {}",
+                                                      describe(&self.target))));
+                        }
+                    }
+
+                    match (self.stage, is_out) {
+                        (ShaderStage::Vertex, false) => output.push_str("attribute"),
+                        (ShaderStage::Vertex, true) => output.push_str("varying"),
+                        (ShaderStage::Fragment, false) => output.push_str("varying"),
+                        (ShaderStage::Fragment, true) => {
+                            let (end, name) = try!(self.parse_output_declaration(index + 1));
+
+                            let replacement = if self.target.0 == Api::GlEs {
+                                if num_fragment_outputs >= 1 {
+                                    return Err(error("multiple fragment shader outputs are not \
+                                                      supported by GLSL ES 1.00"));
+                                }
+                                "gl_FragColor".to_string()
+                            } else {
+                                let location = pending_location.unwrap_or(num_fragment_outputs);
+                                format!("gl_FragData[{}]", location)
+                            };
+
+                            num_fragment_outputs += 1;
+                            self.outputs.insert(name, replacement);
+                            pending_location = None;
+
+                            // also removing the line break that follows the declaration
+                            index = match self.tokens.get(end) {
+                                Some(t) if t.is_blank() && !t.as_str().starts_with("/") => end + 1,
+                                _ => end,
+                            };
+                            continue;
+                        },
+                        _ => unreachable!()
+                    }
+                },
+
+                Token::Ident("smooth") if global && self.legacy => {
+                    index = self.next_non_blank(index + 1);
+                    continue;
+                },
+
+                Token::Ident("flat") | Token::Ident("noperspective") if global && self.legacy => {
+                    return Err(error(&format!("the `{}` qualifier is not supported by {}",
+                                              token.as_str(), describe(&self.target))));
+                },
+
+                Token::Ident("uniform") if global => {
+                    let name = self.next_non_blank(index + 1);
+                    let brace = self.next_non_blank(name + 1);
+
+                    if self.tokens.get(brace) == Some(&Token::Punct("{")) &&
+                       !(self.target >= Version(Api::Gl, 1, 4) ||
+                         self.target >= Version(Api::GlEs, 3, 0))
+                    {
+                        return Err(error(&format!("uniform blocks are not supported by {}",
+                                                  describe(&self.target))));
+                    }
+
+                    output.push_str("uniform");
+                },
+
+                Token::Ident("texture") if self.legacy => {
+                    let function = try!(self.legacy_texture_function(index + 1));
+                    output.push_str(function);
+                },
+
+                Token::Ident(name) if self.legacy && is_unsupported_in_legacy(name) => {
+                    return Err(error(&format!("`{}` is not supported by {}", name,
+                                              describe(&self.target))));
+                },
+
+                Token::Ident(name) if self.outputs.contains_key(name) => {
+                    // an identifier that follows a type is the declaration of a local variable
+                    // or of a parameter, which hides the output
+                    let declaration = match prev {
+                        Some(Token::Ident(prev)) => prev != "return",
+                        _ => false,
+                    };
+
+                    if declaration && !in_member_list {
+                        let depth = if brace_depth == 0 { 1 } else { brace_depth };
+                        shadowed.push((name, depth));
+                    }
+
+                    let is_output = !in_member_list && !declaration &&
+                                    prev != Some(Token::Punct(".")) &&
+                                    !shadowed.iter().any(|&(n, _)| n == name);
+
+                    if is_output {
+                        output.push_str(&self.outputs[name]);
+                    } else {
+                        output.push_str(name);
+                    }
+                },
+
+                t => output.push_str(t.as_str()),
+            }
+
+            index += 1;
+        }
+
+        Ok(output)
+    }
+
+    /// Parses the content of a `layout(...)` qualifier whose opening parenthesis is at or after
+    /// `index`. Returns the index after the closing parenthesis, and the list of entries.
+    fn parse_layout(&self, index: usize)
+                    -> Result<(usize, Vec<(&'a str, Option<u32>)>), ProgramCreationError>
+    {
+        let mut index = self.next_non_blank(index);
+        if self.tokens.get(index) != Some(&Token::Punct("(")) {
+            return Err(error("expected `(` after `layout`"));
+        }
+
+        let mut entries = Vec::new();
+        let mut current: Option<&'a str> = None;
+        let mut value: Option<u32> = None;
+
+        index += 1;
+        loop {
+            match self.tokens.get(index) {
+                None => return Err(error("unterminated `layout` qualifier")),
+                Some(&Token::Punct(")")) => break,
+                Some(&Token::Punct(",")) => {
+                    if let Some(name) = current.take() {
+                        entries.push((name, value.take()));
+                    }
+                },
+                Some(&Token::Ident(name)) => current = Some(name),
+                Some(&Token::Other(num)) => {
+                    if let Ok(num) = num.parse() {
+                        value = Some(num);
+                    }
+                },
+                _ => ()
+            }
+
+            index += 1;
+        }
+
+        if let Some(name) = current.take() {
+            entries.push((name, value.take()));
+        }
+
+        Ok((index + 1, entries))
+    }
+
+    /// Parses the declaration of a fragment shader output, starting after the `out` keyword.
+    ///
+    /// Returns the index after the `;` and the name of the output.
+    fn parse_output_declaration(&self, mut index: usize)
+                                -> Result<(usize, &'a str), ProgramCreationError>
+    {
+        let mut name = None;
+
+        loop {
+            match self.tokens.get(index) {
+                None => return Err(error("unterminated fragment shader output declaration")),
+                Some(&Token::Punct(";")) => break,
+                Some(&Token::Punct("[")) => {
+                    return Err(error(&format!("arrays of fragment shader outputs are not \
+                                               supported by {}", describe(&self.target))));
+                },
+                Some(&Token::Punct(",")) => {
+                    return Err(error("declaring multiple fragment shader outputs in a single \
+                                      statement is not supported"));
+                },
+                Some(&Token::Ident(n)) => name = Some(n),
+                _ => ()
+            }
+
+            index += 1;
+        }
+
+        match name {
+            Some(name) => Ok((index + 1, name)),
+            None => Err(error("invalid fragment shader output declaration")),
+        }
+    }
+
+    /// Returns the name of the legacy function that corresponds to `texture()`, given the
+    /// index of the token following `texture`.
+    fn legacy_texture_function(&self, index: usize) -> Result<&'static str, ProgramCreationError> {
+        let paren = self.next_non_blank(index);
+        if self.tokens.get(paren) != Some(&Token::Punct("(")) {
+            return Err(error(&format!("`texture` can't be used as an identifier with {}",
+                                      describe(&self.target))));
+        }
+
+        let sampler_ty = match self.tokens.get(self.next_non_blank(paren + 1)) {
+            Some(&Token::Ident(name)) => self.samplers.get(name).map(|s| *s),
+            _ => None,
+        };
+
+        let es = self.target.0 == Api::GlEs;
+
+        match sampler_ty.unwrap_or("sampler2D") {
+            "sampler2D" => Ok("texture2D"),
+            "samplerCube" => Ok("textureCube"),
+            "sampler1D" if !es => Ok("texture1D"),
+            "sampler3D" if !es => Ok("texture3D"),
+            "sampler2DShadow" if !es => Ok("shadow2D"),
+            ty => Err(error(&format!("sampling a `{}` is not supported by {}", ty,
+                                     describe(&self.target)))),
+        }
+    }
+}
+
+/// Returns true if the identifier is a type or function that doesn't exist in GLSL 1.10,
+/// GLSL 1.20 and GLSL ES 1.00.
+fn is_unsupported_in_legacy(name: &str) -> bool {
+    match name {
+        "uint" | "uvec2" | "uvec3" | "uvec4" => true,
+        "texelFetch" | "texelFetchOffset" | "textureSize" | "textureLod" | "textureOffset" |
+        "textureProj" | "textureProjLod" | "textureGrad" | "textureLodOffset" => true,
+        "switch" => true,
+        _ => name.starts_with("isampler") || name.starts_with("usampler")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{translate_shader, ShaderStage};
+    use version::{Api, Version};
+
+    #[test]
+    fn unchanged_when_supported() {
+        let source = "#version 330 core\nin vec2 position;\nvoid main() {}\n";
+        let result = translate_shader(source, ShaderStage::Vertex,
+                                      &Version(Api::Gl, 4, 5)).unwrap();
+        assert_eq!(result, source);
+    }
+
+    #[test]
+    fn vertex_to_glsl_110() {
+        let source = "#version 330 core\n\
+                      layout(location = 0) in vec2 position;\n\
+                      out vec2 v_tex_coords;\n\
+                      float f(in float x) { return x; }\n\
+                      void main() { v_tex_coords = position; gl_Position = vec4(position, 0.0, 1.0); }\n";
+
+        let result = translate_shader(source, ShaderStage::Vertex,
+                                      &Version(Api::Gl, 1, 1)).unwrap();
+
+        assert_eq!(result, "#version 110\n\n\
+                            attribute vec2 position;\n\
+                            varying vec2 v_tex_coords;\n\
+                            float f(in float x) { return x; }\n\
+                            void main() { v_tex_coords = position; gl_Position = vec4(position, 0.0, 1.0); }\n");
+    }
+
+    #[test]
+    fn fragment_to_glsl_es_100() {
+        let source = "#version 330 core\n\
+                      uniform sampler2D tex;\n\
+                      uniform samplerCube cube;\n\
+                      in vec2 v_tex_coords;\n\
+                      out vec4 color;\n\
+                      void main() { color = texture(tex, v_tex_coords) + texture(cube, vec3(0.0)); }\n";
+
+        let result = translate_shader(source, ShaderStage::Fragment,
+                                      &Version(Api::GlEs, 1, 0)).unwrap();
+
+        assert_eq!(result, "#version 100\nprecision mediump float;\n\n\
+                            uniform sampler2D tex;\n\
+                            uniform samplerCube cube;\n\
+                            varying vec2 v_tex_coords;\n\
+                            void main() { gl_FragColor = texture2D(tex, v_tex_coords) + textureCube(cube, vec3(0.0)); }\n");
+    }
+
+    #[test]
+    fn multiple_outputs_to_glsl_120() {
+        let source = "#version 330\n\
+                      layout(location = 1) out vec4 normal;\n\
+                      layout(location = 0) out vec4 color;\n\
+                      void main() { color = vec4(1.0); normal = vec4(0.0); }\n";
+
+        let result = translate_shader(source, ShaderStage::Fragment,
+                                      &Version(Api::Gl, 1, 2)).unwrap();
+
+        assert_eq!(result, "#version 120\n\n\
+                            void main() { gl_FragData[0] = vec4(1.0); gl_FragData[1] = vec4(0.0); }\n");
+    }
+
+    #[test]
+    fn layout_removed_for_glsl_130() {
+        let source = "#version 330\nlayout(location = 0) in vec2 position;\nvoid main() {}\n";
+        let result = translate_shader(source, ShaderStage::Vertex,
+                                      &Version(Api::Gl, 1, 3)).unwrap();
+        assert_eq!(result, "#version 130\n\nin vec2 position;\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn glsl_es_300() {
+        let source = "#version 330 core\nlayout(location = 0) out vec4 color;\nvoid main() {}\n";
+        let result = translate_shader(source, ShaderStage::Fragment,
+                                      &Version(Api::GlEs, 3, 0)).unwrap();
+        assert_eq!(result, "#version 300 es\nprecision mediump float;\n\n\
+                            layout(location = 0) out vec4 color;\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn precision_after_extensions() {
+        let source = "#version 330\n\
+                      #extension GL_OES_standard_derivatives : enable\n\
+                      out vec4 color;\n\
+                      void main() { color = vec4(dFdx(1.0)); }\n";
+
+        let result = translate_shader(source, ShaderStage::Fragment,
+                                      &Version(Api::GlEs, 1, 0)).unwrap();
+
+        assert_eq!(result, "#version 100\n\n\
+                            #extension GL_OES_standard_derivatives : enable\n\
+                            precision mediump float;\n\n\
+                            void main() { gl_FragColor = vec4(dFdx(1.0)); }\n");
+    }
+
+    #[test]
+    fn output_name_reused() {
+        let source = "#version 330\n\
+                      struct Light { vec4 color; };\n\
+                      uniform Light light;\n\
+                      out vec4 color;\n\
+                      vec4 f(vec4 color) { return color; }\n\
+                      void main() { { vec4 color = light.color; } color = f(light.color); }\n";
+
+        let result = translate_shader(source, ShaderStage::Fragment,
+                                      &Version(Api::Gl, 1, 1)).unwrap();
+
+        assert_eq!(result, "#version 110\n\n\
+                            struct Light { vec4 color; };\n\
+                            uniform Light light;\n\
+                            vec4 f(vec4 color) { return color; }\n\
+                            void main() { { vec4 color = light.color; } \
+                            gl_FragData[0] = f(light.color); }\n");
+    }
+
+    #[test]
+    fn unsupported_constructs() {
+        let source = "#version 330\nflat in int id;\nvoid main() {}\n";
+        assert!(translate_shader(source, ShaderStage::Fragment,
+                                 &Version(Api::Gl, 1, 1)).is_err());
+
+        let source = "#version 330\nuniform usampler2D tex;\nvoid main() {}\n";
+        assert!(translate_shader(source, ShaderStage::Fragment,
+                                 &Version(Api::GlEs, 1, 0)).is_err());
+
+        let source = "#version 330\nvoid main() {}\n";
+        assert!(translate_shader(source, ShaderStage::Geometry,
+                                 &Version(Api::Gl, 1, 2)).is_err());
+
+        let source = "void main() {}\n";
+        assert!(translate_shader(source, ShaderStage::Vertex,
+                                 &Version(Api::Gl, 1, 2)).is_err());
+    }
+}
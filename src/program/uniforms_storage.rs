@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use RawUniformValue;
+use RawUniformArray;
 
 use smallvec::SmallVec;
 
@@ -56,6 +57,26 @@ impl UniformsStorage {
             )
         );
 
+        macro_rules! uniform_non_square(
+            ($ctxt:expr, $uniform:ident, $($params:expr),+) => (
+                unsafe {
+                    assert!($ctxt.version >= &Version(Api::Gl, 2, 1) ||
+                            $ctxt.version >= &Version(Api::GlEs, 3, 0));
+                    $ctxt.gl.$uniform($($params),+)
+                }
+            )
+        );
+
+        macro_rules! uniform_double(
+            ($ctxt:expr, $uniform:ident, $($params:expr),+) => (
+                unsafe {
+                    assert!($ctxt.version >= &Version(Api::Gl, 4, 0) ||
+                            $ctxt.extensions.gl_arb_gpu_shader_fp64);
+                    $ctxt.gl.$uniform($($params),+)
+                }
+            )
+        );
+
         match (value, &mut values[location as usize]) {
             (&RawUniformValue::SignedInt(a), &mut Some(RawUniformValue::SignedInt(b))) if a == b => (),
             (&RawUniformValue::UnsignedInt(a), &mut Some(RawUniformValue::UnsignedInt(b))) if a == b => (),
@@ -66,6 +87,31 @@ impl UniformsStorage {
             (&RawUniformValue::Vec2(a), &mut Some(RawUniformValue::Vec2(b))) if a == b => (),
             (&RawUniformValue::Vec3(a), &mut Some(RawUniformValue::Vec3(b))) if a == b => (),
             (&RawUniformValue::Vec4(a), &mut Some(RawUniformValue::Vec4(b))) if a == b => (),
+            (&RawUniformValue::IntVec2(a), &mut Some(RawUniformValue::IntVec2(b))) if a == b => (),
+            (&RawUniformValue::IntVec3(a), &mut Some(RawUniformValue::IntVec3(b))) if a == b => (),
+            (&RawUniformValue::IntVec4(a), &mut Some(RawUniformValue::IntVec4(b))) if a == b => (),
+            (&RawUniformValue::UnsignedIntVec2(a), &mut Some(RawUniformValue::UnsignedIntVec2(b))) if a == b => (),
+            (&RawUniformValue::UnsignedIntVec3(a), &mut Some(RawUniformValue::UnsignedIntVec3(b))) if a == b => (),
+            (&RawUniformValue::UnsignedIntVec4(a), &mut Some(RawUniformValue::UnsignedIntVec4(b))) if a == b => (),
+            (&RawUniformValue::Mat2x3(a), &mut Some(RawUniformValue::Mat2x3(b))) if a == b => (),
+            (&RawUniformValue::Mat2x4(a), &mut Some(RawUniformValue::Mat2x4(b))) if a == b => (),
+            (&RawUniformValue::Mat3x2(a), &mut Some(RawUniformValue::Mat3x2(b))) if a == b => (),
+            (&RawUniformValue::Mat3x4(a), &mut Some(RawUniformValue::Mat3x4(b))) if a == b => (),
+            (&RawUniformValue::Mat4x2(a), &mut Some(RawUniformValue::Mat4x2(b))) if a == b => (),
+            (&RawUniformValue::Mat4x3(a), &mut Some(RawUniformValue::Mat4x3(b))) if a == b => (),
+            (&RawUniformValue::Double(a), &mut Some(RawUniformValue::Double(b))) if a == b => (),
+            (&RawUniformValue::DoubleVec2(a), &mut Some(RawUniformValue::DoubleVec2(b))) if a == b => (),
+            (&RawUniformValue::DoubleVec3(a), &mut Some(RawUniformValue::DoubleVec3(b))) if a == b => (),
+            (&RawUniformValue::DoubleVec4(a), &mut Some(RawUniformValue::DoubleVec4(b))) if a == b => (),
+            (&RawUniformValue::DoubleMat2(a), &mut Some(RawUniformValue::DoubleMat2(b))) if a == b => (),
+            (&RawUniformValue::DoubleMat3(a), &mut Some(RawUniformValue::DoubleMat3(b))) if a == b => (),
+            (&RawUniformValue::DoubleMat4(a), &mut Some(RawUniformValue::DoubleMat4(b))) if a == b => (),
+            (&RawUniformValue::DoubleMat2x3(a), &mut Some(RawUniformValue::DoubleMat2x3(b))) if a == b => (),
+            (&RawUniformValue::DoubleMat2x4(a), &mut Some(RawUniformValue::DoubleMat2x4(b))) if a == b => (),
+            (&RawUniformValue::DoubleMat3x2(a), &mut Some(RawUniformValue::DoubleMat3x2(b))) if a == b => (),
+            (&RawUniformValue::DoubleMat3x4(a), &mut Some(RawUniformValue::DoubleMat3x4(b))) if a == b => (),
+            (&RawUniformValue::DoubleMat4x2(a), &mut Some(RawUniformValue::DoubleMat4x2(b))) if a == b => (),
+            (&RawUniformValue::DoubleMat4x3(a), &mut Some(RawUniformValue::DoubleMat4x3(b))) if a == b => (),
 
             (&RawUniformValue::SignedInt(v), target) => {
                 *target = Some(RawUniformValue::SignedInt(v));
@@ -125,6 +171,294 @@ impl UniformsStorage {
                 *target = Some(RawUniformValue::Vec4(v));
                 uniform!(ctxt, Uniform4fv, Uniform4fvARB, location, 1, v.as_ptr() as *const f32);
             },
+
+            (&RawUniformValue::IntVec2(v), target) => {
+                *target = Some(RawUniformValue::IntVec2(v));
+                uniform!(ctxt, Uniform2iv, Uniform2ivARB, location, 1, v.as_ptr());
+            },
+
+            (&RawUniformValue::IntVec3(v), target) => {
+                *target = Some(RawUniformValue::IntVec3(v));
+                uniform!(ctxt, Uniform3iv, Uniform3ivARB, location, 1, v.as_ptr());
+            },
+
+            (&RawUniformValue::IntVec4(v), target) => {
+                *target = Some(RawUniformValue::IntVec4(v));
+                uniform!(ctxt, Uniform4iv, Uniform4ivARB, location, 1, v.as_ptr());
+            },
+
+            (&RawUniformValue::UnsignedIntVec2(v), target) => {
+                *target = Some(RawUniformValue::UnsignedIntVec2(v));
+                // Uniform2uivARB doesn't exist
+                unsafe {
+                    if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                       ctxt.version >= &Version(Api::GlEs, 3, 0)
+                    {
+                        ctxt.gl.Uniform2uiv(location, 1, v.as_ptr())
+                    } else {
+                        assert!(ctxt.extensions.gl_arb_shader_objects);
+                        ctxt.gl.Uniform2ivARB(location, 1, v.as_ptr() as *const gl::types::GLint)
+                    }
+                }
+            },
+
+            (&RawUniformValue::UnsignedIntVec3(v), target) => {
+                *target = Some(RawUniformValue::UnsignedIntVec3(v));
+                // Uniform3uivARB doesn't exist
+                unsafe {
+                    if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                       ctxt.version >= &Version(Api::GlEs, 3, 0)
+                    {
+                        ctxt.gl.Uniform3uiv(location, 1, v.as_ptr())
+                    } else {
+                        assert!(ctxt.extensions.gl_arb_shader_objects);
+                        ctxt.gl.Uniform3ivARB(location, 1, v.as_ptr() as *const gl::types::GLint)
+                    }
+                }
+            },
+
+            (&RawUniformValue::UnsignedIntVec4(v), target) => {
+                *target = Some(RawUniformValue::UnsignedIntVec4(v));
+                // Uniform4uivARB doesn't exist
+                unsafe {
+                    if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                       ctxt.version >= &Version(Api::GlEs, 3, 0)
+                    {
+                        ctxt.gl.Uniform4uiv(location, 1, v.as_ptr())
+                    } else {
+                        assert!(ctxt.extensions.gl_arb_shader_objects);
+                        ctxt.gl.Uniform4ivARB(location, 1, v.as_ptr() as *const gl::types::GLint)
+                    }
+                }
+            },
+
+            (&RawUniformValue::Mat2x3(v), target) => {
+                *target = Some(RawUniformValue::Mat2x3(v));
+                uniform_non_square!(ctxt, UniformMatrix2x3fv, location, 1, gl::FALSE,
+                                    v.as_ptr() as *const f32);
+            },
+
+            (&RawUniformValue::Mat2x4(v), target) => {
+                *target = Some(RawUniformValue::Mat2x4(v));
+                uniform_non_square!(ctxt, UniformMatrix2x4fv, location, 1, gl::FALSE,
+                                    v.as_ptr() as *const f32);
+            },
+
+            (&RawUniformValue::Mat3x2(v), target) => {
+                *target = Some(RawUniformValue::Mat3x2(v));
+                uniform_non_square!(ctxt, UniformMatrix3x2fv, location, 1, gl::FALSE,
+                                    v.as_ptr() as *const f32);
+            },
+
+            (&RawUniformValue::Mat3x4(v), target) => {
+                *target = Some(RawUniformValue::Mat3x4(v));
+                uniform_non_square!(ctxt, UniformMatrix3x4fv, location, 1, gl::FALSE,
+                                    v.as_ptr() as *const f32);
+            },
+
+            (&RawUniformValue::Mat4x2(v), target) => {
+                *target = Some(RawUniformValue::Mat4x2(v));
+                uniform_non_square!(ctxt, UniformMatrix4x2fv, location, 1, gl::FALSE,
+                                    v.as_ptr() as *const f32);
+            },
+
+            (&RawUniformValue::Mat4x3(v), target) => {
+                *target = Some(RawUniformValue::Mat4x3(v));
+                uniform_non_square!(ctxt, UniformMatrix4x3fv, location, 1, gl::FALSE,
+                                    v.as_ptr() as *const f32);
+            },
+
+            (&RawUniformValue::Double(v), target) => {
+                *target = Some(RawUniformValue::Double(v));
+                uniform_double!(ctxt, Uniform1d, location, v);
+            },
+
+            (&RawUniformValue::DoubleVec2(v), target) => {
+                *target = Some(RawUniformValue::DoubleVec2(v));
+                uniform_double!(ctxt, Uniform2dv, location, 1, v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleVec3(v), target) => {
+                *target = Some(RawUniformValue::DoubleVec3(v));
+                uniform_double!(ctxt, Uniform3dv, location, 1, v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleVec4(v), target) => {
+                *target = Some(RawUniformValue::DoubleVec4(v));
+                uniform_double!(ctxt, Uniform4dv, location, 1, v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleMat2(v), target) => {
+                *target = Some(RawUniformValue::DoubleMat2(v));
+                uniform_double!(ctxt, UniformMatrix2dv, location, 1, gl::FALSE,
+                                v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleMat3(v), target) => {
+                *target = Some(RawUniformValue::DoubleMat3(v));
+                uniform_double!(ctxt, UniformMatrix3dv, location, 1, gl::FALSE,
+                                v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleMat4(v), target) => {
+                *target = Some(RawUniformValue::DoubleMat4(v));
+                uniform_double!(ctxt, UniformMatrix4dv, location, 1, gl::FALSE,
+                                v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleMat2x3(v), target) => {
+                *target = Some(RawUniformValue::DoubleMat2x3(v));
+                uniform_double!(ctxt, UniformMatrix2x3dv, location, 1, gl::FALSE,
+                                v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleMat2x4(v), target) => {
+                *target = Some(RawUniformValue::DoubleMat2x4(v));
+                uniform_double!(ctxt, UniformMatrix2x4dv, location, 1, gl::FALSE,
+                                v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleMat3x2(v), target) => {
+                *target = Some(RawUniformValue::DoubleMat3x2(v));
+                uniform_double!(ctxt, UniformMatrix3x2dv, location, 1, gl::FALSE,
+                                v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleMat3x4(v), target) => {
+                *target = Some(RawUniformValue::DoubleMat3x4(v));
+                uniform_double!(ctxt, UniformMatrix3x4dv, location, 1, gl::FALSE,
+                                v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleMat4x2(v), target) => {
+                *target = Some(RawUniformValue::DoubleMat4x2(v));
+                uniform_double!(ctxt, UniformMatrix4x2dv, location, 1, gl::FALSE,
+                                v.as_ptr() as *const f64);
+            },
+
+            (&RawUniformValue::DoubleMat4x3(v), target) => {
+                *target = Some(RawUniformValue::DoubleMat4x3(v));
+                uniform_double!(ctxt, UniformMatrix4x3dv, location, 1, gl::FALSE,
+                                v.as_ptr() as *const f64);
+            },
+        }
+    }
+
+    /// Calls `glUniform*v` with all the elements of an array uniform.
+    ///
+    /// Arrays are not cached, so this always calls `glUniform*v`.
+    pub fn set_uniform_array_value(&self, ctxt: &mut CommandContext, program: Handle,
+                                   location: gl::types::GLint, value: &RawUniformArray)
+    {
+        // the value of the first element is no longer known
+        if let Some(value) = self.values.borrow_mut().get_mut(location as usize) {
+            *value = None;
+        }
+
+        // TODO: don't assume that, instead use DSA if the program is not current
+        assert!(ctxt.state.program == program);
+
+        macro_rules! uniform(
+            ($ctxt:expr, $uniform:ident, $uniform_arb:ident, $($params:expr),+) => (
+                unsafe {
+                    if $ctxt.version >= &Version(Api::Gl, 1, 5) ||
+                       $ctxt.version >= &Version(Api::GlEs, 2, 0)
+                    {
+                        $ctxt.gl.$uniform($($params),+)
+                    } else {
+                        assert!($ctxt.extensions.gl_arb_shader_objects);
+                        $ctxt.gl.$uniform_arb($($params),+)
+                    }
+                }
+            )
+        );
+
+        // the caller must check that `glUniform*uiv` is supported
+        macro_rules! uniform_unsigned(
+            ($ctxt:expr, $uniform:ident, $($params:expr),+) => (
+                unsafe {
+                    debug_assert!($ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                                  $ctxt.version >= &Version(Api::GlEs, 3, 0));
+                    $ctxt.gl.$uniform($($params),+)
+                }
+            )
+        );
+
+        match value {
+            &RawUniformArray::SignedInt(v) => {
+                uniform!(ctxt, Uniform1iv, Uniform1ivARB, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr());
+            },
+
+            &RawUniformArray::UnsignedInt(v) => {
+                uniform_unsigned!(ctxt, Uniform1uiv, location, v.len() as gl::types::GLsizei,
+                                  v.as_ptr());
+            },
+
+            &RawUniformArray::Float(v) => {
+                uniform!(ctxt, Uniform1fv, Uniform1fvARB, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr());
+            },
+
+            &RawUniformArray::Mat2(v) => {
+                uniform!(ctxt, UniformMatrix2fv, UniformMatrix2fvARB, location,
+                         v.len() as gl::types::GLsizei, gl::FALSE, v.as_ptr() as *const f32);
+            },
+
+            &RawUniformArray::Mat3(v) => {
+                uniform!(ctxt, UniformMatrix3fv, UniformMatrix3fvARB, location,
+                         v.len() as gl::types::GLsizei, gl::FALSE, v.as_ptr() as *const f32);
+            },
+
+            &RawUniformArray::Mat4(v) => {
+                uniform!(ctxt, UniformMatrix4fv, UniformMatrix4fvARB, location,
+                         v.len() as gl::types::GLsizei, gl::FALSE, v.as_ptr() as *const f32);
+            },
+
+            &RawUniformArray::Vec2(v) => {
+                uniform!(ctxt, Uniform2fv, Uniform2fvARB, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr() as *const f32);
+            },
+
+            &RawUniformArray::Vec3(v) => {
+                uniform!(ctxt, Uniform3fv, Uniform3fvARB, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr() as *const f32);
+            },
+
+            &RawUniformArray::Vec4(v) => {
+                uniform!(ctxt, Uniform4fv, Uniform4fvARB, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr() as *const f32);
+            },
+
+            &RawUniformArray::IntVec2(v) => {
+                uniform!(ctxt, Uniform2iv, Uniform2ivARB, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr() as *const gl::types::GLint);
+            },
+
+            &RawUniformArray::IntVec3(v) => {
+                uniform!(ctxt, Uniform3iv, Uniform3ivARB, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr() as *const gl::types::GLint);
+            },
+
+            &RawUniformArray::IntVec4(v) => {
+                uniform!(ctxt, Uniform4iv, Uniform4ivARB, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr() as *const gl::types::GLint);
+            },
+
+            &RawUniformArray::UnsignedIntVec2(v) => {
+                uniform_unsigned!(ctxt, Uniform2uiv, location, v.len() as gl::types::GLsizei,
+                                  v.as_ptr() as *const gl::types::GLuint);
+            },
+
+            &RawUniformArray::UnsignedIntVec3(v) => {
+                uniform_unsigned!(ctxt, Uniform3uiv, location, v.len() as gl::types::GLsizei,
+                                  v.as_ptr() as *const gl::types::GLuint);
+            },
+
+            &RawUniformArray::UnsignedIntVec4(v) => {
+                uniform_unsigned!(ctxt, Uniform4uiv, location, v.len() as gl::types::GLsizei,
+                                  v.as_ptr() as *const gl::types::GLuint);
+            },
         }
     }
 
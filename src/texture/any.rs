@@ -438,6 +438,41 @@ impl<'a> TextureAnyMipmap<'a> {
     pub fn get_layer(&self) -> u32 {
         self.layer
     }
+
+    /// Tells the backend that the content of this mipmap is no longer needed.
+    ///
+    /// After this call, the content of the mipmap is undefined. This is only a hint and may do
+    /// nothing. If `glInvalidateTexImage` is not supported, the mipmap is invalidated through
+    /// a framebuffer object that already contains it, if any.
+    pub fn invalidate(&self) {
+        let mut ctxt = self.texture.context.make_current();
+
+        if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.extensions.gl_arb_invalidate_subdata {
+            let id = self.texture.id;
+            let level = self.level as gl::types::GLint;
+            let width = self.width as gl::types::GLsizei;
+            let height = self.height.unwrap_or(1) as gl::types::GLsizei;
+            let layer = self.layer as gl::types::GLint;
+
+            unsafe {
+                match self.texture.ty {
+                    TextureType::Texture1dArray => {
+                        ctxt.gl.InvalidateTexSubImage(id, level, 0, layer, 0, width, 1, 1);
+                    },
+                    TextureType::Texture2dArray | TextureType::Texture2dMultisampleArray => {
+                        ctxt.gl.InvalidateTexSubImage(id, level, 0, 0, layer, width, height, 1);
+                    },
+                    _ => {
+                        ctxt.gl.InvalidateTexImage(id, level);
+                    },
+                }
+            }
+
+        } else {
+            fbo::FramebuffersContainer::invalidate_texture(&mut ctxt, self.texture.id,
+                                                           self.layer, self.level);
+        }
+    }
 }
 
 /// Changes some parts of the texture.
@@ -533,6 +533,10 @@ impl<'a> Surface for TextureSurface<'a> {
         self.0.clear(rect, color, depth, stencil)
     }
 
+    fn invalidate(&mut self, rect: Option<&Rect>, color: bool, depth: bool, stencil: bool) {
+        self.0.invalidate(rect, color, depth, stencil)
+    }
+
     fn get_dimensions(&self) -> (u32, u32) {
         self.0.get_dimensions()
     }
@@ -0,0 +1,108 @@
+use buffer::{BufferView, BufferType, BufferMode, BufferCreationError};
+use uniforms::{AsUniformValue, UniformValue, UniformType};
+
+use std::ops::{Deref, DerefMut};
+
+use backend::Facade;
+
+/// Buffer that contains atomic counters.
+///
+/// Each element of the buffer is the value of an `atomic_uint`. Pass a reference to the buffer
+/// as the value of an atomic counter uniform, and the buffer is binded to the binding point of
+/// this counter. The binding points and offsets of the counters are chosen in the shader with
+/// `layout(binding = ..., offset = ...)`, and can be obtained with `Program::get_atomic_counters`.
+#[derive(Debug)]
+pub struct AtomicCounterBuffer {
+    buffer: BufferView<u32>,
+}
+
+impl AtomicCounterBuffer {
+    /// Builds a buffer containing the given counters.
+    ///
+    /// Returns `None` if atomic counters are not supported by the backend.
+    pub fn new_if_supported<F>(facade: &F, counters: &[u32]) -> Option<AtomicCounterBuffer>
+                               where F: Facade
+    {
+        let buffer = match BufferView::new(facade, counters, BufferType::AtomicCounterBuffer,
+                                           BufferMode::Default)
+        {
+            Ok(b) => b,
+            Err(BufferCreationError::BufferTypeNotSupported) => return None,
+            e @ Err(_) => e.unwrap(),
+        };
+
+        Some(AtomicCounterBuffer {
+            buffer: buffer,
+        })
+    }
+
+    /// Builds a buffer containing `len` counters set to zero.
+    ///
+    /// Returns `None` if atomic counters are not supported by the backend.
+    pub fn zeroed_if_supported<F>(facade: &F, len: usize) -> Option<AtomicCounterBuffer>
+                                  where F: Facade
+    {
+        let counters = ::std::iter::repeat(0).take(len).collect::<Vec<u32>>();
+        AtomicCounterBuffer::new_if_supported(facade, &counters)
+    }
+
+    /// Sets all the counters to zero.
+    pub fn reset(&self) {
+        self.reset_to(0);
+    }
+
+    /// Sets all the counters to `value`.
+    ///
+    /// The operation is done by the GPU if the backend supports it.
+    pub fn reset_to(&self, value: u32) {
+        self.buffer.clear_to(value);
+    }
+
+    /// Reads the value of the counter at the given index if supported.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `index` is out of range.
+    pub fn get_if_supported(&self, index: usize) -> Option<u32> {
+        let slice = self.buffer.slice(index .. index + 1).expect("Index out of range");
+        slice.read_if_supported().and_then(|buf| buf.into_iter().next())
+    }
+
+    /// Reads the value of the counter at the given index.
+    ///
+    /// # Features
+    ///
+    /// Only available if the 'gl_read_buffer' feature is enabled.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `index` is out of range.
+    #[cfg(feature = "gl_read_buffer")]
+    pub fn get(&self, index: usize) -> u32 {
+        self.get_if_supported(index).unwrap()
+    }
+}
+
+impl Deref for AtomicCounterBuffer {
+    type Target = BufferView<u32>;
+
+    fn deref(&self) -> &BufferView<u32> {
+        &self.buffer
+    }
+}
+
+impl DerefMut for AtomicCounterBuffer {
+    fn deref_mut(&mut self) -> &mut BufferView<u32> {
+        &mut self.buffer
+    }
+}
+
+impl<'a> AsUniformValue for &'a AtomicCounterBuffer {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::AtomicCounterBuffer(self.buffer.as_slice_any())
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        *ty == UniformType::AtomicCounterUint
+    }
+}
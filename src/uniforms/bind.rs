@@ -14,9 +14,11 @@ use DrawError;
 use ProgramExt;
 use UniformsExt;
 use RawUniformValue;
+use RawUniformArray;
 
 use uniforms::Uniforms;
 use uniforms::UniformValue;
+use uniforms::UniformType;
 use uniforms::SamplerBehavior;
 
 use context::CommandContext;
@@ -47,9 +49,20 @@ impl<U> UniformsExt for U where U: Uniforms {
         self.visit_values(|name, value| {
             if visiting_result.is_err() { return; }
 
-            if let Some(uniform) = program.get_uniform(name) {
-                assert!(uniform.size.is_none(), "Uniform arrays not supported yet");
+            if let Some(counter) = program.get_atomic_counters().get(name) {
+                let fence = match bind_atomic_counter_buffer(&mut ctxt, &value, counter, name) {
+                    Ok(f) => f,
+                    Err(e) => {
+                        visiting_result = Err(e);
+                        return;
+                    }
+                };
+
+                if let Some(fence) = fence {
+                    fences.push(fence);
+                }
 
+            } else if let Some(uniform) = program.get_uniform(name) {
                 if !value.is_usable_with(&uniform.ty) {
                     visiting_result = Err(DrawError::UniformTypeMismatch {
                         name: name.to_string(),
@@ -58,6 +71,18 @@ impl<U> UniformsExt for U where U: Uniforms {
                     return;
                 }
 
+                if let Some(len) = value.get_array_len() {
+                    let max_len = uniform.size.unwrap_or(1);
+                    if len > max_len {
+                        visiting_result = Err(DrawError::UniformArrayTooLong {
+                            name: name.to_string(),
+                            len: len,
+                            max_len: max_len,
+                        });
+                        return;
+                    }
+                }
+
                 match bind_uniform(&mut ctxt, &value, program, uniform.location,
                                    &mut texture_bind_points, name)
                 {
@@ -113,14 +138,16 @@ fn bind_uniform_block<'a, P>(ctxt: &mut context::CommandContext, value: &Uniform
 {
     match value {
         &UniformValue::Block(buffer, ref layout) => {
-            if !layout(block) {
-                return Err(DrawError::UniformBlockLayoutMismatch { name: name.to_string() });
+            if let Err(err) = layout(block) {
+                return Err(DrawError::UniformBlockLayoutMismatch {
+                    name: name.to_string(),
+                    err: err,
+                });
             }
 
             let bind_point = buffer_bind_points.get_unused().expect("Not enough buffer units");
             buffer_bind_points.set_used(bind_point);
 
-            assert!(buffer.get_offset_bytes() == 0);     // TODO: not implemented
             let fence = buffer.add_fence();
             let binding = block.binding as gl::types::GLuint;
 
@@ -143,14 +170,16 @@ fn bind_shared_storage_block<'a, P>(ctxt: &mut context::CommandContext, value: &
 {
     match value {
         &UniformValue::Block(buffer, ref layout) => {
-            if !layout(block) {
-                return Err(DrawError::UniformBlockLayoutMismatch { name: name.to_string() });
+            if let Err(err) = layout(block) {
+                return Err(DrawError::UniformBlockLayoutMismatch {
+                    name: name.to_string(),
+                    err: err,
+                });
             }
 
             let bind_point = buffer_bind_points.get_unused().expect("Not enough buffer units");
             buffer_bind_points.set_used(bind_point);
 
-            assert!(buffer.get_offset_bytes() == 0);     // TODO: not implemented
             let fence = buffer.add_fence();
             let binding = block.binding as gl::types::GLuint;
 
@@ -165,6 +194,33 @@ fn bind_shared_storage_block<'a, P>(ctxt: &mut context::CommandContext, value: &
     }
 }
 
+fn bind_atomic_counter_buffer<'a>(ctxt: &mut context::CommandContext, value: &UniformValue<'a>,
+                                  counter: &program::AtomicCounter, name: &str)
+                                  -> Result<Option<&'a RefCell<Option<sync::LinearSyncFence>>>, DrawError>
+{
+    match value {
+        &UniformValue::AtomicCounterBuffer(buffer) => {
+            if buffer.get_size() < counter.buffer_size {
+                return Err(DrawError::AtomicCounterBufferTooSmall {
+                    name: name.to_string(),
+                    size: buffer.get_size(),
+                    required: counter.buffer_size,
+                });
+            }
+
+            let fence = buffer.add_fence();
+            buffer.prepare_and_bind_for_atomic_counter(ctxt, counter.binding as gl::types::GLuint);
+            Ok(fence)
+        },
+        _ => {
+            Err(DrawError::UniformTypeMismatch {
+                name: name.to_string(),
+                expected: UniformType::AtomicCounterUint,
+            })
+        }
+    }
+}
+
 fn bind_uniform<P>(ctxt: &mut context::CommandContext,
                    value: &UniformValue, program: &P, location: gl::types::GLint,
                    texture_bind_points: &mut Bitsfield, name: &str)
@@ -173,7 +229,7 @@ fn bind_uniform<P>(ctxt: &mut context::CommandContext,
     assert!(location >= 0);
 
     match *value {
-        UniformValue::Block(_, _) => {
+        UniformValue::Block(_, _) | UniformValue::AtomicCounterBuffer(_) => {
             Err(DrawError::UniformBufferToValue {
                 name: name.to_string(),
             })
@@ -214,6 +270,203 @@ fn bind_uniform<P>(ctxt: &mut context::CommandContext,
             program.set_uniform(ctxt, location, &RawUniformValue::Vec4(val));
             Ok(())
         },
+        UniformValue::Bool(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::SignedInt(val as gl::types::GLint));
+            Ok(())
+        },
+        UniformValue::BoolVec2(val) => {
+            let val = [val[0] as gl::types::GLint, val[1] as gl::types::GLint];
+            program.set_uniform(ctxt, location, &RawUniformValue::IntVec2(val));
+            Ok(())
+        },
+        UniformValue::BoolVec3(val) => {
+            let val = [val[0] as gl::types::GLint, val[1] as gl::types::GLint,
+                       val[2] as gl::types::GLint];
+            program.set_uniform(ctxt, location, &RawUniformValue::IntVec3(val));
+            Ok(())
+        },
+        UniformValue::BoolVec4(val) => {
+            let val = [val[0] as gl::types::GLint, val[1] as gl::types::GLint,
+                       val[2] as gl::types::GLint, val[3] as gl::types::GLint];
+            program.set_uniform(ctxt, location, &RawUniformValue::IntVec4(val));
+            Ok(())
+        },
+        UniformValue::IntVec2(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::IntVec2(val));
+            Ok(())
+        },
+        UniformValue::IntVec3(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::IntVec3(val));
+            Ok(())
+        },
+        UniformValue::IntVec4(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::IntVec4(val));
+            Ok(())
+        },
+        UniformValue::UnsignedIntVec2(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::UnsignedIntVec2(val));
+            Ok(())
+        },
+        UniformValue::UnsignedIntVec3(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::UnsignedIntVec3(val));
+            Ok(())
+        },
+        UniformValue::UnsignedIntVec4(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::UnsignedIntVec4(val));
+            Ok(())
+        },
+        UniformValue::Mat2x3(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::Mat2x3(val));
+            Ok(())
+        },
+        UniformValue::Mat2x4(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::Mat2x4(val));
+            Ok(())
+        },
+        UniformValue::Mat3x2(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::Mat3x2(val));
+            Ok(())
+        },
+        UniformValue::Mat3x4(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::Mat3x4(val));
+            Ok(())
+        },
+        UniformValue::Mat4x2(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::Mat4x2(val));
+            Ok(())
+        },
+        UniformValue::Mat4x3(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::Mat4x3(val));
+            Ok(())
+        },
+        UniformValue::Double(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::Double(val));
+            Ok(())
+        },
+        UniformValue::DoubleVec2(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleVec2(val));
+            Ok(())
+        },
+        UniformValue::DoubleVec3(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleVec3(val));
+            Ok(())
+        },
+        UniformValue::DoubleVec4(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleVec4(val));
+            Ok(())
+        },
+        UniformValue::DoubleMat2(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleMat2(val));
+            Ok(())
+        },
+        UniformValue::DoubleMat3(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleMat3(val));
+            Ok(())
+        },
+        UniformValue::DoubleMat4(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleMat4(val));
+            Ok(())
+        },
+        UniformValue::DoubleMat2x3(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleMat2x3(val));
+            Ok(())
+        },
+        UniformValue::DoubleMat2x4(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleMat2x4(val));
+            Ok(())
+        },
+        UniformValue::DoubleMat3x2(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleMat3x2(val));
+            Ok(())
+        },
+        UniformValue::DoubleMat3x4(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleMat3x4(val));
+            Ok(())
+        },
+        UniformValue::DoubleMat4x2(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleMat4x2(val));
+            Ok(())
+        },
+        UniformValue::DoubleMat4x3(val) => {
+            program.set_uniform(ctxt, location, &RawUniformValue::DoubleMat4x3(val));
+            Ok(())
+        },
+        UniformValue::SignedIntArray(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::SignedInt(val));
+            Ok(())
+        },
+        UniformValue::UnsignedIntArray(val) => {
+            if !is_unsigned_int_array_supported(ctxt) {
+                return Err(DrawError::UniformTypeNotSupported { name: name.to_string() });
+            }
+
+            program.set_uniform_array(ctxt, location, &RawUniformArray::UnsignedInt(val));
+            Ok(())
+        },
+        UniformValue::FloatArray(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::Float(val));
+            Ok(())
+        },
+        UniformValue::Mat2Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::Mat2(val));
+            Ok(())
+        },
+        UniformValue::Mat3Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::Mat3(val));
+            Ok(())
+        },
+        UniformValue::Mat4Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::Mat4(val));
+            Ok(())
+        },
+        UniformValue::Vec2Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::Vec2(val));
+            Ok(())
+        },
+        UniformValue::Vec3Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::Vec3(val));
+            Ok(())
+        },
+        UniformValue::Vec4Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::Vec4(val));
+            Ok(())
+        },
+        UniformValue::IntVec2Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::IntVec2(val));
+            Ok(())
+        },
+        UniformValue::IntVec3Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::IntVec3(val));
+            Ok(())
+        },
+        UniformValue::IntVec4Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArray::IntVec4(val));
+            Ok(())
+        },
+        UniformValue::UnsignedIntVec2Array(val) => {
+            if !is_unsigned_int_array_supported(ctxt) {
+                return Err(DrawError::UniformTypeNotSupported { name: name.to_string() });
+            }
+
+            program.set_uniform_array(ctxt, location, &RawUniformArray::UnsignedIntVec2(val));
+            Ok(())
+        },
+        UniformValue::UnsignedIntVec3Array(val) => {
+            if !is_unsigned_int_array_supported(ctxt) {
+                return Err(DrawError::UniformTypeNotSupported { name: name.to_string() });
+            }
+
+            program.set_uniform_array(ctxt, location, &RawUniformArray::UnsignedIntVec3(val));
+            Ok(())
+        },
+        UniformValue::UnsignedIntVec4Array(val) => {
+            if !is_unsigned_int_array_supported(ctxt) {
+                return Err(DrawError::UniformTypeNotSupported { name: name.to_string() });
+            }
+
+            program.set_uniform_array(ctxt, location, &RawUniformArray::UnsignedIntVec4(val));
+            Ok(())
+        },
         UniformValue::Texture1d(texture, sampler) => {
             let texture = texture.get_id();
             bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_1D)
@@ -474,3 +727,8 @@ fn bind_texture_uniform<P>(mut ctxt: &mut context::CommandContext,
 
     Ok(())
 }
+
+/// Returns true if the backend supports `glUniform*uiv`.
+fn is_unsigned_int_array_supported(ctxt: &context::CommandContext) -> bool {
+    ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0)
+}
@@ -1,4 +1,4 @@
-use buffer::{BufferView, BufferViewAny, BufferType, BufferCreationError};
+use buffer::{BufferView, BufferViewAny, BufferType, BufferMode, BufferCreationError};
 use buffer::Mapping as BufferMapping;
 use uniforms::{AsUniformValue, UniformValue, UniformBlock, UniformType};
 
@@ -36,7 +36,8 @@ impl<T> UniformBuffer<T> where T: Copy + Send + 'static {
 
     /// Uploads data in the uniforms buffer.
     pub fn new_if_supported<F>(facade: &F, data: T) -> Option<UniformBuffer<T>> where F: Facade {
-        let buffer = match BufferView::new(facade, &[data], BufferType::UniformBuffer, true) {
+        let buffer = match BufferView::new(facade, &[data], BufferType::UniformBuffer,
+                                           BufferMode::Dynamic) {
             Ok(b) => b,
             Err(BufferCreationError::BufferTypeNotSupported) => return None,
             e @ Err(_) => e.unwrap(),
@@ -47,6 +48,19 @@ impl<T> UniformBuffer<T> where T: Copy + Send + 'static {
         })
     }
 
+    /// Uploads data in a uniforms buffer created with the given mode.
+    ///
+    /// Returns an error if uniform buffers or the mode are not supported by the backend.
+    pub fn with_mode<F>(facade: &F, data: T, mode: BufferMode)
+                        -> Result<UniformBuffer<T>, BufferCreationError> where F: Facade
+    {
+        let buffer = try!(BufferView::new(facade, &[data], BufferType::UniformBuffer, mode));
+
+        Ok(UniformBuffer {
+            buffer: buffer,
+        })
+    }
+
     /// Modifies the content of the buffer.
     pub fn upload(&mut self, data: T) {
         self.write(&[data]);
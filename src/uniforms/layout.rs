@@ -0,0 +1,494 @@
+use std::fmt;
+use std::error::Error;
+
+use program;
+use uniforms::UniformType;
+
+/// Rules that determine the layout of the members of a block in memory.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlockLayout {
+    /// The `std140` rules. Used for uniform blocks.
+    Std140,
+
+    /// The `std430` rules. Used for shader storage blocks.
+    Std430,
+}
+
+/// Describes the way a Rust type is laid out in memory.
+///
+/// This is used by the `implement_uniform_block!` macro to compare the fields of a struct
+/// with the members of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberLayout {
+    /// A 32-bits float.
+    Float,
+
+    /// A 32-bits signed integer.
+    Int,
+
+    /// A 32-bits unsigned integer.
+    UnsignedInt,
+
+    /// An array of elements.
+    Array(Box<MemberLayout>, usize),
+}
+
+impl MemberLayout {
+    /// Returns the size in bytes of the Rust type.
+    pub fn get_size(&self) -> usize {
+        match self {
+            &MemberLayout::Float | &MemberLayout::Int | &MemberLayout::UnsignedInt => 4,
+            &MemberLayout::Array(ref elem, len) => elem.get_size() * len,
+        }
+    }
+}
+
+/// Types that can be a field of a struct that implements `UniformBlock` through the
+/// `implement_uniform_block!` macro.
+///
+/// This is implemented for `f32`, `i32`, `u32`, and for arrays of these types or of arrays
+/// whose length is between 1 and 32, or is 64, 128 or 256. Arrays of other lengths, for example
+/// `[f32; 48]`, must be split into several fields or implement this trait manually.
+pub trait UniformBlockField {
+    /// Returns the way this type is laid out in memory.
+    fn get_layout() -> MemberLayout;
+}
+
+impl UniformBlockField for f32 {
+    fn get_layout() -> MemberLayout {
+        MemberLayout::Float
+    }
+}
+
+impl UniformBlockField for i32 {
+    fn get_layout() -> MemberLayout {
+        MemberLayout::Int
+    }
+}
+
+impl UniformBlockField for u32 {
+    fn get_layout() -> MemberLayout {
+        MemberLayout::UnsignedInt
+    }
+}
+
+macro_rules! impl_array {
+    ($($len:expr),+) => (
+        $(
+            impl<T> UniformBlockField for [T; $len] where T: UniformBlockField {
+                fn get_layout() -> MemberLayout {
+                    MemberLayout::Array(Box::new(<T as UniformBlockField>::get_layout()), $len)
+                }
+            }
+        )+
+    );
+}
+
+impl_array!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+            24, 25, 26, 27, 28, 29, 30, 31, 32, 64, 128, 256);
+
+/// Error that can happen when the layout of a Rust type doesn't match the layout of a block.
+///
+/// "Expected" values are the ones of the block, "obtained" values are the ones of the Rust type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutMismatchError {
+    /// The size of the Rust type is not the same as the size of the block.
+    SizeMismatch {
+        /// Size of the block.
+        expected: usize,
+        /// Size of the Rust type.
+        obtained: usize,
+    },
+
+    /// The block doesn't have any member.
+    EmptyBlock,
+
+    /// A member of the block doesn't have any corresponding field in the Rust type.
+    MissingField {
+        /// Name of the member in the block.
+        name: String,
+    },
+
+    /// A field of the Rust type doesn't have any corresponding member in the block.
+    MissingMember {
+        /// Name of the field.
+        name: String,
+    },
+
+    /// The offset of a field is not the same as the offset of the member.
+    OffsetMismatch {
+        /// Name of the member.
+        member: String,
+        /// Offset of the member in the block.
+        expected: usize,
+        /// Offset of the field in the Rust type.
+        obtained: usize,
+    },
+
+    /// The type of a field doesn't match the type of the member.
+    TypeMismatch {
+        /// Name of the member.
+        member: String,
+        /// Offset of the member in the block.
+        offset: usize,
+        /// Type of the member.
+        expected: UniformType,
+    },
+
+    /// The number of array elements of a field is not the same as the member.
+    ArrayLengthMismatch {
+        /// Name of the member.
+        member: String,
+        /// Offset of the member in the block.
+        offset: usize,
+        /// Number of elements of the member, or `None` if it is not an array.
+        expected: Option<usize>,
+        /// Number of elements of the field, or `None` if it is not an array.
+        obtained: Option<usize>,
+    },
+
+    /// The distance between two elements of an array or two columns of a matrix is not the
+    /// one required by the layout rules.
+    StrideMismatch {
+        /// Name of the member.
+        member: String,
+        /// Offset of the member in the block.
+        offset: usize,
+        /// Stride reported by the implementation, or required by the layout rules.
+        expected: usize,
+        /// Stride of the Rust type.
+        obtained: usize,
+    },
+}
+
+impl fmt::Display for LayoutMismatchError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            &LayoutMismatchError::SizeMismatch { expected, obtained } => {
+                write!(fmt, "The block has a size of {} bytes, but the data has a size of {} \
+                             bytes", expected, obtained)
+            },
+            &LayoutMismatchError::EmptyBlock => {
+                write!(fmt, "The block doesn't have any member")
+            },
+            &LayoutMismatchError::MissingField { ref name } => {
+                write!(fmt, "The member `{}` of the block has no corresponding field", name)
+            },
+            &LayoutMismatchError::MissingMember { ref name } => {
+                write!(fmt, "The field `{}` has no corresponding member in the block", name)
+            },
+            &LayoutMismatchError::OffsetMismatch { ref member, expected, obtained } => {
+                write!(fmt, "The member `{}` is at offset {} in the block, but the field is at \
+                             offset {}", member, expected, obtained)
+            },
+            &LayoutMismatchError::TypeMismatch { ref member, offset, expected } => {
+                write!(fmt, "The type of the member `{}` at offset {} is `{:?}`, which doesn't \
+                             match the field", member, offset, expected)
+            },
+            &LayoutMismatchError::ArrayLengthMismatch { ref member, offset, expected,
+                                                        obtained } =>
+            {
+                write!(fmt, "The member `{}` at offset {} has {:?} array elements, but the \
+                             field has {:?}", member, offset, expected, obtained)
+            },
+            &LayoutMismatchError::StrideMismatch { ref member, offset, expected, obtained } => {
+                write!(fmt, "The member `{}` at offset {} requires a stride of {} bytes, but the \
+                             field has a stride of {} bytes", member, offset, expected, obtained)
+            },
+        }
+    }
+}
+
+impl Error for LayoutMismatchError {
+    fn description(&self) -> &str {
+        match self {
+            &LayoutMismatchError::SizeMismatch { .. } => "The size of the data doesn't match \
+                                                          the size of the block",
+            &LayoutMismatchError::EmptyBlock => "The block doesn't have any member",
+            &LayoutMismatchError::MissingField { .. } => "A member of the block has no \
+                                                          corresponding field",
+            &LayoutMismatchError::MissingMember { .. } => "A field has no corresponding member \
+                                                           in the block",
+            &LayoutMismatchError::OffsetMismatch { .. } => "The offset of a field doesn't match \
+                                                            the offset of the member",
+            &LayoutMismatchError::TypeMismatch { .. } => "The type of a field doesn't match the \
+                                                          type of the member",
+            &LayoutMismatchError::ArrayLengthMismatch { .. } => "The number of elements of an \
+                                                                 array doesn't match",
+            &LayoutMismatchError::StrideMismatch { .. } => "The stride of an array or matrix \
+                                                            doesn't match the layout rules",
+        }
+    }
+}
+
+/// Returns the components of a scalar, vector or matrix type: the layout of each component,
+/// the number of rows and the number of columns.
+fn decompose(ty: &UniformType) -> Option<(MemberLayout, usize, usize)> {
+    let (scalar, rows, columns) = match *ty {
+        UniformType::Float => (MemberLayout::Float, 1, 1),
+        UniformType::FloatVec2 => (MemberLayout::Float, 2, 1),
+        UniformType::FloatVec3 => (MemberLayout::Float, 3, 1),
+        UniformType::FloatVec4 => (MemberLayout::Float, 4, 1),
+        UniformType::Int => (MemberLayout::Int, 1, 1),
+        UniformType::IntVec2 => (MemberLayout::Int, 2, 1),
+        UniformType::IntVec3 => (MemberLayout::Int, 3, 1),
+        UniformType::IntVec4 => (MemberLayout::Int, 4, 1),
+        UniformType::UnsignedInt => (MemberLayout::UnsignedInt, 1, 1),
+        UniformType::UnsignedIntVec2 => (MemberLayout::UnsignedInt, 2, 1),
+        UniformType::UnsignedIntVec3 => (MemberLayout::UnsignedInt, 3, 1),
+        UniformType::UnsignedIntVec4 => (MemberLayout::UnsignedInt, 4, 1),
+        UniformType::FloatMat2 => (MemberLayout::Float, 2, 2),
+        UniformType::FloatMat3 => (MemberLayout::Float, 3, 3),
+        UniformType::FloatMat4 => (MemberLayout::Float, 4, 4),
+        UniformType::FloatMat2x3 => (MemberLayout::Float, 3, 2),
+        UniformType::FloatMat2x4 => (MemberLayout::Float, 4, 2),
+        UniformType::FloatMat3x2 => (MemberLayout::Float, 2, 3),
+        UniformType::FloatMat3x4 => (MemberLayout::Float, 4, 3),
+        UniformType::FloatMat4x2 => (MemberLayout::Float, 2, 4),
+        UniformType::FloatMat4x3 => (MemberLayout::Float, 3, 4),
+        _ => return None,
+    };
+
+    Some((scalar, rows, columns))
+}
+
+/// Returns the base alignment of a vector of `rows` components, according to the rules.
+fn vector_alignment(rows: usize) -> usize {
+    match rows {
+        1 => 4,
+        2 => 8,
+        _ => 16,
+    }
+}
+
+/// Returns the stride between two columns of a matrix of `rows` rows, according to the rules.
+fn column_stride(rows: usize, layout: BlockLayout) -> usize {
+    match layout {
+        BlockLayout::Std140 => 16,
+        BlockLayout::Std430 => vector_alignment(rows),
+    }
+}
+
+/// Returns the stride between two elements of an array of `ty`, according to the rules.
+///
+/// Returns `None` if the type can't be put in a block.
+pub fn array_stride(ty: &UniformType, layout: BlockLayout) -> Option<usize> {
+    let (_, rows, columns) = match decompose(ty) {
+        Some(d) => d,
+        None => return None
+    };
+
+    if columns >= 2 {
+        return Some(column_stride(rows, layout) * columns);
+    }
+
+    match layout {
+        BlockLayout::Std140 => Some(16),
+        BlockLayout::Std430 => Some(vector_alignment(rows)),
+    }
+}
+
+/// Checks whether a Rust type matches a value of type `ty`, ignoring arrays.
+///
+/// `matrix_stride` is the stride between two columns reported by the implementation. If it is
+/// `None`, the stride required by `layout` is used instead.
+fn check_value(field: &MemberLayout, ty: &UniformType, layout: BlockLayout,
+               matrix_stride: Option<usize>, member: &str, offset: usize)
+               -> Result<(), LayoutMismatchError>
+{
+    let type_mismatch = || LayoutMismatchError::TypeMismatch {
+        member: member.to_string(),
+        offset: offset,
+        expected: *ty,
+    };
+
+    let (scalar, rows, columns) = match decompose(ty) {
+        Some(d) => d,
+        None => return Err(type_mismatch()),
+    };
+
+    // a vector, or a column of a matrix
+    let check_vector = |field: &MemberLayout| -> bool {
+        match (field, rows) {
+            (f, 1) => *f == scalar,
+            (&MemberLayout::Array(ref elem, len), _) => **elem == scalar && len == rows,
+            _ => false
+        }
+    };
+
+    if columns == 1 {
+        return if check_vector(field) { Ok(()) } else { Err(type_mismatch()) };
+    }
+
+    // for matrices, the columns can be padded with unused components in order to match
+    // the stride required by the rules
+    match field {
+        &MemberLayout::Array(ref column, len) if len == columns => {
+            let column_data = match **column {
+                MemberLayout::Array(ref elem, len) if **elem == scalar && len >= rows => {
+                    MemberLayout::Array(elem.clone(), rows)
+                },
+                _ => return Err(type_mismatch()),
+            };
+
+            if !check_vector(&column_data) {
+                return Err(type_mismatch());
+            }
+
+            let expected = matrix_stride.unwrap_or_else(|| column_stride(rows, layout));
+            if column.get_size() != expected {
+                return Err(LayoutMismatchError::StrideMismatch {
+                    member: member.to_string(),
+                    offset: offset,
+                    expected: expected,
+                    obtained: column.get_size(),
+                });
+            }
+
+            Ok(())
+        },
+        _ => Err(type_mismatch()),
+    }
+}
+
+/// Returns true if the name of a member of a block corresponds to the name of a field.
+///
+/// The names of the members of blocks that have an instance name are prefixed with the name of
+/// the block, and the names of arrays may be suffixed with `[0]`.
+fn member_name_matches(member: &str, field: &str) -> bool {
+    let member = if member.ends_with("[0]") { &member[.. member.len() - 3] } else { member };
+
+    if member == field {
+        return true;
+    }
+
+    member.len() > field.len() && member.ends_with(field) &&
+        member.as_bytes()[member.len() - field.len() - 1] == b'.'
+}
+
+/// Checks whether a field of a Rust type matches the corresponding member of a block.
+///
+/// This function is used by the `implement_uniform_block!` macro.
+pub fn check_block_field(block: &program::UniformBlock, name: &str, offset: usize,
+                         field: MemberLayout) -> Result<(), LayoutMismatchError>
+{
+    let member = match block.members.iter().find(|m| member_name_matches(&m.name, name)) {
+        Some(m) => m,
+        None => return Err(LayoutMismatchError::MissingMember { name: name.to_string() }),
+    };
+
+    if member.offset != offset {
+        return Err(LayoutMismatchError::OffsetMismatch {
+            member: member.name.clone(),
+            expected: member.offset,
+            obtained: offset,
+        });
+    }
+
+    match member.size {
+        None => check_value(&field, &member.ty, block.layout, member.matrix_stride,
+                            &member.name, member.offset),
+
+        Some(expected_len) => {
+            let (elem, len) = match field {
+                MemberLayout::Array(elem, len) => (elem, len),
+                _ => return Err(LayoutMismatchError::ArrayLengthMismatch {
+                    member: member.name.clone(),
+                    offset: member.offset,
+                    expected: Some(expected_len),
+                    obtained: None,
+                })
+            };
+
+            if len != expected_len {
+                return Err(LayoutMismatchError::ArrayLengthMismatch {
+                    member: member.name.clone(),
+                    offset: member.offset,
+                    expected: Some(expected_len),
+                    obtained: Some(len),
+                });
+            }
+
+            let expected_stride = match array_stride(&member.ty, block.layout) {
+                Some(s) => member.array_stride.unwrap_or(s),
+                None => return Err(LayoutMismatchError::TypeMismatch {
+                    member: member.name.clone(),
+                    offset: member.offset,
+                    expected: member.ty,
+                }),
+            };
+
+            // the elements of an array of scalars or vectors can be padded with unused components
+            let elem_data = match (decompose(&member.ty), &*elem) {
+                (Some((_, rows, 1)), &MemberLayout::Array(ref inner, inner_len))
+                    if inner_len > rows =>
+                {
+                    if rows == 1 {
+                        (**inner).clone()
+                    } else {
+                        MemberLayout::Array(inner.clone(), rows)
+                    }
+                },
+                _ => (*elem).clone(),
+            };
+
+            try!(check_value(&elem_data, &member.ty, block.layout, member.matrix_stride,
+                             &member.name, member.offset));
+
+            if elem.get_size() != expected_stride {
+                return Err(LayoutMismatchError::StrideMismatch {
+                    member: member.name.clone(),
+                    offset: member.offset,
+                    expected: expected_stride,
+                    obtained: elem.get_size(),
+                });
+            }
+
+            Ok(())
+        },
+    }
+}
+
+/// Checks that each member of a block has a corresponding field.
+///
+/// This function is used by the `implement_uniform_block!` macro.
+pub fn check_block_fields_present(block: &program::UniformBlock, fields: &[&str])
+                                  -> Result<(), LayoutMismatchError>
+{
+    for member in block.members.iter() {
+        if !fields.iter().any(|f| member_name_matches(&member.name, f)) {
+            return Err(LayoutMismatchError::MissingField { name: member.name.clone() });
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the columns of a member are tightly packed, like the columns of a Rust
+/// `[[f32; R]; C]` array.
+///
+/// Vectors and scalars always pass this check.
+pub fn check_columns_packed(member: &program::UniformBlockMember)
+                            -> Result<(), LayoutMismatchError>
+{
+    let (_, rows, columns) = match decompose(&member.ty) {
+        Some(d) => d,
+        None => return Ok(()),
+    };
+
+    let stride = match member.matrix_stride {
+        Some(s) if columns >= 2 => s,
+        _ => return Ok(()),
+    };
+
+    if stride != rows * 4 {
+        return Err(LayoutMismatchError::StrideMismatch {
+            member: member.name.clone(),
+            offset: member.offset,
+            expected: stride,
+            obtained: rows * 4,
+        });
+    }
+
+    Ok(())
+}
@@ -25,6 +25,30 @@ let uniforms = uniform! {
 
 In both situations, each field must implement the `UniformValue` trait.
 
+## Arrays and structs
+
+Uniform arrays such as `uniform vec3 lights[16];` can be set by passing a slice, for example a
+`&[[f32; 3]]`. The slice must not be longer than the array declared in the program, but it can
+be shorter.
+
+Arrays of `int`, `uint`, `float`, of `ivec*`, `uvec*` and `vec*`, and of `mat2`, `mat3` and `mat4`
+are supported. Arrays of booleans, of doubles and of non-square matrices can't be set with a
+slice yet, but each of their elements can be set individually with its name, for example
+`flags[3]`. Arrays of `uint` and `uvec*` require OpenGL 3.0 or OpenGL ES 3.0.
+
+The members of structs and arrays of structs are set individually with the name used in GLSL,
+for example `lights[0].color`. Since these names are not valid identifiers, you need to use
+`UniformsStorage` directly instead of the `uniform!` macro.
+
+```no_run
+# let display: glium::Display = unsafe { std::mem::uninitialized() };
+let positions = [[0.0, 1.0, 0.0f32], [1.0, 0.0, 0.0]];
+
+let uniforms = glium::uniforms::UniformsStorage::new("positions", &positions[..])
+                    .add("lights[0].color", [1.0, 0.0, 0.0f32])
+                    .add("lights[1].color", [0.0, 0.0, 1.0f32]);
+```
+
 ## Samplers
 
 In order to customize the way a texture is being sampled, you must use a `Sampler`.
@@ -89,8 +113,29 @@ let uniforms = uniform! {
 # }
 ```
 
+## Atomic counters
+
+Atomic counters are stored in an `AtomicCounterBuffer`. Link the buffer to the name of one of
+the counters that it contains, and it is binded to the binding point of this counter.
+
+```no_run
+#[macro_use]
+extern crate glium;
+# fn main() {
+# let display: glium::Display = unsafe { std::mem::uninitialized() };
+let counters = glium::uniforms::AtomicCounterBuffer::zeroed_if_supported(&display, 1).unwrap();
+
+let uniforms = uniform! {
+    visible_count: &counters
+};
+# }
+```
+
 */
+pub use self::atomic_counter::AtomicCounterBuffer;
 pub use self::buffer::UniformBuffer;
+pub use self::layout::{BlockLayout, LayoutMismatchError, MemberLayout, UniformBlockField};
+pub use self::layout::{check_block_field, check_block_fields_present};
 pub use self::sampler::{SamplerWrapFunction, MagnifySamplerFilter, MinifySamplerFilter};
 pub use self::sampler::{Sampler, SamplerBehavior};
 pub use self::uniforms::{EmptyUniforms, UniformsStorage};
@@ -98,8 +143,10 @@ pub use self::value::{UniformValue, AsUniformValue, UniformType};
 
 use program;
 
+mod atomic_counter;
 mod bind;
 mod buffer;
+mod layout;
 mod sampler;
 mod uniforms;
 mod value;
@@ -111,7 +158,11 @@ pub trait Uniforms {
 }
 
 /// Objects that are suitable for being binded to a uniform block.
+///
+/// You can implement this trait for your structs with the `implement_uniform_block!` macro.
 pub trait UniformBlock: Copy {
     /// Checks whether the uniforms' layout matches the given block.
-    fn matches(&program::UniformBlock) -> bool;
+    ///
+    /// Returns an error describing the first difference otherwise.
+    fn matches(&program::UniformBlock) -> Result<(), LayoutMismatchError>;
 }
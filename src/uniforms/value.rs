@@ -1,6 +1,7 @@
 use program;
 use texture;
-use uniforms::UniformBlock;
+use uniforms::{UniformBlock, LayoutMismatchError};
+use uniforms::layout;
 use uniforms::SamplerBehavior;
 
 use buffer::BufferViewAnySlice;
@@ -141,10 +142,12 @@ pub trait AsUniformValue {
 #[derive(Copy)]
 pub enum UniformValue<'a> {
     /// Contains a handle to the buffer, and a function that indicates whether this buffer
-    /// can be binded on a block with the given layout.
+    /// can be binded on a block with the given layout, or why it can't.
     /// The last parameter is a sender which must be used to send a `SyncFence` that expires when
     /// the buffer has finished being used.
-    Block(BufferViewAnySlice<'a>, fn(&program::UniformBlock) -> bool),
+    Block(BufferViewAnySlice<'a>, fn(&program::UniformBlock) -> Result<(), LayoutMismatchError>),
+    /// Buffer to bind to the binding point of an atomic counter.
+    AtomicCounterBuffer(BufferViewAnySlice<'a>),
     SignedInt(i32),
     UnsignedInt(u32),
     Float(f32),
@@ -157,6 +160,68 @@ pub enum UniformValue<'a> {
     Vec2([f32; 2]),
     Vec3([f32; 3]),
     Vec4([f32; 4]),
+    Bool(bool),
+    BoolVec2([bool; 2]),
+    BoolVec3([bool; 3]),
+    BoolVec4([bool; 4]),
+    IntVec2([i32; 2]),
+    IntVec3([i32; 3]),
+    IntVec4([i32; 4]),
+    UnsignedIntVec2([u32; 2]),
+    UnsignedIntVec3([u32; 3]),
+    UnsignedIntVec4([u32; 4]),
+    /// 2x3 column-major matrix (two columns of three elements).
+    Mat2x3([[f32; 3]; 2]),
+    /// 2x4 column-major matrix (two columns of four elements).
+    Mat2x4([[f32; 4]; 2]),
+    /// 3x2 column-major matrix (three columns of two elements).
+    Mat3x2([[f32; 2]; 3]),
+    /// 3x4 column-major matrix (three columns of four elements).
+    Mat3x4([[f32; 4]; 3]),
+    /// 4x2 column-major matrix (four columns of two elements).
+    Mat4x2([[f32; 2]; 4]),
+    /// 4x3 column-major matrix (four columns of three elements).
+    Mat4x3([[f32; 3]; 4]),
+    Double(f64),
+    DoubleVec2([f64; 2]),
+    DoubleVec3([f64; 3]),
+    DoubleVec4([f64; 4]),
+    /// 2x2 column-major matrix.
+    DoubleMat2([[f64; 2]; 2]),
+    /// 3x3 column-major matrix.
+    DoubleMat3([[f64; 3]; 3]),
+    /// 4x4 column-major matrix.
+    DoubleMat4([[f64; 4]; 4]),
+    /// 2x3 column-major matrix (two columns of three elements).
+    DoubleMat2x3([[f64; 3]; 2]),
+    /// 2x4 column-major matrix (two columns of four elements).
+    DoubleMat2x4([[f64; 4]; 2]),
+    /// 3x2 column-major matrix (three columns of two elements).
+    DoubleMat3x2([[f64; 2]; 3]),
+    /// 3x4 column-major matrix (three columns of four elements).
+    DoubleMat3x4([[f64; 4]; 3]),
+    /// 4x2 column-major matrix (four columns of two elements).
+    DoubleMat4x2([[f64; 2]; 4]),
+    /// 4x3 column-major matrix (four columns of three elements).
+    DoubleMat4x3([[f64; 3]; 4]),
+    SignedIntArray(&'a [i32]),
+    UnsignedIntArray(&'a [u32]),
+    FloatArray(&'a [f32]),
+    /// Array of 2x2 column-major matrices.
+    Mat2Array(&'a [[[f32; 2]; 2]]),
+    /// Array of 3x3 column-major matrices.
+    Mat3Array(&'a [[[f32; 3]; 3]]),
+    /// Array of 4x4 column-major matrices.
+    Mat4Array(&'a [[[f32; 4]; 4]]),
+    Vec2Array(&'a [[f32; 2]]),
+    Vec3Array(&'a [[f32; 3]]),
+    Vec4Array(&'a [[f32; 4]]),
+    IntVec2Array(&'a [[i32; 2]]),
+    IntVec3Array(&'a [[i32; 3]]),
+    IntVec4Array(&'a [[i32; 4]]),
+    UnsignedIntVec2Array(&'a [[u32; 2]]),
+    UnsignedIntVec3Array(&'a [[u32; 3]]),
+    UnsignedIntVec4Array(&'a [[u32; 4]]),
     Texture1d(&'a texture::Texture1d, Option<SamplerBehavior>),
     CompressedTexture1d(&'a texture::CompressedTexture1d, Option<SamplerBehavior>),
     SrgbTexture1d(&'a texture::SrgbTexture1d, Option<SamplerBehavior>),
@@ -212,6 +277,8 @@ impl<'a> Clone for UniformValue<'a> {
 
 impl<'a> UniformValue<'a> {
     /// Returns true if this value can be used with a uniform of the given type.
+    ///
+    /// For arrays, this checks the type of the elements.
     pub fn is_usable_with(&self, ty: &UniformType) -> bool {
         match (self, *ty) {
             (&UniformValue::SignedInt(_), UniformType::Int) => true,
@@ -223,6 +290,50 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::Vec2(_), UniformType::FloatVec2) => true,
             (&UniformValue::Vec3(_), UniformType::FloatVec3) => true,
             (&UniformValue::Vec4(_), UniformType::FloatVec4) => true,
+            (&UniformValue::Bool(_), UniformType::Bool) => true,
+            (&UniformValue::BoolVec2(_), UniformType::BoolVec2) => true,
+            (&UniformValue::BoolVec3(_), UniformType::BoolVec3) => true,
+            (&UniformValue::BoolVec4(_), UniformType::BoolVec4) => true,
+            (&UniformValue::IntVec2(_), UniformType::IntVec2) => true,
+            (&UniformValue::IntVec3(_), UniformType::IntVec3) => true,
+            (&UniformValue::IntVec4(_), UniformType::IntVec4) => true,
+            (&UniformValue::UnsignedIntVec2(_), UniformType::UnsignedIntVec2) => true,
+            (&UniformValue::UnsignedIntVec3(_), UniformType::UnsignedIntVec3) => true,
+            (&UniformValue::UnsignedIntVec4(_), UniformType::UnsignedIntVec4) => true,
+            (&UniformValue::Mat2x3(_), UniformType::FloatMat2x3) => true,
+            (&UniformValue::Mat2x4(_), UniformType::FloatMat2x4) => true,
+            (&UniformValue::Mat3x2(_), UniformType::FloatMat3x2) => true,
+            (&UniformValue::Mat3x4(_), UniformType::FloatMat3x4) => true,
+            (&UniformValue::Mat4x2(_), UniformType::FloatMat4x2) => true,
+            (&UniformValue::Mat4x3(_), UniformType::FloatMat4x3) => true,
+            (&UniformValue::Double(_), UniformType::Double) => true,
+            (&UniformValue::DoubleVec2(_), UniformType::DoubleVec2) => true,
+            (&UniformValue::DoubleVec3(_), UniformType::DoubleVec3) => true,
+            (&UniformValue::DoubleVec4(_), UniformType::DoubleVec4) => true,
+            (&UniformValue::DoubleMat2(_), UniformType::DoubleMat2) => true,
+            (&UniformValue::DoubleMat3(_), UniformType::DoubleMat3) => true,
+            (&UniformValue::DoubleMat4(_), UniformType::DoubleMat4) => true,
+            (&UniformValue::DoubleMat2x3(_), UniformType::DoubleMat2x3) => true,
+            (&UniformValue::DoubleMat2x4(_), UniformType::DoubleMat2x4) => true,
+            (&UniformValue::DoubleMat3x2(_), UniformType::DoubleMat3x2) => true,
+            (&UniformValue::DoubleMat3x4(_), UniformType::DoubleMat3x4) => true,
+            (&UniformValue::DoubleMat4x2(_), UniformType::DoubleMat4x2) => true,
+            (&UniformValue::DoubleMat4x3(_), UniformType::DoubleMat4x3) => true,
+            (&UniformValue::SignedIntArray(_), UniformType::Int) => true,
+            (&UniformValue::UnsignedIntArray(_), UniformType::UnsignedInt) => true,
+            (&UniformValue::FloatArray(_), UniformType::Float) => true,
+            (&UniformValue::Mat2Array(_), UniformType::FloatMat2) => true,
+            (&UniformValue::Mat3Array(_), UniformType::FloatMat3) => true,
+            (&UniformValue::Mat4Array(_), UniformType::FloatMat4) => true,
+            (&UniformValue::Vec2Array(_), UniformType::FloatVec2) => true,
+            (&UniformValue::Vec3Array(_), UniformType::FloatVec3) => true,
+            (&UniformValue::Vec4Array(_), UniformType::FloatVec4) => true,
+            (&UniformValue::IntVec2Array(_), UniformType::IntVec2) => true,
+            (&UniformValue::IntVec3Array(_), UniformType::IntVec3) => true,
+            (&UniformValue::IntVec4Array(_), UniformType::IntVec4) => true,
+            (&UniformValue::UnsignedIntVec2Array(_), UniformType::UnsignedIntVec2) => true,
+            (&UniformValue::UnsignedIntVec3Array(_), UniformType::UnsignedIntVec3) => true,
+            (&UniformValue::UnsignedIntVec4Array(_), UniformType::UnsignedIntVec4) => true,
             (&UniformValue::Texture1d(_, _), UniformType::Sampler1d) => true,
             (&UniformValue::CompressedTexture1d(_, _), UniformType::Sampler1d) => true,
             (&UniformValue::SrgbTexture1d(_, _), UniformType::Sampler1d) => true,
@@ -261,33 +372,77 @@ impl<'a> UniformValue<'a> {
             _ => false,
         }
     }
+
+    /// If this value is an array, returns its number of elements.
+    pub fn get_array_len(&self) -> Option<usize> {
+        match *self {
+            UniformValue::SignedIntArray(val) => Some(val.len()),
+            UniformValue::UnsignedIntArray(val) => Some(val.len()),
+            UniformValue::FloatArray(val) => Some(val.len()),
+            UniformValue::Mat2Array(val) => Some(val.len()),
+            UniformValue::Mat3Array(val) => Some(val.len()),
+            UniformValue::Mat4Array(val) => Some(val.len()),
+            UniformValue::Vec2Array(val) => Some(val.len()),
+            UniformValue::Vec3Array(val) => Some(val.len()),
+            UniformValue::Vec4Array(val) => Some(val.len()),
+            UniformValue::IntVec2Array(val) => Some(val.len()),
+            UniformValue::IntVec3Array(val) => Some(val.len()),
+            UniformValue::IntVec4Array(val) => Some(val.len()),
+            UniformValue::UnsignedIntVec2Array(val) => Some(val.len()),
+            UniformValue::UnsignedIntVec3Array(val) => Some(val.len()),
+            UniformValue::UnsignedIntVec4Array(val) => Some(val.len()),
+            _ => None,
+        }
+    }
 }
 
 impl<T> UniformBlock for T where T: AsUniformValue + Copy + Send + 'static {
-    fn matches(block: &program::UniformBlock) -> bool {
-        if block.members.len() != 1 {
-            return false;
+    fn matches(block: &program::UniformBlock) -> Result<(), LayoutMismatchError> {
+        if block.size != mem::size_of::<T>() {
+            return Err(LayoutMismatchError::SizeMismatch {
+                expected: block.size,
+                obtained: mem::size_of::<T>(),
+            });
         }
 
-        if block.size != mem::size_of::<T>() {
-            return false;
+        if let Some(member) = block.members.get(1) {
+            return Err(LayoutMismatchError::MissingField { name: member.name.clone() });
         }
 
-        let ref member = block.members[0];
+        let member = match block.members.get(0) {
+            Some(m) => m,
+            None => return Err(LayoutMismatchError::EmptyBlock),
+        };
 
         if member.offset != 0 {
-            return false;
+            return Err(LayoutMismatchError::OffsetMismatch {
+                member: member.name.clone(),
+                expected: member.offset,
+                obtained: 0,
+            });
         }
 
         if !<T as AsUniformValue>::matches(&member.ty) {
-            return false;
+            return Err(LayoutMismatchError::TypeMismatch {
+                member: member.name.clone(),
+                offset: member.offset,
+                expected: member.ty,
+            });
         }
 
         if member.size.is_some() {
-            return false;
+            return Err(LayoutMismatchError::ArrayLengthMismatch {
+                member: member.name.clone(),
+                offset: member.offset,
+                expected: member.size,
+                obtained: None,
+            });
         }
 
-        true
+        // `shared` and `packed` blocks can put padding between the columns of a matrix
+        try!(layout::check_columns_packed(member));
+
+        Ok(())
     }
 }
 
@@ -451,322 +606,1124 @@ impl AsUniformValue for [f32; 4] {
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Mat2<f32> {
+impl AsUniformValue for bool {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_array();
-        my_value.as_uniform_value()
+        UniformValue::Bool(*self)
     }
 
-    fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat2
+    fn matches(_: &UniformType) -> bool {
+        false
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Mat3<f32> {
+impl AsUniformValue for [bool; 2] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_array();
-        my_value.as_uniform_value()
+        UniformValue::BoolVec2(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl AsUniformValue for [bool; 3] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::BoolVec3(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl AsUniformValue for [bool; 4] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::BoolVec4(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl AsUniformValue for [i32; 2] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::IntVec2(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat3
+        ty == &UniformType::IntVec2
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Mat4<f32> {
+impl AsUniformValue for [i32; 3] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_array();
-        my_value.as_uniform_value()
+        UniformValue::IntVec3(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat4
+        ty == &UniformType::IntVec3
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Ortho3<f32> {
+impl AsUniformValue for [i32; 4] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.to_mat(); // Bind to a Mat4
-        UniformValue::Mat4(*my_value.as_array())
+        UniformValue::IntVec4(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat4
+        ty == &UniformType::IntVec4
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::OrthoMat3<f32> {
+impl AsUniformValue for [u32; 2] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_mat(); // Bind to a Mat4
-        my_value.as_uniform_value()
+        UniformValue::UnsignedIntVec2(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat4
+        ty == &UniformType::UnsignedIntVec2
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Persp3<f32> {
+impl AsUniformValue for [u32; 3] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.to_mat(); // Bind to a Mat4
-        UniformValue::Mat4(*my_value.as_array())
+        UniformValue::UnsignedIntVec3(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat4
+        ty == &UniformType::UnsignedIntVec3
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::PerspMat3<f32> {
+impl AsUniformValue for [u32; 4] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_mat(); // Bind to a Mat4
-        my_value.as_uniform_value()
+        UniformValue::UnsignedIntVec4(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat4
+        ty == &UniformType::UnsignedIntVec4
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Pnt2<f32> {
+impl AsUniformValue for [[f32; 3]; 2] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_array();
-        my_value.as_uniform_value()
+        UniformValue::Mat2x3(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec2
+        ty == &UniformType::FloatMat2x3
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Pnt3<f32> {
+impl AsUniformValue for [[f32; 4]; 2] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_array();
-        my_value.as_uniform_value()
+        UniformValue::Mat2x4(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec3
+        ty == &UniformType::FloatMat2x4
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Pnt4<f32> {
+impl AsUniformValue for [[f32; 2]; 3] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_array();
-        my_value.as_uniform_value()
+        UniformValue::Mat3x2(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec4
+        ty == &UniformType::FloatMat3x2
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Quat<f32> {
+impl AsUniformValue for [[f32; 4]; 3] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_array();
-        my_value.as_uniform_value()
+        UniformValue::Mat3x4(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec4
+        ty == &UniformType::FloatMat3x4
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Rot2<f32> {
+impl AsUniformValue for [[f32; 2]; 4] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.submat(); // Bind to a Mat2
-        UniformValue::Mat2(*my_value.as_array())
+        UniformValue::Mat4x2(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat2
+        ty == &UniformType::FloatMat4x2
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Rot3<f32> {
+impl AsUniformValue for [[f32; 3]; 4] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.submat(); // Bind to a Mat3
-        UniformValue::Mat3(*my_value.as_array())
+        UniformValue::Mat4x3(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat3
+        ty == &UniformType::FloatMat4x3
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Rot4<f32> {
+impl AsUniformValue for f64 {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.submat(); // Bind to a Mat4
-        UniformValue::Mat4(*my_value.as_array())
+        UniformValue::Double(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat4
+        ty == &UniformType::Double
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::UnitQuat<f32> {
+impl AsUniformValue for [f64; 2] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.quat(); // Bind to a Quat
-        UniformValue::Vec4(*my_value.as_array())
+        UniformValue::DoubleVec2(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec4
+        ty == &UniformType::DoubleVec2
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Vec2<f32> {
+impl AsUniformValue for [f64; 3] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_array();
-        my_value.as_uniform_value()
+        UniformValue::DoubleVec3(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec2
+        ty == &UniformType::DoubleVec3
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Vec3<f32> {
+impl AsUniformValue for [f64; 4] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_array();
-        my_value.as_uniform_value()
+        UniformValue::DoubleVec4(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec3
+        ty == &UniformType::DoubleVec4
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl AsUniformValue for nalgebra::Vec4<f32> {
+impl AsUniformValue for [[f64; 2]; 2] {
     fn as_uniform_value(&self) -> UniformValue {
-        let my_value = self.as_array();
-        my_value.as_uniform_value()
+        UniformValue::DoubleMat2(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec4
+        ty == &UniformType::DoubleMat2
     }
 }
 
-#[cfg(feature = "cgmath")]
-impl AsUniformValue for cgmath::Matrix2<f32> {
+impl AsUniformValue for [[f64; 3]; 3] {
     fn as_uniform_value(&self) -> UniformValue {
-        use cgmath::FixedArray;
-        let my_value = self.into_fixed();
-        UniformValue::Mat2(my_value)
+        UniformValue::DoubleMat3(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat2
+        ty == &UniformType::DoubleMat3
     }
 }
 
-#[cfg(feature = "cgmath")]
-impl AsUniformValue for cgmath::Matrix3<f32> {
+impl AsUniformValue for [[f64; 4]; 4] {
     fn as_uniform_value(&self) -> UniformValue {
-        use cgmath::FixedArray;
-        let my_value = self.into_fixed();
-        UniformValue::Mat3(my_value)
+        UniformValue::DoubleMat4(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat3
+        ty == &UniformType::DoubleMat4
     }
 }
 
-#[cfg(feature = "cgmath")]
-impl AsUniformValue for cgmath::Matrix4<f32> {
+impl AsUniformValue for [[f64; 3]; 2] {
     fn as_uniform_value(&self) -> UniformValue {
-        use cgmath::FixedArray;
-        let my_value = self.into_fixed();
-        UniformValue::Mat4(my_value)
+        UniformValue::DoubleMat2x3(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatMat4
+        ty == &UniformType::DoubleMat2x3
     }
 }
 
-#[cfg(feature = "cgmath")]
-impl AsUniformValue for cgmath::Vector2<f32> {
+impl AsUniformValue for [[f64; 4]; 2] {
     fn as_uniform_value(&self) -> UniformValue {
-        use cgmath::FixedArray;
-        let my_value = self.into_fixed();
-        UniformValue::Vec2(my_value)
+        UniformValue::DoubleMat2x4(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec2
+        ty == &UniformType::DoubleMat2x4
     }
 }
 
-#[cfg(feature = "cgmath")]
-impl AsUniformValue for cgmath::Vector3<f32> {
+impl AsUniformValue for [[f64; 2]; 3] {
     fn as_uniform_value(&self) -> UniformValue {
-        use cgmath::FixedArray;
-        let my_value = self.into_fixed();
-        UniformValue::Vec3(my_value)
+        UniformValue::DoubleMat3x2(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec3
+        ty == &UniformType::DoubleMat3x2
     }
 }
 
-#[cfg(feature = "cgmath")]
-impl AsUniformValue for cgmath::Vector4<f32> {
+impl AsUniformValue for [[f64; 4]; 3] {
     fn as_uniform_value(&self) -> UniformValue {
-        use cgmath::FixedArray;
-        let my_value = self.into_fixed();
-        UniformValue::Vec4(my_value)
+        UniformValue::DoubleMat3x4(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec4
+        ty == &UniformType::DoubleMat3x4
     }
 }
 
-#[cfg(feature = "cgmath")]
-impl AsUniformValue for cgmath::Point2<f32> {
+impl AsUniformValue for [[f64; 2]; 4] {
     fn as_uniform_value(&self) -> UniformValue {
-        use cgmath::FixedArray;
-        let my_value = self.into_fixed();
-        UniformValue::Vec2(my_value)
+        UniformValue::DoubleMat4x2(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec2
+        ty == &UniformType::DoubleMat4x2
     }
 }
 
-#[cfg(feature = "cgmath")]
-impl AsUniformValue for cgmath::Point3<f32> {
+impl AsUniformValue for [[f64; 3]; 4] {
     fn as_uniform_value(&self) -> UniformValue {
-        use cgmath::FixedArray;
-        let my_value = self.into_fixed();
-        UniformValue::Vec3(my_value)
+        UniformValue::DoubleMat4x3(*self)
     }
 
     fn matches(ty: &UniformType) -> bool {
-        ty == &UniformType::FloatVec3
+        ty == &UniformType::DoubleMat4x3
+    }
+}
+
+impl<'a> AsUniformValue for &'a [i32] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::SignedIntArray(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [u32] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::UnsignedIntArray(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [f32] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::FloatArray(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[[f32; 2]; 2]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Mat2Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[[f32; 3]; 3]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Mat3Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[[f32; 4]; 4]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Mat4Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[f32; 2]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Vec2Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[f32; 3]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Vec3Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[f32; 4]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Vec4Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[i32; 2]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::IntVec2Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[i32; 3]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::IntVec3Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[i32; 4]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::IntVec4Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[u32; 2]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::UnsignedIntVec2Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[u32; 3]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::UnsignedIntVec3Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[u32; 4]] {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::UnsignedIntVec4Array(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Mat2<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat2
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Mat3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat3
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Mat4<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Ortho3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.to_mat(); // Bind to a Mat4
+        UniformValue::Mat4(*my_value.as_array())
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::OrthoMat3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_mat(); // Bind to a Mat4
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Persp3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.to_mat(); // Bind to a Mat4
+        UniformValue::Mat4(*my_value.as_array())
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::PerspMat3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_mat(); // Bind to a Mat4
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Pnt2<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec2
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Pnt3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec3
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Pnt4<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Quat<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Rot2<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.submat(); // Bind to a Mat2
+        UniformValue::Mat2(*my_value.as_array())
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat2
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Rot3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.submat(); // Bind to a Mat3
+        UniformValue::Mat3(*my_value.as_array())
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat3
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Rot4<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.submat(); // Bind to a Mat4
+        UniformValue::Mat4(*my_value.as_array())
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::UnitQuat<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.quat(); // Bind to a Quat
+        UniformValue::Vec4(*my_value.as_array())
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec2<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec2
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec3
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec4<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Mat2<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleMat2
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Mat3<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleMat3
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Mat4<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleMat4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Pnt2<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec2
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Pnt3<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec3
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Pnt4<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec2<i32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::IntVec2
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec3<i32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::IntVec3
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec4<i32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::IntVec4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec2<u32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::UnsignedIntVec2
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec3<u32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::UnsignedIntVec3
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec4<u32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::UnsignedIntVec4
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec2<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec2
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec3<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec3
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl AsUniformValue for nalgebra::Vec4<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        let my_value = self.as_array();
+        my_value.as_uniform_value()
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec4
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Matrix2<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::Mat2(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat2
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Matrix3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::Mat3(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat3
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Matrix4<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::Mat4(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatMat4
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector2<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::Vec2(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec2
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::Vec3(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec3
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector4<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::Vec4(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec4
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Point2<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::Vec2(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec2
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Point3<f32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::Vec3(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::FloatVec3
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Matrix2<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::DoubleMat2(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleMat2
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Matrix3<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::DoubleMat3(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleMat3
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Matrix4<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::DoubleMat4(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleMat4
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector2<i32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::IntVec2(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::IntVec2
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector3<i32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::IntVec3(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::IntVec3
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector4<i32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::IntVec4(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::IntVec4
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector2<u32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::UnsignedIntVec2(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::UnsignedIntVec2
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector3<u32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::UnsignedIntVec3(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::UnsignedIntVec3
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector4<u32> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::UnsignedIntVec4(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::UnsignedIntVec4
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector2<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::DoubleVec2(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec2
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector3<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::DoubleVec3(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec3
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Vector4<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::DoubleVec4(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec4
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Point2<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::DoubleVec2(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec2
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl AsUniformValue for cgmath::Point3<f64> {
+    fn as_uniform_value(&self) -> UniformValue {
+        use cgmath::FixedArray;
+        let my_value = self.into_fixed();
+        UniformValue::DoubleVec3(my_value)
+    }
+
+    fn matches(ty: &UniformType) -> bool {
+        ty == &UniformType::DoubleVec3
     }
 }
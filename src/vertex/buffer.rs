@@ -1,6 +1,7 @@
 use std::ops::{Range, Deref, DerefMut};
 
-use buffer::{BufferView, BufferViewSlice, BufferViewAny, BufferType};
+use buffer::{BufferView, BufferViewSlice, BufferViewAny, BufferType, BufferMode};
+use buffer::BufferCreationError;
 use vertex::{Vertex, VerticesSource, IntoVerticesSource, PerInstance};
 use vertex::format::VertexFormat;
 
@@ -53,7 +54,7 @@ impl<T: Vertex + 'static + Send> VertexBuffer<T> {
     ///
     pub fn new<F, D>(facade: &F, data: D) -> VertexBuffer<T> where F: Facade, D: AsRef<[T]> {
         let buffer = BufferView::new(facade, data.as_ref(), BufferType::ArrayBuffer,
-                                    false).unwrap();
+                                    BufferMode::Immutable).unwrap();
         buffer.into()
     }
 
@@ -62,7 +63,7 @@ impl<T: Vertex + 'static + Send> VertexBuffer<T> {
     /// This function will create a buffer that is intended to be modified frequently.
     pub fn dynamic<F, D>(facade: &F, data: D) -> VertexBuffer<T> where F: Facade, D: AsRef<[T]> {
         let buffer = BufferView::new(facade, data.as_ref(), BufferType::ArrayBuffer,
-                                    true).unwrap();
+                                    BufferMode::Dynamic).unwrap();
         buffer.into()
     }
 
@@ -70,7 +71,8 @@ impl<T: Vertex + 'static + Send> VertexBuffer<T> {
     ///
     /// The parameter indicates the number of elements.
     pub fn empty<F>(facade: &F, elements: usize) -> VertexBuffer<T> where F: Facade {
-        let buffer = BufferView::empty(facade, BufferType::ArrayBuffer, elements, false).unwrap();
+        let buffer = BufferView::empty(facade, BufferType::ArrayBuffer, elements,
+                                       BufferMode::Immutable).unwrap();
         buffer.into()
     }
 
@@ -78,9 +80,32 @@ impl<T: Vertex + 'static + Send> VertexBuffer<T> {
     ///
     /// The parameter indicates the number of elements.
     pub fn empty_dynamic<F>(facade: &F, elements: usize) -> VertexBuffer<T> where F: Facade {
-        let buffer = BufferView::empty(facade, BufferType::ArrayBuffer, elements, true).unwrap();
+        let buffer = BufferView::empty(facade, BufferType::ArrayBuffer, elements,
+                                       BufferMode::Dynamic).unwrap();
         buffer.into()
     }
+
+    /// Builds a new vertex buffer with the given mode.
+    ///
+    /// Returns an error if the mode is not supported by the backend.
+    pub fn with_mode<F, D>(facade: &F, data: D, mode: BufferMode)
+                           -> Result<VertexBuffer<T>, BufferCreationError>
+                           where F: Facade, D: AsRef<[T]>
+    {
+        BufferView::new(facade, data.as_ref(), BufferType::ArrayBuffer, mode)
+            .map(|buffer| buffer.into())
+    }
+
+    /// Builds an empty vertex buffer with the given mode.
+    ///
+    /// The parameter indicates the number of elements. Returns an error if the mode is not
+    /// supported by the backend.
+    pub fn empty_with_mode<F>(facade: &F, elements: usize, mode: BufferMode)
+                              -> Result<VertexBuffer<T>, BufferCreationError> where F: Facade
+    {
+        BufferView::empty(facade, BufferType::ArrayBuffer, elements, mode)
+            .map(|buffer| buffer.into())
+    }
 }
 
 impl<T> VertexBuffer<T> where T: Send + Copy + 'static {
@@ -96,10 +121,10 @@ impl<T> VertexBuffer<T> where T: Send + Copy + 'static {
     ///
     /// let bindings = Cow::Owned(vec![(
     ///         Cow::Borrowed("position"), 0,
-    ///         glium::vertex::AttributeType::F32F32,
+    ///         glium::vertex::AttributeType::F32F32, false,
     ///     ), (
     ///         Cow::Borrowed("color"), 2 * ::std::mem::size_of::<f32>(),
-    ///         glium::vertex::AttributeType::F32,
+    ///         glium::vertex::AttributeType::F32, false,
     ///     ),
     /// ]);
     ///
@@ -121,7 +146,7 @@ impl<T> VertexBuffer<T> where T: Send + Copy + 'static {
     {
         VertexBuffer {
             buffer: BufferView::new(facade, &data, BufferType::ArrayBuffer,
-                                   false).unwrap(),
+                                   BufferMode::Immutable).unwrap(),
             bindings: bindings,
         }
     }
@@ -133,7 +158,7 @@ impl<T> VertexBuffer<T> where T: Send + Copy + 'static {
     {
         VertexBuffer {
             buffer: BufferView::new(facade, &data, BufferType::ArrayBuffer,
-                                   true).unwrap(),
+                                   BufferMode::Dynamic).unwrap(),
             bindings: bindings,
         }
     }
@@ -176,6 +201,23 @@ impl<T> VertexBuffer<T> where T: Send + Copy + 'static {
     ///
     /// Returns `None` if the backend doesn't support instancing.
     pub fn per_instance_if_supported(&self) -> Option<PerInstance> {
+        self.per_instance_with_divisor_if_supported(1)
+    }
+
+    /// Creates a marker that instructs glium to use multiple instances, and to use each
+    /// element of this buffer for `divisor` consecutive instances.
+    ///
+    /// For example with a divisor of `2`, the first element is used for the first two
+    /// instances, the second element for the next two instances, and so on.
+    ///
+    /// Returns `None` if the backend doesn't support instancing.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `divisor` is `0`.
+    pub fn per_instance_with_divisor_if_supported(&self, divisor: u32) -> Option<PerInstance> {
+        assert!(divisor >= 1, "The instance divisor must be at least 1");
+
         // TODO: don't check this here
         if !(self.buffer.get_context().get_version() >= &Version(Api::Gl, 3, 3)) &&
             !self.buffer.get_context().get_extensions().gl_arb_instanced_arrays
@@ -183,7 +225,7 @@ impl<T> VertexBuffer<T> where T: Send + Copy + 'static {
             return None;
         }
 
-        Some(PerInstance(self.buffer.as_slice_any(), &self.bindings))
+        Some(PerInstance(self.buffer.as_slice_any(), &self.bindings, divisor))
     }
 
     /// Creates a marker that instructs glium to use multiple instances.
@@ -200,6 +242,17 @@ impl<T> VertexBuffer<T> where T: Send + Copy + 'static {
     pub fn per_instance(&self) -> PerInstance {
         self.per_instance_if_supported().unwrap()
     }
+
+    /// Creates a marker that instructs glium to use multiple instances, and to use each
+    /// element of this buffer for `divisor` consecutive instances.
+    ///
+    /// # Features
+    ///
+    /// Only available if the `gl_instancing` feature is enabled.
+    #[cfg(feature = "gl_instancing")]
+    pub fn per_instance_with_divisor(&self, divisor: u32) -> PerInstance {
+        self.per_instance_with_divisor_if_supported(divisor).unwrap()
+    }
 }
 
 impl<T> From<BufferView<T>> for VertexBuffer<T> where T: Vertex + Send + Copy + 'static {
@@ -229,7 +282,7 @@ impl<T> DerefMut for VertexBuffer<T> where T: Send + Copy + 'static {
 
 impl<'a, T> IntoVerticesSource<'a> for &'a VertexBuffer<T> where T: Send + Copy + 'static {
     fn into_vertices_source(self) -> VerticesSource<'a> {
-        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, false)
+        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, None)
     }
 }
 
@@ -249,7 +302,7 @@ impl<'a, T> DerefMut for VertexBufferSlice<'a, T> where T: Send + Copy + 'static
 
 impl<'a, T> IntoVerticesSource<'a> for VertexBufferSlice<'a, T> where T: Copy + Send + 'static {
     fn into_vertices_source(self) -> VerticesSource<'a> {
-        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, false)
+        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, None)
     }
 }
 
@@ -296,6 +349,23 @@ impl VertexBufferAny {
     ///
     /// Returns `None` if the backend doesn't support instancing.
     pub fn per_instance_if_supported(&self) -> Option<PerInstance> {
+        self.per_instance_with_divisor_if_supported(1)
+    }
+
+    /// Creates a marker that instructs glium to use multiple instances, and to use each
+    /// element of this buffer for `divisor` consecutive instances.
+    ///
+    /// For example with a divisor of `2`, the first element is used for the first two
+    /// instances, the second element for the next two instances, and so on.
+    ///
+    /// Returns `None` if the backend doesn't support instancing.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `divisor` is `0`.
+    pub fn per_instance_with_divisor_if_supported(&self, divisor: u32) -> Option<PerInstance> {
+        assert!(divisor >= 1, "The instance divisor must be at least 1");
+
         // TODO: don't check this here
         if !(self.buffer.get_context().get_version() >= &Version(Api::Gl, 3, 3)) &&
             !self.buffer.get_context().get_extensions().gl_arb_instanced_arrays
@@ -303,7 +373,7 @@ impl VertexBufferAny {
             return None;
         }
 
-        Some(PerInstance(self.buffer.as_slice_any(), &self.bindings))
+        Some(PerInstance(self.buffer.as_slice_any(), &self.bindings, divisor))
     }
 
     /// Creates a marker that instructs glium to use multiple instances.
@@ -320,6 +390,17 @@ impl VertexBufferAny {
     pub fn per_instance(&self) -> PerInstance {
         self.per_instance_if_supported().unwrap()
     }
+
+    /// Creates a marker that instructs glium to use multiple instances, and to use each
+    /// element of this buffer for `divisor` consecutive instances.
+    ///
+    /// # Features
+    ///
+    /// Only available if the `gl_instancing` feature is enabled.
+    #[cfg(feature = "gl_instancing")]
+    pub fn per_instance_with_divisor(&self, divisor: u32) -> PerInstance {
+        self.per_instance_with_divisor_if_supported(divisor).unwrap()
+    }
 }
 
 impl<T> From<VertexBuffer<T>> for VertexBufferAny where T: Send + Copy + 'static {
@@ -337,6 +418,6 @@ impl<T> From<BufferView<T>> for VertexBufferAny where T: Vertex + Send + Copy +
 
 impl<'a> IntoVerticesSource<'a> for &'a VertexBufferAny {
     fn into_vertices_source(self) -> VerticesSource<'a> {
-        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, false)
+        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, None)
     }
 }
@@ -89,6 +89,22 @@ pub enum AttributeType {
     /// 4x4 matrix of `f64`s
     /// Warning: using `f64`s can be very slow.
     F64x4x4,
+    /// A 32-bits value containing three signed 10-bits components followed by a signed 2-bits
+    /// component, from the least significant bits to the most significant bits.
+    ///
+    /// Can only be read as a `vec4` in the shader. Usually used with normalization, for
+    /// example to store normals.
+    I2I10I10I10Rev,
+    /// A 32-bits value containing three unsigned 10-bits components followed by an unsigned
+    /// 2-bits component, from the least significant bits to the most significant bits.
+    ///
+    /// Can only be read as a `vec4` in the shader.
+    U2U10U10U10Rev,
+    /// A 32-bits value containing two unsigned 11-bits floats followed by an unsigned 10-bits
+    /// float, from the least significant bits to the most significant bits.
+    ///
+    /// Can only be read as a `vec3` in the shader.
+    F10F11F11UnsignedIntRev,
 }
 
 impl AttributeType {
@@ -145,6 +161,9 @@ impl AttributeType {
             AttributeType::F64x4x2 => 8 * mem::size_of::<f64>(),
             AttributeType::F64x4x3 => 12 * mem::size_of::<f64>(),
             AttributeType::F64x4x4 => 16 * mem::size_of::<f64>(),
+            AttributeType::I2I10I10I10Rev => 1 * mem::size_of::<u32>(),
+            AttributeType::U2U10U10U10Rev => 1 * mem::size_of::<u32>(),
+            AttributeType::F10F11F11UnsignedIntRev => 1 * mem::size_of::<u32>(),
         }
     }
 
@@ -201,6 +220,9 @@ impl AttributeType {
             AttributeType::F64x4x2 => 8,
             AttributeType::F64x4x3 => 12,
             AttributeType::F64x4x4 => 16,
+            AttributeType::I2I10I10I10Rev => 4,
+            AttributeType::U2U10U10U10Rev => 4,
+            AttributeType::F10F11F11UnsignedIntRev => 3,
         }
     }
 }
@@ -209,7 +231,12 @@ impl AttributeType {
 ///
 /// The first element is the name of the binding, the second element is the offset
 /// from the start of each vertex to this element, and the third element is the type.
-pub type VertexFormat = Cow<'static, [(Cow<'static, str>, usize, AttributeType)]>;
+///
+/// The fourth element indicates whether integer values should be normalized when they are read
+/// as floats by the shader. If `true`, unsigned values are mapped to the `[0.0, 1.0]` range and
+/// signed values to the `[-1.0, 1.0]` range. If `false`, they are converted directly. This is
+/// ignored for floating-point types and for integer attributes in the shader.
+pub type VertexFormat = Cow<'static, [(Cow<'static, str>, usize, AttributeType, bool)]>;
 
 unsafe impl Attribute for i8 {
     fn get_type() -> AttributeType {
@@ -583,6 +610,57 @@ unsafe impl Attribute for [[f64; 4]; 4] {
     }
 }
 
+/// Three signed 10-bits components and a signed 2-bits component packed in a `u32`. Corresponds
+/// to `AttributeType::I2I10I10I10Rev`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PackedI2I10I10I10(pub u32);
+
+impl PackedI2I10I10I10 {
+    /// Packs four components. The first three must be in the `[-512, 511]` range and the last
+    /// one in the `[-2, 1]` range, otherwise they are truncated.
+    pub fn new(x: i16, y: i16, z: i16, w: i8) -> PackedI2I10I10I10 {
+        PackedI2I10I10I10((x as u32 & 0x3ff) | ((y as u32 & 0x3ff) << 10) |
+                          ((z as u32 & 0x3ff) << 20) | ((w as u32 & 0x3) << 30))
+    }
+}
+
+unsafe impl Attribute for PackedI2I10I10I10 {
+    fn get_type() -> AttributeType {
+        AttributeType::I2I10I10I10Rev
+    }
+}
+
+/// Three unsigned 10-bits components and an unsigned 2-bits component packed in a `u32`.
+/// Corresponds to `AttributeType::U2U10U10U10Rev`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PackedU2U10U10U10(pub u32);
+
+impl PackedU2U10U10U10 {
+    /// Packs four components. The first three must be lower than 1024 and the last one lower
+    /// than 4, otherwise they are truncated.
+    pub fn new(x: u16, y: u16, z: u16, w: u8) -> PackedU2U10U10U10 {
+        PackedU2U10U10U10((x as u32 & 0x3ff) | ((y as u32 & 0x3ff) << 10) |
+                          ((z as u32 & 0x3ff) << 20) | ((w as u32 & 0x3) << 30))
+    }
+}
+
+unsafe impl Attribute for PackedU2U10U10U10 {
+    fn get_type() -> AttributeType {
+        AttributeType::U2U10U10U10Rev
+    }
+}
+
+/// Two unsigned 11-bits floats and an unsigned 10-bits float packed in a `u32`. Corresponds to
+/// `AttributeType::F10F11F11UnsignedIntRev`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PackedF10F11F11(pub u32);
+
+unsafe impl Attribute for PackedF10F11F11 {
+    fn get_type() -> AttributeType {
+        AttributeType::F10F11F11UnsignedIntRev
+    }
+}
+
 
 #[cfg(feature="cgmath")]
 unsafe impl Attribute for cgmath::Point2<i8> {
@@ -120,6 +120,10 @@ be the same, or a `DrawError::VerticesSourcesLengthMismatch` will be produced.
 In all situation, the length of all per-instance sources must match, or
 `DrawError::InstancesCountMismatch` will be retured.
 
+The attributes of the vertex formats must match the attributes of the program, or drawing
+returns `DrawError::AttributeMissing` or `DrawError::AttributeTypeMismatch`. You can use an
+`AttributesReport` to compare them before drawing.
+
 # Transform feedback
 
 Transform feedback allows you to write in a buffer the list of primitives that are generated by
@@ -131,6 +135,11 @@ type and a program. Then you must pass the `&TransformFeedbackSession` to the dr
 The program you use when drawing must be the same as you the one you created the session
 with, or else you will get an error.
 
+If the program writes to multiple buffers, use `TransformFeedbackSession::with_outputs()`
+instead. Once you have finished writing, `TransformFeedbackSession::finish()` returns a
+`TransformFeedbackVertices` that can be used as a vertices source to draw exactly the vertices
+that have been written.
+
 */
 use std::iter::Chain;
 use std::option::IntoIter;
@@ -138,12 +147,19 @@ use std::option::IntoIter;
 pub use self::buffer::{VertexBuffer, VertexBufferAny};
 pub use self::buffer::VertexBufferSlice;
 pub use self::format::{AttributeType, VertexFormat};
+pub use self::format::{PackedI2I10I10I10, PackedU2U10U10U10, PackedF10F11F11};
+pub use self::report::{AttributesReport, AttributeReport, AttributeStatus};
 pub use self::transform_feedback::{is_transform_feedback_supported, TransformFeedbackSession};
+pub use self::transform_feedback::is_transform_feedback_pause_supported;
+pub use self::transform_feedback::is_draw_transform_feedback_supported;
+pub use self::transform_feedback::{TransformFeedbackOutput, TransformFeedbackVertices};
+pub use self::transform_feedback::TransformFeedbackSessionCreationError;
 
 use buffer::BufferViewAnySlice;
 
 mod buffer;
 mod format;
+mod report;
 mod transform_feedback;
 
 /// Describes the source to use for the vertices when drawing.
@@ -153,9 +169,16 @@ pub enum VerticesSource<'a> {
     ///
     /// The second parameter is the number of vertices in the buffer.
     ///
-    /// The third parameter tells whether or not this buffer is "per instance" (true) or
-    /// "per vertex" (false).
-    VertexBuffer(BufferViewAnySlice<'a>, &'a VertexFormat, bool),
+    /// The third parameter is `None` if this buffer is "per vertex", or contains the instance
+    /// divisor if this buffer is "per instance". With a divisor of `n`, each element of the
+    /// buffer is used for `n` consecutive instances.
+    VertexBuffer(BufferViewAnySlice<'a>, &'a VertexFormat, Option<u32>),
+
+    /// A buffer written by a transform feedback session.
+    ///
+    /// The number of vertices to draw is the number of vertices that have been written by the
+    /// session, and is never read back by the CPU.
+    TransformFeedback(BufferViewAnySlice<'a>, &'a VertexFormat, &'a TransformFeedbackVertices<'a>),
 
     /// A marker indicating a "phantom list of attributes".
     Marker {
@@ -204,11 +227,13 @@ impl<'a> IntoVerticesSource<'a> for EmptyInstanceAttributes {
 }
 
 /// Marker that instructs glium that the buffer is to be used per instance.
-pub struct PerInstance<'a>(BufferViewAnySlice<'a>, &'a VertexFormat);
+///
+/// The third element is the instance divisor.
+pub struct PerInstance<'a>(BufferViewAnySlice<'a>, &'a VertexFormat, u32);
 
 impl<'a> IntoVerticesSource<'a> for PerInstance<'a> {
     fn into_vertices_source(self) -> VerticesSource<'a> {
-        VerticesSource::VertexBuffer(self.0, self.1, true)
+        VerticesSource::VertexBuffer(self.0, self.1, Some(self.2))
     }
 }
 
@@ -0,0 +1,200 @@
+use std::borrow::Borrow;
+use std::fmt;
+
+use DrawError;
+use program::Program;
+use vertex::{AttributeType, VertexFormat};
+use vertex_array_object;
+
+/// Comparison between the attributes of a program and the vertex formats of some vertices
+/// sources.
+///
+/// Drawing fails with `DrawError::AttributeMissing` or `DrawError::AttributeTypeMismatch` when
+/// `first_error` returns `Some`. You can build this report before drawing and print it in order
+/// to find out which attributes are wrong.
+///
+/// # Example
+///
+/// ```no_run
+/// # let program: glium::Program = unsafe { std::mem::uninitialized() };
+/// # let vertex_buffer: glium::vertex::VertexBufferAny = unsafe { std::mem::uninitialized() };
+/// let report = glium::vertex::AttributesReport::new(&program, &[vertex_buffer.get_bindings()]);
+///
+/// if report.first_error().is_some() {
+///     println!("{}", report);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AttributesReport {
+    /// One entry for each attribute of the program, followed by one entry for each attribute
+    /// of the vertex formats that the program doesn't use. Each group is sorted by name.
+    pub entries: Vec<AttributeReport>,
+}
+
+/// Comparison between an attribute of a program and the vertex formats.
+#[derive(Debug, Clone)]
+pub struct AttributeReport {
+    /// Name of the attribute.
+    pub name: String,
+
+    /// Type and number of elements of the attribute in the program, or `None` if the program
+    /// doesn't use this attribute.
+    pub program_type: Option<(AttributeType, usize)>,
+
+    /// Index of the vertex format that contains the attribute and type of the attribute in
+    /// this format, or `None` if no format contains it.
+    pub source: Option<(usize, AttributeType)>,
+
+    /// Result of the comparison.
+    pub status: AttributeStatus,
+}
+
+/// Result of the comparison between an attribute of a program and the vertex formats.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AttributeStatus {
+    /// The attribute is in a vertex format with a type that the program can read.
+    Ok,
+
+    /// The attribute is in a vertex format, but the program doesn't use it. This is not an
+    /// error.
+    Unused,
+
+    /// The program uses the attribute, but it is in none of the vertex formats.
+    Missing,
+
+    /// The type of the attribute in the vertex format doesn't match what the program requires.
+    TypeMismatch,
+}
+
+impl AttributesReport {
+    /// Compares the attributes of `program` with a list of vertex formats, one for each vertices
+    /// source that you would pass when drawing.
+    ///
+    /// If multiple formats contain the same attribute, the first one is used.
+    pub fn new(program: &Program, formats: &[&VertexFormat]) -> AttributesReport {
+        let find = |name: &str| {
+            for (index, format) in formats.iter().enumerate() {
+                let entry = format.iter().find(|e| Borrow::<str>::borrow(&e.0) == name);
+
+                if let Some(&(_, _, ty, _)) = entry {
+                    return Some((index, ty));
+                }
+            }
+
+            None
+        };
+
+        let mut entries = Vec::new();
+
+        let mut program_attributes = program.attributes().collect::<Vec<_>>();
+        program_attributes.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (name, attribute) in program_attributes {
+            let source = find(&name[..]);
+
+            let status = match source {
+                None => AttributeStatus::Missing,
+                Some((_, ty)) => {
+                    if attribute.size == 1 &&
+                       vertex_array_object::is_attribute_type_compatible(attribute.ty, ty)
+                    {
+                        AttributeStatus::Ok
+                    } else {
+                        AttributeStatus::TypeMismatch
+                    }
+                },
+            };
+
+            entries.push(AttributeReport {
+                name: name.clone(),
+                program_type: Some((attribute.ty, attribute.size)),
+                source: source,
+                status: status,
+            });
+        }
+
+        let mut unused = Vec::new();
+        for (index, format) in formats.iter().enumerate() {
+            for &(ref name, _, ty, _) in format.iter() {
+                let name = Borrow::<str>::borrow(name);
+
+                if program.get_attribute(name).is_some() ||
+                   unused.iter().any(|e: &AttributeReport| e.name == name)
+                {
+                    continue;
+                }
+
+                unused.push(AttributeReport {
+                    name: name.to_string(),
+                    program_type: None,
+                    source: Some((index, ty)),
+                    status: AttributeStatus::Unused,
+                });
+            }
+        }
+
+        unused.sort_by(|a, b| a.name.cmp(&b.name));
+        entries.extend(unused.into_iter());
+
+        AttributesReport {
+            entries: entries,
+        }
+    }
+
+    /// Returns the error that drawing with these vertex formats would produce, if any.
+    ///
+    /// The `source` of `DrawError::AttributeTypeMismatch` is the index of the format in the list
+    /// passed to `new`.
+    pub fn first_error(&self) -> Option<DrawError> {
+        for entry in self.entries.iter() {
+            match (entry.status, entry.program_type, entry.source) {
+                (AttributeStatus::Missing, _, _) => {
+                    return Some(DrawError::AttributeMissing {
+                        name: entry.name.clone(),
+                    });
+                },
+
+                (AttributeStatus::TypeMismatch, Some((expected, _)), Some((source, obtained))) => {
+                    return Some(DrawError::AttributeTypeMismatch {
+                        name: entry.name.clone(),
+                        expected: expected,
+                        obtained: obtained,
+                        source: source,
+                    });
+                },
+
+                _ => ()
+            }
+        }
+
+        None
+    }
+}
+
+impl fmt::Display for AttributesReport {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for entry in self.entries.iter() {
+            try!(write!(fmt, "`{}`: ", entry.name));
+
+            match entry.program_type {
+                Some((ty, 1)) => try!(write!(fmt, "program expects {:?}", ty)),
+                Some((ty, size)) => try!(write!(fmt, "program expects {:?}[{}]", ty, size)),
+                None => try!(write!(fmt, "unused by the program")),
+            }
+
+            match entry.source {
+                Some((index, ty)) => try!(write!(fmt, ", source {} provides {:?}", index, ty)),
+                None => try!(write!(fmt, ", provided by no source")),
+            }
+
+            match entry.status {
+                AttributeStatus::Ok => try!(writeln!(fmt, " (ok)")),
+                AttributeStatus::Unused => try!(writeln!(fmt, " (unused)")),
+                AttributeStatus::Missing => try!(writeln!(fmt, " (error: missing)")),
+                AttributeStatus::TypeMismatch => try!(writeln!(fmt, " (error: type mismatch)")),
+            }
+        }
+
+        Ok(())
+    }
+}
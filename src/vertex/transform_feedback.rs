@@ -1,17 +1,22 @@
+use std::fmt;
 use std::mem;
+use std::rc::Rc;
+use std::vec::IntoIter;
 
 use version::Api;
 use version::Version;
-use context::CommandContext;
+use context::{CommandContext, Context};
 use backend::Facade;
 use BufferViewExt;
 use ContextExt;
+use DrawError;
+use GlObject;
 use TransformFeedbackSessionExt;
 use buffer::{BufferView, BufferViewAnySlice};
 use index::PrimitiveType;
 use program::OutputPrimitives;
 use program::Program;
-use vertex::Vertex;
+use vertex::{Vertex, VertexFormat, VerticesSource, MultiVerticesSource};
 
 use gl;
 
@@ -21,9 +26,9 @@ use gl;
 /// different fragment shaders.
 ///
 /// To use transform feedback, you must create a transform feedback session. A transform feedback
-/// session mutably borrows the buffer where the data will be written. Each draw command submitted
+/// session mutably borrows the buffers where the data will be written. Each draw command submitted
 /// with a session will continue to append data after the data written by the previous draw command.
-/// You can only use the data when the session is destroyed.
+/// You can only use the data when the session is destroyed or finished.
 ///
 /// # Notes
 ///
@@ -33,14 +38,18 @@ use gl;
 ///   vertex shader's source code (for recent OpenGL versions only) or by indicating a list of
 ///   vertex attributes when building the program.
 ///
-/// - A transform feedback session is bound to a specific program and buffer. You can't switch
+/// - A transform feedback session is bound to a specific program and buffers. You can't switch
 ///   them once the session has been created. An error is generated if you draw with a different
 ///   program than the one you created the session with.
 ///
+/// - If the program uses `TransformFeedbackMode::Separate`, it writes each varying to a different
+///   buffer. Use `with_outputs` to create a session with one buffer per varying.
+///
 /// - The transform feedback process doesn't necessarly fill the whole buffer. To retreive the
 ///   number of vertices that are written to the buffer, use a query object (see the
 ///   `draw_parameters` module). It is however usually easy to determine in advance the number of
-///   vertices that will be written based on the input data.
+///   vertices that will be written based on the input data. You can also draw the vertices that
+///   have been written without knowing their number with `finish`.
 ///
 /// - The buffer will obtain either a list of points, a list of lines (two vertices), or a list of
 ///   triangles (three vertices). If you draw a triangle strip or a triangle fan for example,
@@ -51,6 +60,11 @@ use gl;
 ///   to be pushed in the buffer after the existing data. However you must always use the same type
 ///   of primitives and the same program.
 ///
+/// - If the backend supports it, the session is paused when you draw without it or with another
+///   session, and resumed the next time you draw with it. This means that you can interleave
+///   draws with multiple sessions. Otherwise, the next draw starts writing again at the
+///   beginning of the buffers.
+///
 /// # Example
 ///
 /// ```no_run
@@ -87,10 +101,20 @@ use gl;
 /// println!("List of generated vertices: {:?}", result);
 /// # }
 /// ```
-#[derive(Debug)]
 pub struct TransformFeedbackSession<'a> {
-    buffer: BufferViewAnySlice<'a>,
+    outputs: Vec<TransformFeedbackOutput<'a>>,
     program: &'a Program,
+    context: Rc<Context>,
+    // transform feedback object, or 0 if they are not supported
+    id: gl::types::GLuint,
+}
+
+/// A buffer where a transform feedback session writes its output.
+#[derive(Debug)]
+pub struct TransformFeedbackOutput<'a> {
+    buffer: BufferViewAnySlice<'a>,
+    bindings: VertexFormat,
+    stride: usize,
 }
 
 /// Error that can happen when creating a `TransformFeedbackSession`.
@@ -98,9 +122,12 @@ pub struct TransformFeedbackSession<'a> {
 pub enum TransformFeedbackSessionCreationError {
     /// Transform feedback is not supported by the OpenGL implementation.
     NotSupported,
-    
+
     /// The format of the output doesn't match what the program is expected to output.
     WrongVertexFormat,
+
+    /// The number of buffers doesn't match the number of buffers the program writes to.
+    WrongBuffersCount,
 }
 
 /// Returns true if transform feedback is supported by the OpenGL implementation.
@@ -112,66 +139,216 @@ pub fn is_transform_feedback_supported<F>(facade: &F) -> bool where F: Facade {
     context.get_extensions().gl_ext_transform_feedback
 }
 
+/// Returns true if transform feedback sessions can be paused and resumed by the OpenGL
+/// implementation.
+pub fn is_transform_feedback_pause_supported<F>(facade: &F) -> bool where F: Facade {
+    let context = facade.get_context();
+
+    context.get_version() >= &Version(Api::Gl, 4, 0) ||
+    context.get_version() >= &Version(Api::GlEs, 3, 0) ||
+    context.get_extensions().gl_arb_transform_feedback2
+}
+
+/// Returns true if the vertices written by a transform feedback session can be drawn with
+/// `TransformFeedbackSession::finish`.
+pub fn is_draw_transform_feedback_supported<F>(facade: &F) -> bool where F: Facade {
+    let context = facade.get_context();
+
+    context.get_version() >= &Version(Api::Gl, 4, 0) ||
+    context.get_extensions().gl_arb_transform_feedback2
+}
+
+impl<'a> TransformFeedbackOutput<'a> {
+    /// Builds an output that writes to a buffer.
+    pub fn new<V>(buffer: &'a mut BufferView<V>) -> TransformFeedbackOutput<'a>
+                  where V: Vertex + Copy + Send + 'static
+    {
+        TransformFeedbackOutput {
+            buffer: buffer.as_slice_any(),
+            bindings: <V as Vertex>::build_bindings(),
+            stride: mem::size_of::<V>(),
+        }
+    }
+}
+
 impl<'a> TransformFeedbackSession<'a> {
-    /// Builds a new transform feedback session.
-    ///
-    /// TODO: this constructor should ultimately support passing multiple buffers of different
-    ///       types
+    /// Builds a new transform feedback session that writes to a single buffer.
     pub fn new<F, V>(facade: &F, program: &'a Program, buffer: &'a mut BufferView<V>)
                      -> Result<TransformFeedbackSession<'a>, TransformFeedbackSessionCreationError>
                      where F: Facade, V: Vertex + Copy + Send + 'static
+    {
+        TransformFeedbackSession::with_outputs(facade, program,
+                                               vec![TransformFeedbackOutput::new(buffer)])
+    }
+
+    /// Builds a new transform feedback session that writes to multiple buffers.
+    ///
+    /// There must be one output per buffer of `program.get_transform_feedback_buffers()`, in the
+    /// same order, and each output must match the corresponding buffer of the program.
+    pub fn with_outputs<F>(facade: &F, program: &'a Program,
+                           outputs: Vec<TransformFeedbackOutput<'a>>)
+                           -> Result<TransformFeedbackSession<'a>,
+                                     TransformFeedbackSessionCreationError>
+                           where F: Facade
     {
         if !is_transform_feedback_supported(facade) {
             return Err(TransformFeedbackSessionCreationError::NotSupported);
         }
 
-        if !program.transform_feedback_matches(&<V as Vertex>::build_bindings(), 
-                                               mem::size_of::<V>())
-        {
-            return Err(TransformFeedbackSessionCreationError::WrongVertexFormat); 
+        if outputs.len() != program.get_transform_feedback_buffers().len() {
+            return Err(TransformFeedbackSessionCreationError::WrongBuffersCount);
         }
 
+        for (index, output) in outputs.iter().enumerate() {
+            if !program.transform_feedback_buffer_matches(index, &output.bindings, output.stride) {
+                return Err(TransformFeedbackSessionCreationError::WrongVertexFormat);
+            }
+        }
+
+        let id = if is_transform_feedback_pause_supported(facade) {
+            let mut ctxt = facade.get_context().make_current();
+
+            unsafe {
+                let mut id = mem::uninitialized();
+                ctxt.gl.GenTransformFeedbacks(1, &mut id);
+                id
+            }
+
+        } else {
+            0
+        };
+
         Ok(TransformFeedbackSession {
-            buffer: buffer.as_slice_any(),
+            outputs: outputs,
             program: program,
+            context: facade.get_context().clone(),
+            id: id,
+        })
+    }
+
+    /// Ends the session and returns an object that can be used as a vertices source to draw
+    /// exactly the vertices that have been written, without reading their number back.
+    ///
+    /// Returns `Err` with the session if this is not supported by the backend. See
+    /// `is_draw_transform_feedback_supported`.
+    pub fn finish(mut self)
+                  -> Result<TransformFeedbackVertices<'a>, TransformFeedbackSession<'a>>
+    {
+        if !is_draw_transform_feedback_supported(&self.context) || self.id == 0 {
+            return Err(self);
+        }
+
+        {
+            let mut ctxt = self.context.make_current();
+            unsafe { end_transform_feedback_object(&mut ctxt, self.id); }
+        }
+
+        Ok(TransformFeedbackVertices {
+            outputs: mem::replace(&mut self.outputs, Vec::new()),
+            context: self.context.clone(),
+            id: mem::replace(&mut self.id, 0),
         })
     }
+
+    /// Returns true if this session is the one that is bound to the context.
+    fn is_current(&self, ctxt: &CommandContext) -> bool {
+        if self.id != 0 {
+            return ctxt.state.transform_feedback == self.id;
+        }
+
+        // without transform feedback objects, we compare the current buffers and program
+        if ctxt.state.transform_feedback != 0 || ctxt.state.transform_feedback_enabled.is_none() {
+            return false;
+        }
+
+        if ctxt.state.program != self.program.get_id() {
+            return false;
+        }
+
+        self.outputs.iter().enumerate().all(|(index, output)| {
+            match ctxt.state.indexed_transform_feedback_buffer_bindings.get(index) {
+                Some(binding) => {
+                    binding.buffer == output.buffer.get_buffer_id() &&
+                    binding.offset == output.buffer.get_offset_bytes() as gl::types::GLintptr &&
+                    binding.size == output.buffer.get_size() as gl::types::GLsizeiptr
+                },
+                None => false,
+            }
+        })
+    }
+}
+
+impl<'a> fmt::Debug for TransformFeedbackSession<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "TransformFeedbackSession({:?})", self.outputs)
+    }
 }
 
 impl<'a> TransformFeedbackSessionExt for TransformFeedbackSession<'a> {
-    fn bind(&self, mut ctxt: &mut CommandContext, draw_primitives: PrimitiveType) {
-        // TODO: check that the state matches what is required
-        if ctxt.state.transform_feedback_enabled.is_some() {
-            unimplemented!();
+    fn bind(&self, ctxt: &mut CommandContext, program: &Program,
+            draw_primitives: PrimitiveType) -> Result<(), DrawError>
+    {
+        if self.program.get_id() != program.get_id() {
+            return Err(DrawError::TransformFeedbackProgramMismatch);
+        }
+
+        let primitives = match (self.program.get_output_primitives(), draw_primitives) {
+            (Some(OutputPrimitives::Points), _) => gl::POINTS,
+            (Some(OutputPrimitives::Lines), _) => gl::LINES,
+            (Some(OutputPrimitives::Triangles), _) => gl::TRIANGLES,
+            // the tessellation of quads generates triangles
+            (Some(OutputPrimitives::Quads), _) => gl::TRIANGLES,
+            (None, PrimitiveType::Points) => gl::POINTS,
+            (None, PrimitiveType::LinesList) => gl::LINES,
+            (None, PrimitiveType::LinesListAdjacency) => gl::LINES,
+            (None, PrimitiveType::LineStrip) => gl::LINES,
+            (None, PrimitiveType::LineStripAdjacency) => gl::LINES,
+            (None, PrimitiveType::LineLoop) => gl::LINES,
+            (None, PrimitiveType::TrianglesList) => gl::TRIANGLES,
+            (None, PrimitiveType::TrianglesListAdjacency) => gl::TRIANGLES,
+            (None, PrimitiveType::TriangleStrip) => gl::TRIANGLES,
+            (None, PrimitiveType::TriangleStripAdjacency) => gl::TRIANGLES,
+            (None, PrimitiveType::TriangleFan) => gl::TRIANGLES,
+            (None, PrimitiveType::Patches { .. }) => unreachable!(),
+        };
+
+        // the session that is currently active, if any, is paused and stays in its transform
+        // feedback object
+        if ctxt.state.transform_feedback != self.id {
+            unsafe { bind_transform_feedback_object(ctxt, self.id); }
         }
 
+        // continuing the session if it has already been started
+        if self.is_current(ctxt) {
+            if let Some(current) = ctxt.state.transform_feedback_enabled {
+                if current != primitives {
+                    return Err(DrawError::TransformFeedbackPrimitivesMismatch);
+                }
+
+                if ctxt.state.transform_feedback_paused {
+                    unsafe { ctxt.gl.ResumeTransformFeedback(); }
+                    ctxt.state.transform_feedback_paused = false;
+                }
+
+                return Ok(());
+            }
+        }
+
+        // ending the previous session, whose state is lost
+        TransformFeedbackSession::unbind(ctxt);
+
         // FIXME: use the memory barrier system
-        self.buffer.bind_to_transform_feedback(ctxt, 0);
+        for (index, output) in self.outputs.iter().enumerate() {
+            output.buffer.bind_to_transform_feedback(ctxt, index as gl::types::GLuint);
+        }
 
         unsafe {
-            let primitives = match (self.program.get_output_primitives(), draw_primitives) {
-                (Some(OutputPrimitives::Points), _) => gl::POINTS,
-                (Some(OutputPrimitives::Lines), _) => gl::LINES,
-                (Some(OutputPrimitives::Triangles), _) => gl::TRIANGLES,
-                (Some(OutputPrimitives::Quads), _) => panic!(),         // TODO: return a proper error
-                (None, PrimitiveType::Points) => gl::POINTS,
-                (None, PrimitiveType::LinesList) => gl::LINES,
-                (None, PrimitiveType::LinesListAdjacency) => gl::LINES,
-                (None, PrimitiveType::LineStrip) => gl::LINES,
-                (None, PrimitiveType::LineStripAdjacency) => gl::LINES,
-                (None, PrimitiveType::LineLoop) => gl::LINES,
-                (None, PrimitiveType::TrianglesList) => gl::TRIANGLES,
-                (None, PrimitiveType::TrianglesListAdjacency) => gl::TRIANGLES,
-                (None, PrimitiveType::TriangleStrip) => gl::TRIANGLES,
-                (None, PrimitiveType::TriangleStripAdjacency) => gl::TRIANGLES,
-                (None, PrimitiveType::TriangleFan) => gl::TRIANGLES,
-                (None, PrimitiveType::Patches { .. }) => unreachable!(),
-            };
-
             ctxt.gl.BeginTransformFeedback(primitives);
             ctxt.state.transform_feedback_enabled = Some(primitives);
             ctxt.state.transform_feedback_paused = false;
         }
+
+        Ok(())
     }
 
     fn unbind(mut ctxt: &mut CommandContext) {
@@ -186,6 +363,21 @@ impl<'a> TransformFeedbackSessionExt for TransformFeedbackSession<'a> {
         }
     }
 
+    fn pause(ctxt: &mut CommandContext) {
+        if ctxt.state.transform_feedback_enabled.is_none() || ctxt.state.transform_feedback_paused {
+            return;
+        }
+
+        if ctxt.version >= &Version(Api::Gl, 4, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+           ctxt.extensions.gl_arb_transform_feedback2
+        {
+            unsafe { ctxt.gl.PauseTransformFeedback(); }
+            ctxt.state.transform_feedback_paused = true;
+        } else {
+            TransformFeedbackSession::unbind(ctxt);
+        }
+    }
+
     fn ensure_buffer_out_of_transform_feedback(mut ctxt: &mut CommandContext, buffer: gl::types::GLuint) {
         if ctxt.state.transform_feedback_enabled.is_none() {
             return;
@@ -209,5 +401,133 @@ impl<'a> Drop for TransformFeedbackSession<'a> {
     fn drop(&mut self) {
         // FIXME: since the session can be mem::forget'ed, the code in buffer/alloc.rs should make
         //        sure that the buffer isn't in use for transform feedback
+
+        if self.id != 0 {
+            let mut ctxt = self.context.make_current();
+            unsafe { destroy_transform_feedback_object(&mut ctxt, self.id); }
+        }
+    }
+}
+
+/// The vertices written by a transform feedback session.
+///
+/// This object can be used as a vertices source, in which case the number of vertices that are
+/// drawn is the number of vertices that have been written during the session. This number is
+/// never read back by the CPU.
+///
+/// If the session had multiple outputs, each output is a separate vertices source. You can't
+/// use indices with this source.
+///
+/// # Example
+///
+/// ```no_run
+/// # use glium::Surface;
+/// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+/// # let program: glium::Program = unsafe { std::mem::uninitialized() };
+/// # let session: glium::vertex::TransformFeedbackSession = unsafe { std::mem::uninitialized() };
+/// let vertices = session.finish().unwrap();
+///
+/// let mut target = display.draw();
+/// target.draw(&vertices, &glium::index::NoIndices(glium::index::PrimitiveType::Points),
+///             &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+/// target.finish().unwrap();
+/// ```
+pub struct TransformFeedbackVertices<'a> {
+    outputs: Vec<TransformFeedbackOutput<'a>>,
+    context: Rc<Context>,
+    id: gl::types::GLuint,
+}
+
+impl<'a> fmt::Debug for TransformFeedbackVertices<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "TransformFeedbackVertices({:?})", self.outputs)
+    }
+}
+
+impl<'a> GlObject for TransformFeedbackVertices<'a> {
+    type Id = gl::types::GLuint;
+
+    fn get_id(&self) -> gl::types::GLuint {
+        self.id
     }
 }
+
+impl<'a, 'b> MultiVerticesSource<'b> for &'b TransformFeedbackVertices<'a> {
+    type Iterator = IntoIter<VerticesSource<'b>>;
+
+    fn iter(self) -> IntoIter<VerticesSource<'b>> {
+        self.outputs.iter().map(|output| {
+            VerticesSource::TransformFeedback(output.buffer.clone(), &output.bindings, self)
+        }).collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<'a> Drop for TransformFeedbackVertices<'a> {
+    fn drop(&mut self) {
+        let mut ctxt = self.context.make_current();
+        unsafe { destroy_transform_feedback_object(&mut ctxt, self.id); }
+    }
+}
+
+/// Binds a transform feedback object to `GL_TRANSFORM_FEEDBACK`.
+///
+/// If the transform feedback of the current object is active, it is paused and resumed the next
+/// time the object is bound with `TransformFeedbackSessionExt::bind`.
+unsafe fn bind_transform_feedback_object(ctxt: &mut CommandContext, id: gl::types::GLuint) {
+    if let Some(primitives) = ctxt.state.transform_feedback_enabled {
+        if !ctxt.state.transform_feedback_paused {
+            ctxt.gl.PauseTransformFeedback();
+        }
+
+        let current = ctxt.state.transform_feedback;
+        ctxt.state.paused_transform_feedback_objects.push((current, primitives));
+    }
+
+    ctxt.gl.BindTransformFeedback(gl::TRANSFORM_FEEDBACK, id);
+    ctxt.state.transform_feedback = id;
+
+    let position = ctxt.state.paused_transform_feedback_objects.iter()
+                                                               .position(|&(i, _)| i == id);
+    match position {
+        Some(position) => {
+            let (_, primitives) = ctxt.state.paused_transform_feedback_objects.remove(position);
+            ctxt.state.transform_feedback_enabled = Some(primitives);
+            ctxt.state.transform_feedback_paused = true;
+        },
+        None => {
+            ctxt.state.transform_feedback_enabled = None;
+            ctxt.state.transform_feedback_paused = false;
+        },
+    }
+
+    // the indexed bindings are part of the transform feedback object
+    for elem in ctxt.state.indexed_transform_feedback_buffer_bindings.iter_mut() {
+        elem.buffer = 0;
+        elem.offset = 0;
+        elem.size = 0;
+    }
+}
+
+/// Ends the transform feedback of the object if it is active, even if the object is not bound.
+unsafe fn end_transform_feedback_object(ctxt: &mut CommandContext, id: gl::types::GLuint) {
+    if ctxt.state.transform_feedback != id {
+        if !ctxt.state.paused_transform_feedback_objects.iter().any(|&(i, _)| i == id) {
+            return;
+        }
+
+        bind_transform_feedback_object(ctxt, id);
+    }
+
+    TransformFeedbackSession::unbind(ctxt);
+}
+
+/// Ends the transform feedback if it uses the object, and destroys the object.
+unsafe fn destroy_transform_feedback_object(ctxt: &mut CommandContext, id: gl::types::GLuint) {
+    end_transform_feedback_object(ctxt, id);
+
+    if ctxt.state.transform_feedback == id {
+        bind_transform_feedback_object(ctxt, 0);
+    }
+
+    ctxt.gl.DeleteTransformFeedbacks(1, &id);
+}
@@ -3,11 +3,13 @@ use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::mem;
 
+use DrawError;
 use Handle;
 use buffer::BufferViewAnySlice;
 use program::Program;
 use vertex::AttributeType;
 use vertex::VertexFormat;
+use vertex::AttributesReport;
 use GlObject;
 use BufferViewExt;
 
@@ -29,6 +31,8 @@ pub struct Binder<'a, 'b, 'c: 'b> {
     program: &'a Program,
     element_array_buffer: Option<BufferViewAnySlice<'a>>,
     vertex_buffers: Vec<(gl::types::GLuint, VertexFormat, usize, usize, Option<u32>)>,
+    // index of the vertices source of each element of `vertex_buffers`
+    sources: Vec<usize>,
 }
 
 impl VertexAttributesSystem {
@@ -52,6 +56,7 @@ impl VertexAttributesSystem {
             program: program,
             element_array_buffer: indices,
             vertex_buffers: Vec::with_capacity(1),
+            sources: Vec::with_capacity(1),
         }
     }
 
@@ -126,13 +131,16 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
     ///
     /// # Parameters
     ///
+    /// - `source`: Index of the buffer in the list of vertices sources, for error reporting.
     /// - `buffer`: The buffer to bind.
-    /// - `first`: Offset of the first element of the buffer in number of elements.
+    /// - `first`: Number of elements before the start of `buffer` that are bound as well. The
+    ///   draw command must skip them with its first vertex or its base vertex. This allows all
+    ///   the slices of a buffer to share the same vertex array object.
     /// - `divisor`: If `Some`, use this value for `glVertexAttribDivisor` (instancing-related).
-    pub fn add(mut self, buffer: &BufferViewAnySlice, bindings: &VertexFormat, divisor: Option<u32>)
-               -> Binder<'a, 'b, 'c>
+    pub fn add(mut self, source: usize, buffer: &BufferViewAnySlice, first: usize,
+               bindings: &VertexFormat, divisor: Option<u32>) -> Binder<'a, 'b, 'c>
     {
-        let offset = buffer.get_offset_bytes();
+        let offset = buffer.get_offset_bytes() - first * buffer.get_elements_size();
 
         buffer.prepare_for_vertex_attrib_array(self.context);
 
@@ -140,11 +148,14 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
                                         buffer.get_elements_size());
 
         self.vertex_buffers.push((buffer, format, offset, stride, divisor));
+        self.sources.push(source);
         self
     }
 
     /// Finish binding the vertex attributes.
-    pub fn bind(self) {
+    ///
+    /// Returns an error if the vertex formats don't match the attributes of the program.
+    pub fn bind(self) -> Result<(), DrawError> {
         let ctxt = self.context;
 
         if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
@@ -165,10 +176,11 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
                                      .get(&(buffers_list.clone(), program_id))
             {
                 value.bind(ctxt);
-                return;
+                return Ok(());
             }
 
             // if not found, building a new one
+            try!(check_attributes(ctxt, self.program, &self.vertex_buffers, &self.sources));
             let new_vao = unsafe {
                 VertexArrayObject::new(ctxt, &self.vertex_buffers,
                                        self.element_array_buffer, self.program)
@@ -179,6 +191,7 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
 
         } else {
             // VAOs are not supported
+            try!(check_attributes(ctxt, self.program, &self.vertex_buffers, &self.sources));
 
             // just in case
             bind_vao(ctxt, 0);
@@ -194,9 +207,52 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
                 }
             }
         }
+
+        Ok(())
     }
 }
 
+/// Checks that the vertex formats match the attributes of the program.
+fn check_attributes(ctxt: &CommandContext, program: &Program,
+                    vertex_buffers: &[(gl::types::GLuint, VertexFormat, usize, usize, Option<u32>)],
+                    sources: &[usize]) -> Result<(), DrawError>
+{
+    let formats = vertex_buffers.iter().map(|&(_, ref bindings, _, _, _)| bindings)
+                                .collect::<Vec<_>>();
+
+    // the report uses the index within `vertex_buffers`, which we turn into the index of the
+    // vertices source
+    match AttributesReport::new(program, &formats).first_error() {
+        Some(DrawError::AttributeTypeMismatch { name, expected, obtained, source }) => {
+            return Err(DrawError::AttributeTypeMismatch {
+                name: name,
+                expected: expected,
+                obtained: obtained,
+                source: sources[source],
+            });
+        },
+        Some(err) => return Err(err),
+        None => ()
+    }
+
+    for &(_, ref bindings, _, _, _) in vertex_buffers {
+        for &(ref name, _, ty, _) in bindings.iter() {
+            if program.get_attribute(Borrow::<str>::borrow(name)).is_none() {
+                continue;
+            }
+
+            if !is_attribute_type_supported(ctxt, ty) {
+                return Err(DrawError::AttributeTypeNotSupported {
+                    name: name.to_string(),
+                    ty: ty,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Stores informations about how to bind a vertex buffer, an index buffer and a program.
 struct VertexArrayObject {
     id: gl::types::GLuint,
@@ -209,42 +265,12 @@ impl VertexArrayObject {
     /// Builds a new `VertexArrayObject`.
     ///
     /// The vertex buffer, index buffer and program must not outlive the
-    /// VAO, and the VB & program attributes must not change.
+    /// VAO, and the VB & program attributes must not change. The attributes must have been
+    /// checked with `check_attributes`.
     unsafe fn new(mut ctxt: &mut CommandContext,
                   vertex_buffers: &[(gl::types::GLuint, VertexFormat, usize, usize, Option<u32>)],
                   index_buffer: Option<BufferViewAnySlice>, program: &Program) -> VertexArrayObject
     {
-        // checking the attributes types
-        for &(_, ref bindings, _, _, _) in vertex_buffers {
-            for &(ref name, _, ty) in bindings.iter() {
-                let attribute = match program.get_attribute(Borrow::<str>::borrow(name)) {
-                    Some(a) => a,
-                    None => continue
-                };
-
-                if ty.get_num_components() != attribute.ty.get_num_components() ||
-                    attribute.size != 1
-                {
-                    panic!("The program attribute `{}` does not match the vertex format. \
-                            Program expected {:?}, got {:?}.", name, attribute.ty, ty);
-                }
-            }
-        }
-
-        // checking for missing attributes
-        for (&ref name, _) in program.attributes() {
-            let mut found = false;
-            for &(_, ref bindings, _, _, _) in vertex_buffers {
-                if bindings.iter().find(|&&(ref n, _, _)| n == name).is_some() {
-                    found = true;
-                    break;
-                }
-            }
-            if !found {
-                panic!("The program attribute `{}` is missing in the vertex bindings", name);
-            }
-        };
-
         // TODO: check for collisions between the vertices sources
 
         // building the VAO
@@ -400,6 +426,9 @@ fn vertex_binding_type_to_gl(ty: AttributeType) -> (gl::types::GLenum, gl::types
         AttributeType::F64x4x2 => (gl::DOUBLE_MAT4x2, 1),
         AttributeType::F64x4x3 => (gl::DOUBLE_MAT4x3, 1),
         AttributeType::F64x4x4 => (gl::DOUBLE_MAT4, 1),
+        AttributeType::I2I10I10I10Rev => (gl::INT_2_10_10_10_REV, 4),
+        AttributeType::U2U10U10U10Rev => (gl::UNSIGNED_INT_2_10_10_10_REV, 4),
+        AttributeType::F10F11F11UnsignedIntRev => (gl::UNSIGNED_INT_10F_11F_11F_REV, 3),
     }
 }
 
@@ -427,6 +456,54 @@ fn bind_vao(ctxt: &mut CommandContext, vao_id: gl::types::GLuint) {
     }
 }
 
+/// Returns true if a vertex attribute of type `ty` can be read by an attribute of the program of
+/// type `program_ty`.
+pub fn is_attribute_type_compatible(program_ty: AttributeType, ty: AttributeType) -> bool {
+    if ty.get_num_components() != program_ty.get_num_components() {
+        return false;
+    }
+
+    // packed types can only be read as floats
+    if is_packed_type(ty) && is_integer_type(program_ty) {
+        return false;
+    }
+
+    true
+}
+
+/// Returns true if the type is one of the packed types.
+fn is_packed_type(ty: AttributeType) -> bool {
+    match ty {
+        AttributeType::I2I10I10I10Rev | AttributeType::U2U10U10U10Rev |
+        AttributeType::F10F11F11UnsignedIntRev => true,
+        _ => false
+    }
+}
+
+/// Returns true if an attribute of the program with this type is an integer.
+fn is_integer_type(ty: AttributeType) -> bool {
+    match vertex_binding_type_to_gl(ty).0 {
+        gl::BYTE | gl::UNSIGNED_BYTE | gl::SHORT | gl::UNSIGNED_SHORT |
+        gl::INT | gl::UNSIGNED_INT => true,
+        _ => false
+    }
+}
+
+/// Returns true if the backend supports reading vertex attributes of this type.
+fn is_attribute_type_supported(ctxt: &CommandContext, ty: AttributeType) -> bool {
+    match ty {
+        AttributeType::I2I10I10I10Rev | AttributeType::U2U10U10U10Rev => {
+            ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+            ctxt.extensions.gl_arb_vertex_type_2_10_10_10_rev
+        },
+        AttributeType::F10F11F11UnsignedIntRev => {
+            ctxt.version >= &Version(Api::Gl, 4, 4) ||
+            ctxt.extensions.gl_arb_vertex_type_10f_11f_11f_rev
+        },
+        _ => true
+    }
+}
+
 /// Binds an individual attribute to the current VAO.
 unsafe fn bind_attribute(ctxt: &mut CommandContext, program: &Program,
                          vertex_buffer: gl::types::GLuint, bindings: &VertexFormat,
@@ -448,7 +525,7 @@ unsafe fn bind_attribute(ctxt: &mut CommandContext, program: &Program,
     }
 
     // binding attributes
-    for &(ref name, offset, ty) in bindings.iter() {
+    for &(ref name, offset, ty, normalize) in bindings.iter() {
         let (data_type, elements_count) = vertex_binding_type_to_gl(ty);
 
         let attribute = match program.get_attribute(Borrow::<str>::borrow(name)) {
@@ -477,7 +554,8 @@ unsafe fn bind_attribute(ctxt: &mut CommandContext, program: &Program,
                                                  (buffer_offset + offset) as *const libc::c_void),
 
                 _ => ctxt.gl.VertexAttribPointer(attribute.location as u32,
-                                                 elements_count as gl::types::GLint, data_type, 0,
+                                                 elements_count as gl::types::GLint, data_type,
+                                                 if normalize { gl::TRUE } else { gl::FALSE },
                                                  stride as i32,
                                                  (buffer_offset + offset) as *const libc::c_void)
             }
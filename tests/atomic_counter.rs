@@ -0,0 +1,184 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+
+mod support;
+
+#[test]
+fn atomic_counter_buffer_reset() {
+    let display = support::build_display();
+
+    let buffer = match glium::uniforms::AtomicCounterBuffer::new_if_supported(&display, &[5, 8]) {
+        None => return,
+        Some(b) => b
+    };
+
+    if let Some(value) = buffer.get_if_supported(1) {
+        assert_eq!(value, 8);
+    }
+
+    buffer.reset_to(3);
+
+    if let Some(data) = buffer.read_if_supported() {
+        assert_eq!(data, vec![3, 3]);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn atomic_counter_reflection() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 420
+
+            layout(binding = 2, offset = 4) uniform atomic_uint counter;
+
+            out vec4 f_color;
+
+            void main() {
+                atomicCounterIncrement(counter);
+                f_color = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let counter = program.get_atomic_counters().get("counter").unwrap();
+    assert_eq!(counter.binding, 2);
+    assert_eq!(counter.offset, 4);
+    assert_eq!(counter.size, None);
+    assert!(counter.buffer_size >= 8);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn atomic_counter_increment() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 420
+
+            layout(binding = 0, offset = 4) uniform atomic_uint counter;
+
+            out vec4 f_color;
+
+            void main() {
+                atomicCounterIncrement(counter);
+                f_color = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let buffer = match glium::uniforms::AtomicCounterBuffer::zeroed_if_supported(&display, 2) {
+        None => return,
+        Some(b) => b
+    };
+
+    let uniforms = uniform!{
+        counter: &buffer
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data = match buffer.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data, vec![0, 1024 * 1024]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn atomic_counter_buffer_too_small() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 420
+
+            layout(binding = 0, offset = 4) uniform atomic_uint counter;
+
+            out vec4 f_color;
+
+            void main() {
+                atomicCounterIncrement(counter);
+                f_color = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    // the counter is at offset 4, so one counter is not enough
+    let buffer = match glium::uniforms::AtomicCounterBuffer::zeroed_if_supported(&display, 1) {
+        None => return,
+        Some(b) => b
+    };
+
+    let uniforms = uniform!{
+        counter: &buffer
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::AtomicCounterBufferTooSmall { size: 4, .. }) => (),
+        e => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
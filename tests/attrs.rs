@@ -7,7 +7,6 @@ use glium::index::PrimitiveType;
 mod support;
 
 #[test]
-#[should_panic(expected = "The program attribute `field1` does not match the vertex format")]
 fn attribute_types_mismatch() {
     let display = support::build_display();
 
@@ -46,15 +45,19 @@ fn attribute_types_mismatch() {
 
     // drawing a frame
     let mut target = display.draw();
-    target.draw(&vertex_buffer, &index_buffer, &program, &glium::uniforms::EmptyUniforms,
-                &Default::default()).unwrap();
+    match target.draw(&vertex_buffer, &index_buffer, &program, &glium::uniforms::EmptyUniforms,
+                      &Default::default())
+    {
+        Err(glium::DrawError::AttributeTypeMismatch { ref name, source: 0, .. })
+            if name == "field1" => (),
+        a => panic!("{:?}", a)
+    };
     target.finish().unwrap();
 
     display.assert_no_error(None);
 }
 
 #[test]
-#[should_panic(expected = "The program attribute `field2` is missing in the vertex bindings")]
 fn missing_attribute() {
     let display = support::build_display();
 
@@ -93,13 +96,65 @@ fn missing_attribute() {
 
     // drawing a frame
     let mut target = display.draw();
-    target.draw(&vertex_buffer, &index_buffer, &program, &glium::uniforms::EmptyUniforms,
-                &Default::default()).unwrap();
+    match target.draw(&vertex_buffer, &index_buffer, &program, &glium::uniforms::EmptyUniforms,
+                      &Default::default())
+    {
+        Err(glium::DrawError::AttributeMissing { ref name }) if name == "field2" => (),
+        a => panic!("{:?}", a)
+    };
     target.finish().unwrap();
 
     display.assert_no_error(None);
 }
 
+#[test]
+fn attributes_report() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [f32; 2],
+        field3: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, field1, field3);
+
+    let vertex_buffer = glium::VertexBuffer::new(&display, Vec::<Vertex>::new());
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 field1;
+            attribute vec2 field2;
+
+            void main() {
+                gl_Position = vec4(field1 + field2, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+            void main() {
+                gl_FragColor = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let report = glium::vertex::AttributesReport::new(&program, &[vertex_buffer.get_bindings()]);
+
+    let statuses = report.entries.iter().map(|e| (&e.name[..], e.status)).collect::<Vec<_>>();
+    assert_eq!(statuses, vec![("field1", glium::vertex::AttributeStatus::Ok),
+                              ("field2", glium::vertex::AttributeStatus::Missing),
+                              ("field3", glium::vertex::AttributeStatus::Unused)]);
+
+    match report.first_error() {
+        Some(glium::DrawError::AttributeMissing { ref name }) if name == "field2" => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
 macro_rules! attribute_test(
     ($name:ident, $attr_ty:ty, $glsl_ty:expr, $value:expr, $gl_pos:expr) => (
         #[test]
@@ -241,3 +296,133 @@ attribute_test!(attribute_vec3_i32, [i32; 3], "vec3", [0, 0, 0], "vec4(field1, 1
 attribute_test!(attribute_vec3_tuple_i32, (i32, i32, i32), "vec3", (0, 0, 0), "vec4(field1, 1.0)");
 attribute_test!(attribute_vec4_i32, [i32; 4], "vec4", [0, 0, 0, 0], "field1");
 attribute_test!(attribute_vec4_tuple_i32, (i32, i32, i32, i32), "vec4", (0, 0, 0, 0), "field1");
+
+#[test]
+fn normalized_attribute() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+        color: [u8; 4],
+    }
+
+    implement_vertex!(Vertex, position, color normalize(true));
+
+    let vertex_buffer = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0], color: [255, 0, 255, 255] },
+        Vertex { position: [ 1.0,  1.0], color: [255, 0, 255, 255] },
+        Vertex { position: [-1.0, -1.0], color: [255, 0, 255, 255] },
+        Vertex { position: [ 1.0, -1.0], color: [255, 0, 255, 255] },
+    ]);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            attribute vec4 color;
+            varying vec4 v_color;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                v_color = color;
+            }
+        ",
+        "
+            #version 110
+
+            varying vec4 v_color;
+
+            void main() {
+                gl_FragColor = v_color;
+            }
+        ",
+        None)
+        .unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vertex_buffer,
+                              &glium::index::NoIndices(PrimitiveType::TriangleStrip),
+                              &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 255, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn packed_attribute() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+        color: glium::vertex::PackedU2U10U10U10,
+    }
+
+    implement_vertex!(Vertex, position, color normalize(true));
+
+    let color = glium::vertex::PackedU2U10U10U10::new(1023, 0, 1023, 3);
+
+    let vertex_buffer = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0], color: color },
+        Vertex { position: [ 1.0,  1.0], color: color },
+        Vertex { position: [-1.0, -1.0], color: color },
+        Vertex { position: [ 1.0, -1.0], color: color },
+    ]);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            attribute vec4 color;
+            varying vec4 v_color;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                v_color = color;
+            }
+        ",
+        "
+            #version 110
+
+            varying vec4 v_color;
+
+            void main() {
+                gl_FragColor = v_color;
+            }
+        ",
+        None)
+        .unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vertex_buffer,
+                                    &glium::index::NoIndices(PrimitiveType::TriangleStrip),
+                                    &program, &glium::uniforms::EmptyUniforms,
+                                    &Default::default())
+    {
+        Ok(_) => (),
+        Err(glium::DrawError::AttributeTypeNotSupported { .. }) => return,
+        Err(e) => panic!("{:?}", e)
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 255, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,144 @@
+extern crate glium;
+
+use glium::Surface;
+use glium::batch::{Sprite, SpriteBatch, SortMode};
+
+mod support;
+
+#[test]
+fn sprite_batch_draw() {
+    let display = support::build_display();
+
+    let red = glium::texture::Texture2d::new(&display, vec![vec![(255u8, 0u8, 0u8, 255u8)]]);
+    let green = glium::texture::Texture2d::new(&display, vec![vec![(0u8, 255u8, 0u8, 255u8)]]);
+
+    let texture = glium::texture::Texture2d::empty(&display, 64, 64);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    // a capacity of 1 forces a draw call for each sprite
+    let mut batch = SpriteBatch::new(&display, 1).unwrap();
+
+    {
+        let mut surface = texture.as_surface();
+        let mut sprites = batch.begin(&mut surface, &Default::default());
+
+        sprites.add(&red, Sprite {
+            position: [-0.5, 0.0],
+            size: [1.0, 2.0],
+            .. Default::default()
+        }).unwrap();
+
+        sprites.add(&green, Sprite {
+            position: [0.5, 0.0],
+            size: [1.0, 2.0],
+            .. Default::default()
+        }).unwrap();
+
+        sprites.finish().unwrap();
+    }
+
+    display.assert_no_error(None);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[32][0], (255, 0, 0, 255));
+    assert_eq!(data[32][63], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sprite_batch_sort_by_depth() {
+    let display = support::build_display();
+
+    let red = glium::texture::Texture2d::new(&display, vec![vec![(255u8, 0u8, 0u8, 255u8)]]);
+    let green = glium::texture::Texture2d::new(&display, vec![vec![(0u8, 255u8, 0u8, 255u8)]]);
+
+    let texture = glium::texture::Texture2d::empty(&display, 64, 64);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let mut batch = SpriteBatch::new(&display, 16).unwrap();
+
+    {
+        let mut surface = texture.as_surface();
+        let mut sprites = batch.begin(&mut surface, &Default::default());
+        sprites.set_sort_mode(SortMode::BackToFront).unwrap();
+
+        // the green sprite is in front, so it must be drawn last despite being added first
+        sprites.add(&green, Sprite { size: [2.0, 2.0], depth: 0.2, .. Default::default() })
+               .unwrap();
+        sprites.add(&red, Sprite { size: [2.0, 2.0], depth: 0.5, .. Default::default() })
+               .unwrap();
+        assert_eq!(sprites.len(), 2);
+
+        sprites.finish().unwrap();
+    }
+
+    display.assert_no_error(None);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[32][32], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sprite_batch_max_capacity() {
+    let display = support::build_display();
+
+    let batch = SpriteBatch::new(&display, glium::batch::MAX_CAPACITY).unwrap();
+    assert_eq!(batch.get_capacity(), 16384);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sprite_batch_capacity_too_large() {
+    let display = support::build_display();
+
+    match SpriteBatch::new(&display, glium::batch::MAX_CAPACITY + 1) {
+        Err(glium::batch::SpriteBatchCreationError::InvalidCapacity(_)) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sprite_batch_many_draw_calls() {
+    let display = support::build_display();
+
+    let red = glium::texture::Texture2d::new(&display, vec![vec![(255u8, 0u8, 0u8, 255u8)]]);
+
+    let texture = glium::texture::Texture2d::empty(&display, 64, 64);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    // more draw calls than regions in the vertex buffer
+    let mut batch = SpriteBatch::new(&display, 1).unwrap();
+
+    {
+        let mut surface = texture.as_surface();
+        let mut sprites = batch.begin(&mut surface, &Default::default());
+
+        for i in 0 .. 8 {
+            let x = -0.875 + i as f32 * 0.25;
+            sprites.add(&red, Sprite {
+                position: [x, 0.0],
+                size: [0.25, 2.0],
+                .. Default::default()
+            }).unwrap();
+        }
+
+        sprites.finish().unwrap();
+    }
+
+    display.assert_no_error(None);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
@@ -45,7 +45,8 @@ fn immutable_mapping_forget_then_remap() {
     let display = support::build_display();
 
     let mut buf = glium::buffer::BufferView::new(&display, &[1, 2, 3],
-                                                 glium::buffer::BufferType::ArrayBuffer, false)
+                                                 glium::buffer::BufferType::ArrayBuffer,
+                                                 glium::buffer::BufferMode::Immutable)
                                                  .unwrap();
 
     {
@@ -66,7 +67,8 @@ fn immutable_mapping_forget_then_read() {
     let display = support::build_display();
 
     let mut buf = glium::buffer::BufferView::new(&display, &[1, 2, 3],
-                                                 glium::buffer::BufferType::ArrayBuffer, false)
+                                                 glium::buffer::BufferType::ArrayBuffer,
+                                                 glium::buffer::BufferMode::Immutable)
                                                  .unwrap();
 
     {
@@ -91,7 +93,8 @@ fn immutable_mapping_forget_then_invalidate() {
     let display = support::build_display();
 
     let mut buf = glium::buffer::BufferView::new(&display, &[1, 2, 3],
-                                                 glium::buffer::BufferType::ArrayBuffer, false)
+                                                 glium::buffer::BufferType::ArrayBuffer,
+                                                 glium::buffer::BufferMode::Immutable)
                                                  .unwrap();
 
     {
@@ -205,7 +208,8 @@ fn dynamic_mapping_forget_then_remap() {
     let display = support::build_display();
 
     let mut buf = glium::buffer::BufferView::new(&display, &[1, 2, 3],
-                                                 glium::buffer::BufferType::ArrayBuffer, true)
+                                                 glium::buffer::BufferType::ArrayBuffer,
+                                                 glium::buffer::BufferMode::Dynamic)
                                                  .unwrap();
 
     {
@@ -227,7 +231,8 @@ fn dynamic_mapping_forget_then_read() {
     let display = support::build_display();
 
     let mut buf = glium::buffer::BufferView::new(&display, &[1, 2, 3],
-                                                 glium::buffer::BufferType::ArrayBuffer, true)
+                                                 glium::buffer::BufferType::ArrayBuffer,
+                                                 glium::buffer::BufferMode::Dynamic)
                                                  .unwrap();
 
     {
@@ -252,7 +257,8 @@ fn dynamic_mapping_forget_then_invalidate() {
     let display = support::build_display();
 
     let mut buf = glium::buffer::BufferView::new(&display, &[1, 2, 3],
-                                                 glium::buffer::BufferType::ArrayBuffer, true)
+                                                 glium::buffer::BufferType::ArrayBuffer,
+                                                 glium::buffer::BufferMode::Dynamic)
                                                  .unwrap();
 
     {
@@ -360,3 +366,382 @@ fn dynamic_mapping_forget_then_draw() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn streaming_buffer_draw() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex { position: [f32; 2] }
+    implement_vertex!(Vertex, position);
+
+    let mut vertices = glium::buffer::StreamingVertexBuffer::<Vertex>::new(&display, 4, 3)
+                                                                 .unwrap();
+    let mut indices = glium::buffer::StreamingIndexBuffer::<u16>::new(&display,
+                                      glium::index::PrimitiveType::TriangleStrip, 4, 3).unwrap();
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+
+    // drawing more frames than there are regions
+    for _ in 0 .. 5 {
+        let mut vb = vertices.next_region();
+        vb.write(&[
+            Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+            Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+        ]);
+        assert_eq!(vb.len(), 4);
+
+        let mut ib = indices.next_region();
+        ib.write(&[0, 1, 2, 3]);
+
+        texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{},
+                                  &Default::default()).unwrap();
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn streaming_buffer_no_regions() {
+    let display = support::build_display();
+
+    match glium::buffer::StreamingIndexBuffer::<u16>::new(&display,
+                                    glium::index::PrimitiveType::TriangleStrip, 4, 0)
+    {
+        Err(glium::buffer::BufferCreationError::NoRegions) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn copy_to() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Vertex { field: f32 }
+    implement_vertex!(Vertex, field);
+
+    let source = glium::VertexBuffer::new(&display,
+        &[ Vertex { field: 1.0 }, Vertex { field: 2.0 }, Vertex { field: 3.0 } ]
+    );
+
+    let mut target = glium::VertexBuffer::new(&display,
+        &[ Vertex { field: 0.0 }, Vertex { field: 0.0 }, Vertex { field: 0.0 } ]
+    );
+
+    match source.slice(1 .. 3).unwrap().copy_to(&target.slice_mut(0 .. 2).unwrap()) {
+        Err(glium::buffer::CopyError::NotSupported) => return,
+        Err(_) => panic!(),
+        Ok(_) => ()
+    };
+
+    let data = match target.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data, vec![Vertex { field: 2.0 }, Vertex { field: 3.0 }, Vertex { field: 0.0 }]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn copy_to_length_mismatch() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Vertex { field: f32 }
+    implement_vertex!(Vertex, field);
+
+    let source = glium::VertexBuffer::new(&display,
+        &[ Vertex { field: 1.0 }, Vertex { field: 2.0 }, Vertex { field: 3.0 } ]
+    );
+
+    let mut target = glium::VertexBuffer::new(&display,
+        &[ Vertex { field: 0.0 }, Vertex { field: 0.0 } ]
+    );
+
+    match source.copy_to(&target.slice_mut(0 .. 2).unwrap()) {
+        Err(glium::buffer::CopyError::LengthMismatch) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clear_to() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Vertex { field: [f32; 3] }
+    implement_vertex!(Vertex, field);
+
+    let buffer = glium::VertexBuffer::new(&display,
+        &[ Vertex { field: [0.0; 3] }, Vertex { field: [0.0; 3] }, Vertex { field: [0.0; 3] } ]
+    );
+
+    buffer.slice(1 .. 3).unwrap().clear_to(Vertex { field: [1.0, 2.0, 3.0] });
+
+    let data = match buffer.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data, vec![Vertex { field: [0.0; 3] }, Vertex { field: [1.0, 2.0, 3.0] },
+                          Vertex { field: [1.0, 2.0, 3.0] }]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn persistent_mapping_flush_range() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Vertex { field: f32 }
+    implement_vertex!(Vertex, field);
+
+    let mut buffer = match glium::VertexBuffer::with_mode(&display,
+        &[ Vertex { field: 1.0 }, Vertex { field: 2.0 }, Vertex { field: 3.0 } ],
+        glium::buffer::BufferMode::Persistent)
+    {
+        Ok(b) => b,
+        Err(glium::buffer::BufferCreationError::BufferModeNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    {
+        let mut mapping = buffer.map();
+        mapping[1] = Vertex { field: 5.0 };
+        mapping.flush_range(1 .. 2);
+    }
+
+    let data = match buffer.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data, vec![Vertex { field: 1.0 }, Vertex { field: 5.0 }, Vertex { field: 3.0 }]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn immutable_mode_write() {
+    let display = support::build_display();
+
+    let buffer = glium::IndexBuffer::with_mode(&display,
+                                               glium::index::PrimitiveType::TrianglesList,
+                                               &[0u16, 1, 2],
+                                               glium::buffer::BufferMode::Immutable).unwrap();
+    buffer.write(&[2, 1, 0]);
+
+    let data = match buffer.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data, vec![2, 1, 0]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn buffer_pool_reuse_freed_ranges() {
+    let display = support::build_display();
+
+    let mut pool = glium::buffer::IndexBufferPool::<u16>::new(&display,
+                                    glium::index::PrimitiveType::TrianglesList, 16,
+                                    glium::buffer::BufferMode::Default).unwrap();
+
+    let a = pool.alloc(&[0, 1, 2]).unwrap();
+    let b = pool.alloc(&[3, 4, 5, 6]).unwrap();
+    let c = pool.alloc_empty(8).unwrap();
+
+    let stats = pool.get_statistics();
+    assert_eq!(stats.buffers, 1);
+    assert_eq!(stats.allocations, 3);
+    assert_eq!(stats.total_bytes, 32);
+    assert_eq!(stats.used_bytes, 30);
+
+    drop(b);
+    let stats = pool.get_statistics();
+    assert_eq!(stats.free_ranges, 2);
+    assert_eq!(stats.largest_free_range, 8);
+    assert!(stats.fragmentation() > 0.0);
+
+    // the freed range is reused before a new buffer is created
+    let d = pool.alloc(&[7, 8, 9, 10]).unwrap();
+    assert_eq!(pool.get_statistics().buffers, 1);
+
+    // too large for the remaining space
+    let e = pool.alloc_empty(16).unwrap();
+    assert_eq!(pool.get_statistics().buffers, 2);
+
+    drop(e);
+    pool.release_unused();
+    assert_eq!(pool.get_statistics().buffers, 1);
+
+    if let Some(data) = a.read_if_supported() {
+        assert_eq!(data, vec![0, 1, 2]);
+    }
+
+    if let Some(data) = d.read_if_supported() {
+        assert_eq!(data, vec![7, 8, 9, 10]);
+    }
+
+    drop(c);
+    display.assert_no_error(None);
+}
+
+#[test]
+fn buffer_pool_draw() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex { position: [f32; 2] }
+    implement_vertex!(Vertex, position);
+
+    let mut vertices = glium::buffer::VertexBufferPool::<Vertex>::new(&display, 64,
+                                                  glium::buffer::BufferMode::Default).unwrap();
+    let mut indices = glium::buffer::IndexBufferPool::<u16>::new(&display,
+                                    glium::index::PrimitiveType::TriangleStrip, 64,
+                                    glium::buffer::BufferMode::Default).unwrap();
+
+    // making sure that the quad is not at the start of the buffer
+    let _padding = vertices.alloc(&[Vertex { position: [0.0, 0.0] }]).unwrap();
+
+    let vb = vertices.alloc(&[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]).unwrap();
+    let ib = indices.alloc(&[0, 1, 2, 3]).unwrap();
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn buffer_pool_draw_slices_without_indices() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex { position: [f32; 2] }
+    implement_vertex!(Vertex, position);
+
+    let mut vertices = glium::buffer::VertexBufferPool::<Vertex>::new(&display, 64,
+                                                  glium::buffer::BufferMode::Default).unwrap();
+
+    // two slices of the same buffer, each covering one half of the target
+    let left = vertices.alloc(&[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [0.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [0.0, -1.0] },
+    ]).unwrap();
+    let right = vertices.alloc(&[
+        Vertex { position: [0.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [0.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]).unwrap();
+    assert_eq!(vertices.get_statistics().buffers, 1);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let indices = glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&left, &indices, &program, &uniform!{},
+                              &Default::default()).unwrap();
+    texture.as_surface().draw(&right, &indices, &program, &uniform!{},
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
@@ -387,3 +387,17 @@ fn is_context_lost() {
     display.is_context_lost();
     display.assert_no_error(None);
 }
+
+#[test]
+fn frame_discard_depth_stencil() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let mut frame = display.draw();
+    frame.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+               &Default::default()).unwrap();
+    frame.discard_depth_stencil();
+    frame.finish().unwrap();
+
+    display.assert_no_error(None);
+}
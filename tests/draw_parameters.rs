@@ -2,6 +2,7 @@
 extern crate glium;
 
 use glium::Surface;
+use glium::index::PrimitiveType;
 
 mod support;
 
@@ -29,3 +30,62 @@ fn color_mask() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn primitive_restart_fixed_index() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        primitive_restart: Some(glium::draw_parameters::PrimitiveRestart::FixedIndex),
+        .. Default::default()
+    };
+
+    let (vb, _, program) = support::build_fullscreen_red_pipeline(&display);
+
+    // two triangles separated by the restart index
+    let ib = glium::IndexBuffer::new(&display, PrimitiveType::TriangleStrip,
+                                     vec![0u16, 1, 2, 0xffff, 1, 2, 3]);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Ok(_) => (),
+        Err(glium::DrawError::PrimitiveRestartNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn primitive_restart_index_out_of_range() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        primitive_restart: Some(glium::draw_parameters::PrimitiveRestart::Index(300)),
+        .. Default::default()
+    };
+
+    // the index buffer of this pipeline contains `u8`s
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::PrimitiveRestartIndexOutOfRange) => (),
+        Err(glium::DrawError::PrimitiveRestartNotSupported) => return,
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
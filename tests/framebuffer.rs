@@ -12,7 +12,7 @@ fn no_depth_buffer_depth_test() {
 
     let texture = glium::texture::Texture2d::new_empty(&display,
                             glium::texture::UncompressedFloatFormat::U8U8U8U8, 128, 128);
-    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
 
     let parameters = glium::DrawParameters {
         depth_test: glium::DepthTest::IfLess,
@@ -36,7 +36,7 @@ fn no_depth_buffer_depth_write() {
 
     let texture = glium::texture::Texture2d::new_empty(&display,
                             glium::texture::UncompressedFloatFormat::U8U8U8U8, 128, 128);
-    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
 
     let parameters = glium::DrawParameters {
         depth_write: true,
@@ -61,7 +61,7 @@ fn simple_dimensions() {
                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
                                               128, 128);
 
-    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
     assert_eq!(framebuffer.get_dimensions(), (128, 128));
 
     display.assert_no_error(None);
@@ -76,7 +76,7 @@ fn simple_render_to_texture() {
                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
                                               128, 128);
 
-    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
     framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
 
     let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
@@ -148,8 +148,8 @@ fn depth_texture2d() {
     };
 
     // drawing with the `IfLess` depth test
-    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
-                                                                                   &color, &depth);
+    let mut framebuffer =
+        glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display, &color, &depth).unwrap();
     let params = glium::DrawParameters {
         depth_test: glium::DepthTest::IfLess,
         .. Default::default()
@@ -213,7 +213,7 @@ fn multioutput() {
 
     // building the framebuffer
     let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
-                                             &[("color1", &color1), ("color2", &color2)]);
+                                             &[("color1", &color1), ("color2", &color2)]).unwrap();
 
     framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
                      &Default::default()).unwrap();
@@ -235,5 +235,558 @@ fn multioutput() {
     }
 
 
+    display.assert_no_error(None);
+}
+
+#[test]
+fn layered_dimensions() {
+    let display = support::build_display();
+
+    if display.get_opengl_version() < glium::Version(glium::Api::Gl, 3, 2) {
+        return;
+    }
+
+    let texture = glium::texture::Texture2dArray::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              128, 128, 4);
+
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+    assert_eq!(framebuffer.get_dimensions(), (128, 128));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn layered_and_non_layered_attachments() {
+    let display = support::build_display();
+
+    let color = glium::texture::Texture2dArray::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              128, 128, 4);
+    let depth = glium::texture::DepthTexture2d::new_empty(&display,
+                                                          glium::texture::DepthFormat::F32,
+                                                          128, 128);
+
+    match glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display, &color, &depth) {
+        Err(glium::framebuffer::ValidationError::NotAllAttachmentsLayered) => (),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn layered_layers_count_mismatch() {
+    let display = support::build_display();
+
+    let color = glium::texture::Texture2dArray::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              128, 128, 4);
+    let depth = glium::texture::DepthTexture2dArray::new_empty(&display,
+                                                               glium::texture::DepthFormat::F32,
+                                                               128, 128, 2);
+
+    match glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display, &color, &depth) {
+        Err(glium::framebuffer::ValidationError::LayersCountMismatch) => (),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn multioutput_clear_attachments() {
+    let display = support::build_display();
+
+    if display.get_opengl_version() < glium::Version(glium::Api::Gl, 3, 0) {
+        return;
+    }
+
+    let color1 = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             128, 128);
+    let color2 = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             128, 128);
+
+    {
+        let output = [("color1", &color1), ("color2", &color2)];
+        let mut framebuffer =
+            glium::framebuffer::MultiOutputFrameBuffer::new(&display, &output).unwrap();
+        framebuffer.clear_color_attachment("color1", (1.0, 0.0, 0.0, 1.0)).unwrap();
+        framebuffer.clear_color_attachment("color2", (0.0, 0.0, 1.0, 1.0)).unwrap();
+    }
+
+    let read_back1: Vec<Vec<(u8, u8, u8, u8)>> = color1.read();
+    assert_eq!(read_back1[0][0], (255, 0, 0, 255));
+    assert_eq!(read_back1[127][127], (255, 0, 0, 255));
+
+    let read_back2: Vec<Vec<(u8, u8, u8, u8)>> = color2.read();
+    assert_eq!(read_back2[0][0], (0, 0, 255, 255));
+    assert_eq!(read_back2[127][127], (0, 0, 255, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multioutput_clear_unknown_attachment() {
+    let display = support::build_display();
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            128, 128);
+
+    let mut framebuffer =
+        glium::framebuffer::MultiOutputFrameBuffer::new(&display, &[("color", &color)]).unwrap();
+
+    match framebuffer.clear_color_attachment_if_supported("unknown", (1.0, 0.0, 0.0, 1.0)) {
+        Err(glium::framebuffer::ClearAttachmentError::AttachmentNotFound) => (),
+        e => panic!("{:?}", e)
+    };
+
+    // unknown names are reported before checking whether the backend supports clearing
+    match framebuffer.clear_color_attachment_uint("unknown", (1, 0, 0, 0)) {
+        Err(glium::framebuffer::ClearAttachmentError::AttachmentNotFound) => (),
+        e => panic!("{:?}", e)
+    };
+
+    match framebuffer.clear_color_attachment_if_supported("color", (1.0, 0.0, 0.0, 1.0)) {
+        Ok(()) | Err(glium::framebuffer::ClearAttachmentError::NotSupported) => (),
+        e => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn integral_attachments_clear() {
+    let display = support::build_display();
+
+    if display.get_opengl_version() < glium::Version(glium::Api::Gl, 3, 0) {
+        return;
+    }
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            128, 128);
+    let ids = glium::texture::UnsignedTexture2d::new_empty(&display,
+                                            glium::texture::UncompressedUintFormat::U32,
+                                            128, 128);
+    let signed = glium::texture::IntegralTexture2d::new_empty(&display,
+                                            glium::texture::UncompressedIntFormat::I32,
+                                            128, 128);
+
+    {
+        let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &ids).unwrap();
+        framebuffer.clear_color_uint(7, 0, 0, 0);
+    }
+
+    {
+        let mut framebuffer =
+            glium::framebuffer::SimpleFrameBuffer::new(&display, &signed).unwrap();
+        framebuffer.clear_color_int(-3, 0, 0, 0);
+    }
+
+    {
+        use glium::framebuffer::ToColorAttachment;
+
+        let color = color.to_color_attachment();
+        let ids = ids.to_color_attachment();
+        let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                                 &[("color", &color), ("ids", &ids)]).unwrap();
+        framebuffer.clear_color_attachment("color", (0.0, 1.0, 0.0, 1.0)).unwrap();
+        framebuffer.clear_color_attachment_uint("ids", (12, 0, 0, 0)).unwrap();
+    }
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = color.read();
+    assert_eq!(read_back[64][64], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn empty_framebuffer() {
+    let display = support::build_display();
+
+    if !glium::framebuffer::EmptyFrameBuffer::is_supported(&display) {
+        return;
+    }
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let mut framebuffer = glium::framebuffer::EmptyFrameBuffer::new(&display, 256, 128,
+                                                                    None, None).unwrap();
+    assert_eq!(framebuffer.get_dimensions(), (256, 128));
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn empty_framebuffer_wrong_dimensions() {
+    let display = support::build_display();
+
+    if !glium::framebuffer::EmptyFrameBuffer::is_supported(&display) {
+        return;
+    }
+
+    match glium::framebuffer::EmptyFrameBuffer::new(&display, 0, 128, None, None) {
+        Err(glium::framebuffer::ValidationError::EmptyFramebufferUnsupportedDimensions) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn empty_framebuffer_blit_color() {
+    let display = support::build_display();
+
+    if !glium::framebuffer::EmptyFrameBuffer::is_supported(&display) {
+        return;
+    }
+
+    let framebuffer = glium::framebuffer::EmptyFrameBuffer::new(&display, 2, 2,
+                                                                None, None).unwrap();
+    let texture = support::build_unicolor_texture2d(&display, 0.0, 1.0, 0.0);
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let target = glium::BlitTarget { left: 0, bottom: 0, width: 2, height: 2 };
+
+    // the empty framebuffer has no color buffer, so both blits do nothing
+    texture.as_surface().blit_color(&rect, &framebuffer, &target,
+                                    glium::uniforms::MagnifySamplerFilter::Nearest);
+    framebuffer.blit_color(&rect, &texture.as_surface(), &target,
+                           glium::uniforms::MagnifySamplerFilter::Nearest);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 255, 0, 255));
+    assert_eq!(read_back[1][1], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_only_framebuffer() {
+    use std::iter;
+
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    // the first program writes a depth of 0.5 everywhere and no color
+    let depth_program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                }
+            ",
+        }).unwrap();
+
+    // the second program returns a Z coordinate between 0 (left of screen) and 1 (right of screen)
+    let color_program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, position.x, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, position.x, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+                }
+            ",
+        }).unwrap();
+
+    let depth_data = iter::repeat(iter::repeat(1.0f32).take(128).collect::<Vec<_>>())
+                                  .take(128).collect::<Vec<_>>();
+    let depth = match glium::texture::DepthTexture2d::new_if_supported(&display, depth_data) {
+        None => return,
+        Some(t) => t
+    };
+
+    {
+        let mut framebuffer =
+            glium::framebuffer::SimpleFrameBuffer::depth_only(&display, &depth).unwrap();
+        assert!(framebuffer.get_depth_buffer_bits().is_some());
+
+        let params = glium::DrawParameters {
+            depth_test: glium::DepthTest::IfLess,
+            depth_write: true,
+            .. Default::default()
+        };
+
+        framebuffer.draw(&vb, &ib, &depth_program, &glium::uniforms::EmptyUniforms,
+                         &params).unwrap();
+    }
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            128, 128);
+
+    let mut framebuffer =
+        glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display, &color, &depth).unwrap();
+    let params = glium::DrawParameters {
+        depth_test: glium::DepthTest::IfLess,
+        .. Default::default()
+    };
+
+    framebuffer.clear_color(0.0, 0.0, 0.0, 1.0);
+    framebuffer.draw(&vb, &ib, &color_program, &glium::uniforms::EmptyUniforms,
+                     &params).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = color.read();
+    assert_eq!(read_back[64][16], (255, 255, 255, 255));
+    assert_eq!(read_back[64][112], (0, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn stencil_only_framebuffer() {
+    let display = support::build_display();
+    let (vb, ib, color_program) = support::build_fullscreen_red_pipeline(&display);
+
+    // the first program only writes to the stencil buffer
+    let stencil_program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                }
+            ",
+        }).unwrap();
+
+    let stencil = glium::framebuffer::StencilRenderBuffer::new(&display,
+                                                    glium::texture::StencilFormat::I8, 128, 128);
+
+    {
+        let mut framebuffer =
+            glium::framebuffer::SimpleFrameBuffer::stencil_only(&display, &stencil).unwrap();
+        assert!(framebuffer.get_stencil_buffer_bits().is_some());
+        framebuffer.clear_stencil(0);
+
+        // writing 1 in the left half of the stencil buffer
+        let params = glium::DrawParameters {
+            stencil_test_clockwise: glium::StencilTest::IfNotEqual { mask: 0xff },
+            stencil_test_counter_clockwise: glium::StencilTest::IfNotEqual { mask: 0xff },
+            stencil_reference_value_clockwise: 1,
+            stencil_depth_pass_operation_clockwise: glium::StencilOperation::Replace,
+            stencil_reference_value_counter_clockwise: 1,
+            stencil_depth_pass_operation_counter_clockwise: glium::StencilOperation::Replace,
+            scissor: Some(glium::Rect { left: 0, bottom: 0, width: 64, height: 128 }),
+            .. Default::default()
+        };
+
+        framebuffer.draw(&vb, &ib, &stencil_program, &glium::uniforms::EmptyUniforms,
+                         &params).unwrap();
+    }
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            128, 128);
+
+    let mut framebuffer =
+        glium::framebuffer::SimpleFrameBuffer::with_stencil_buffer(&display, &color, &stencil)
+            .unwrap();
+    let params = glium::DrawParameters {
+        stencil_test_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+        stencil_reference_value_clockwise: 1,
+        stencil_test_counter_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+        stencil_reference_value_counter_clockwise: 1,
+        .. Default::default()
+    };
+
+    framebuffer.clear_color(0.0, 0.0, 0.0, 1.0);
+    framebuffer.draw(&vb, &ib, &color_program, &glium::uniforms::EmptyUniforms,
+                     &params).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = color.read();
+    assert_eq!(read_back[64][16], (255, 0, 0, 255));
+    assert_eq!(read_back[64][112], (0, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_only_framebuffer_color_output() {
+    use std::iter;
+
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match program!(&display,
+        330 => {
+            vertex: "
+                #version 330
+
+                in vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 330
+
+                out vec4 color;
+
+                void main() {
+                    color = vec4(1.0, 1.0, 1.0, 1.0);
+                }
+            ",
+        })
+    {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    if program.get_fragment_outputs_if_supported().is_none() {
+        return;
+    }
+
+    let depth_data = iter::repeat(iter::repeat(1.0f32).take(128).collect::<Vec<_>>())
+                                  .take(128).collect::<Vec<_>>();
+    let depth = match glium::texture::DepthTexture2d::new_if_supported(&display, depth_data) {
+        None => return,
+        Some(t) => t
+    };
+
+    let mut framebuffer =
+        glium::framebuffer::SimpleFrameBuffer::depth_only(&display, &depth).unwrap();
+
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                           &Default::default())
+    {
+        Err(glium::DrawError::NoColorBuffer) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn invalidate_attachments() {
+    use std::iter;
+
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            128, 128);
+
+    let depth_data = iter::repeat(iter::repeat(1.0f32).take(128).collect::<Vec<_>>())
+                                  .take(128).collect::<Vec<_>>();
+    let depth = match glium::texture::DepthTexture2d::new_if_supported(&display, depth_data) {
+        None => return,
+        Some(t) => t
+    };
+
+    let mut framebuffer =
+        glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display, &color, &depth).unwrap();
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    framebuffer.invalidate(None, false, true, false);
+    framebuffer.invalidate(Some(&glium::Rect { left: 0, bottom: 0, width: 64, height: 64 }),
+                           true, true, true);
+
+    // invalidating doesn't affect the other attachments
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = color.read();
+    assert_eq!(read_back[96][96], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn invalidate_texture() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = glium::Texture2d::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              128, 128);
+
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+    texture.main_level().invalidate();
+
     display.assert_no_error(None);
 }
@@ -361,3 +361,98 @@ fn multidraw_array() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn optimize_vertex_cache() {
+    // a grid of 10x10 quads whose triangles are in a scattered order
+    let mut triangles = Vec::new();
+    for y in 0 .. 10u16 {
+        for x in 0 .. 10u16 {
+            let a = y * 11 + x;
+            triangles.push([a, a + 1, a + 11]);
+            triangles.push([a + 11, a + 1, a + 12]);
+        }
+    }
+
+    let indices = (0 .. triangles.len()).flat_map(|t| triangles[(t * 37) % 200].iter().cloned())
+                                        .collect::<Vec<_>>();
+
+    let optimized = index::optimize::optimize_vertex_cache(&indices, 121);
+    assert_eq!(optimized.len(), indices.len());
+
+    let before = index::optimize::analyze_vertex_cache(&indices, 16);
+    let after = index::optimize::analyze_vertex_cache(&optimized, 16);
+    assert!(after.acmr < before.acmr);
+    assert!(after.atvr >= 1.0);
+}
+
+#[test]
+#[should_panic]
+fn optimize_vertex_cache_index_out_of_range() {
+    index::optimize::optimize_vertex_cache(&[0u16, 1, 3], 3);
+}
+
+#[test]
+fn optimize_vertex_fetch_and_deduplicate() {
+    // `flag` is followed by padding, which must be ignored
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    #[repr(C)]
+    struct Vertex {
+        position: [f32; 2],
+        flag: u8,
+    }
+
+    implement_vertex!(Vertex, position, flag);
+
+    let v = |x, y| Vertex { position: [x, y], flag: 1 };
+
+    let vertices = [v(0.0, 0.0), v(1.0, 1.0), v(1.0, 0.0), v(0.0, 0.0), v(0.0, 1.0)];
+    let indices = [3u8, 2, 1, 0, 1, 4];
+
+    let (vertices, indices) = index::optimize::deduplicate_vertices(&vertices, &indices);
+    assert_eq!(vertices.len(), 4);
+    assert_eq!(indices, vec![0, 2, 1, 0, 1, 3]);
+
+    let (vertices, indices, remap) = index::optimize::optimize_vertex_fetch(&vertices, &indices);
+    assert_eq!(vertices, vec![v(0.0, 0.0), v(1.0, 0.0), v(1.0, 1.0), v(0.0, 1.0)]);
+    assert_eq!(indices, vec![0, 1, 2, 0, 2, 3]);
+    assert_eq!(remap, vec![Some(0), Some(2), Some(1), Some(3)]);
+}
+
+#[test]
+fn convert_to_triangles_list() {
+    let fan = [0u16, 1, 2, 3];
+    assert_eq!(index::convert::convert(&fan, PrimitiveType::TriangleFan,
+                                       PrimitiveType::TrianglesList).unwrap(),
+               vec![0, 1, 2, 0, 2, 3]);
+
+    // the degenerate triangles that join two strips are removed
+    let strip = [0u16, 1, 2, 3, 3, 4, 4, 5, 6];
+    let list = index::convert::convert(&strip, PrimitiveType::TriangleStrip,
+                                       PrimitiveType::TrianglesList).unwrap();
+    assert_eq!(list, vec![0, 1, 2, 2, 1, 3, 4, 5, 6]);
+
+    assert!(index::convert::convert(&strip, PrimitiveType::TriangleStrip,
+                                    PrimitiveType::LineStrip).is_err());
+}
+
+#[test]
+fn convert_lines_adjacency() {
+    let strip = [0u16, 1, 2];
+    let adjacency = index::convert::convert(&strip, PrimitiveType::LineStrip,
+                                            PrimitiveType::LinesListAdjacency).unwrap();
+    assert_eq!(adjacency, vec![0, 0, 1, 2, 0, 1, 2, 2]);
+}
+
+#[test]
+fn grid_triangles_adjacency() {
+    let indices: Vec<u16> = index::convert::grid_triangles(2, 1);
+    assert_eq!(indices, vec![0, 1, 4, 0, 4, 3, 1, 2, 5, 1, 5, 4]);
+
+    let adjacency = index::convert::triangles_adjacency(&indices);
+    assert_eq!(adjacency.len(), indices.len() * 2);
+
+    // shared edges use the vertex of the other triangle, border edges use the triangle itself
+    assert_eq!(&adjacency[0 .. 6], &[0, 4, 1, 5, 4, 3][..]);
+    assert_eq!(&adjacency[6 .. 12], &[0, 1, 4, 0, 3, 4][..]);
+}
@@ -0,0 +1,185 @@
+#![cfg(feature = "mesh")]
+
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::mesh::TextureSource;
+
+use std::path::Path;
+
+mod support;
+
+#[derive(Copy, Clone)]
+struct Vertex {
+    position: [f32; 3],
+    normal: [f32; 3],
+    tex_coords: [f32; 2],
+}
+
+implement_vertex!(Vertex, position, normal, tex_coords);
+
+fn draw_red_fullscreen(display: &glium::Display, mesh: &glium::mesh::Mesh<Vertex>) {
+    let (vb, ib) = mesh.build_buffers(display);
+
+    let program = program!(display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+}
+
+#[test]
+fn load_obj() {
+    let display = support::build_display();
+
+    let scene = glium::mesh::load_obj::<Vertex, _>("tests/fixture/quad.obj").unwrap();
+
+    assert_eq!(scene.materials.len(), 1);
+    assert_eq!(scene.materials[0].name, "red");
+    assert_eq!(scene.materials[0].diffuse_color, [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(scene.materials[0].diffuse_texture,
+               Some(TextureSource::File(Path::new("tests/fixture/opengl.png").to_path_buf())));
+
+    assert_eq!(scene.meshes.len(), 1);
+    let mesh = &scene.meshes[0];
+    assert_eq!(mesh.name, Some("quad".to_string()));
+    assert_eq!(mesh.material, Some(0));
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+    assert_eq!(mesh.vertices[2].tex_coords, [1.0, 1.0]);
+    assert_eq!(mesh.vertices[2].normal, [0.0, 0.0, 1.0]);
+
+    draw_red_fullscreen(&display, mesh);
+    display.assert_no_error(None);
+}
+
+#[test]
+fn load_gltf() {
+    let display = support::build_display();
+
+    let scene = glium::mesh::load_gltf::<Vertex, _>("tests/fixture/quad.gltf").unwrap();
+
+    assert_eq!(scene.materials.len(), 1);
+    assert_eq!(scene.materials[0].diffuse_color, [1.0, 0.0, 0.0, 1.0]);
+
+    assert_eq!(scene.meshes.len(), 1);
+    let mesh = &scene.meshes[0];
+    assert_eq!(mesh.material, Some(0));
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.indices.len(), 6);
+
+    // the file doesn't contain normals
+    assert_eq!(mesh.vertices[0].normal, [0.0, 0.0, 0.0]);
+
+    draw_red_fullscreen(&display, mesh);
+    display.assert_no_error(None);
+}
+
+#[test]
+fn load_glb() {
+    let display = support::build_display();
+
+    let scene = glium::mesh::load_gltf::<Vertex, _>("tests/fixture/quad.glb").unwrap();
+
+    assert_eq!(scene.materials.len(), 1);
+    assert_eq!(scene.meshes.len(), 1);
+    let mesh = &scene.meshes[0];
+    assert_eq!(mesh.material, Some(0));
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.indices.len(), 6);
+
+    draw_red_fullscreen(&display, mesh);
+    display.assert_no_error(None);
+}
+
+#[test]
+fn load_gltf_external_buffer() {
+    let display = support::build_display();
+
+    let scene = glium::mesh::load_gltf::<Vertex, _>("tests/fixture/quad_external.gltf").unwrap();
+
+    assert_eq!(scene.meshes.len(), 1);
+    let mesh = &scene.meshes[0];
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.indices.len(), 6);
+
+    draw_red_fullscreen(&display, mesh);
+    display.assert_no_error(None);
+}
+
+#[test]
+fn load_gltf_percent_encoded_uri() {
+    let scene = glium::mesh::load_gltf::<Vertex, _>("tests/fixture/quad_escaped_uri.gltf")
+                    .unwrap();
+
+    assert_eq!(scene.meshes.len(), 1);
+    assert_eq!(scene.meshes[0].vertices.len(), 4);
+}
+
+#[test]
+fn gltf_deeply_nested_document() {
+    use std::fs::File;
+    use std::io::Write;
+    use std::iter;
+
+    let path = std::env::temp_dir().join("glium-deeply-nested.gltf");
+    let document: String = iter::repeat('[').take(100000)
+                                .chain(iter::repeat(']').take(100000)).collect();
+    File::create(&path).unwrap().write_all(document.as_bytes()).unwrap();
+
+    match glium::mesh::load_gltf::<Vertex, _>(&path) {
+        Err(glium::mesh::MeshError::ParseError { .. }) => (),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn gltf_accessor_out_of_range() {
+    match glium::mesh::load_gltf::<Vertex, _>("tests/fixture/out_of_range.gltf") {
+        Err(glium::mesh::MeshError::ParseError { .. }) => (),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn unknown_attribute() {
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 3],
+        foo: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position, foo);
+
+    match glium::mesh::load_obj::<Vertex, _>("tests/fixture/quad.obj") {
+        Err(glium::mesh::MeshError::UnknownAttribute(ref name)) if name == "foo" => (),
+        _ => panic!()
+    }
+}
@@ -256,3 +256,119 @@ fn conditional_render_simultaneous_query() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn samples_passed_to_buffer() {
+    let display = support::build_display();
+
+    let query = match glium::draw_parameters::SamplesPassedQuery::new_if_supported(&display) {
+        Some(q) => q,
+        None => return
+    };
+
+    let buffer = match glium::buffer::BufferView::new(&display, &[5u32, 5, 5, 5],
+                                                      glium::buffer::BufferType::QueryBuffer,
+                                                      glium::buffer::BufferMode::Default)
+    {
+        Ok(b) => b,
+        Err(glium::buffer::BufferCreationError::BufferTypeNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    {
+        let params = glium::DrawParameters::new(&display)
+                        .with_samples_passed_query(&query);
+
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+               .unwrap();
+    }
+
+    query.write_u32_to_buffer(buffer.slice(2 .. 3).unwrap()).unwrap();
+
+    let data = match buffer.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data, vec![5, 5, 1024 * 1024, 5]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn unused_query_to_buffer() {
+    let display = support::build_display();
+
+    let query = match glium::draw_parameters::SamplesPassedQuery::new_if_supported(&display) {
+        Some(q) => q,
+        None => return
+    };
+
+    let buffer = match glium::buffer::BufferView::<u32>::empty(&display,
+                                                               glium::buffer::BufferType::QueryBuffer,
+                                                               2, glium::buffer::BufferMode::Default)
+    {
+        Ok(b) => b,
+        Err(glium::buffer::BufferCreationError::BufferTypeNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    match query.write_u32_to_buffer(buffer.as_slice()) {
+        Err(glium::draw_parameters::ToBufferError::WrongLength) => (),
+        _ => panic!()
+    };
+
+    match query.write_u32_to_buffer(buffer.slice(0 .. 1).unwrap()) {
+        Err(glium::draw_parameters::ToBufferError::QueryNotUsed) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn time_elapsed_to_buffer_u64() {
+    let display = support::build_display();
+
+    let query = match glium::draw_parameters::TimeElapsedQuery::new_if_supported(&display) {
+        Some(q) => q,
+        None => return
+    };
+
+    let buffer = match glium::buffer::BufferView::<u64>::empty(&display,
+                                                               glium::buffer::BufferType::QueryBuffer,
+                                                               1, glium::buffer::BufferMode::Default)
+    {
+        Ok(b) => b,
+        Err(glium::buffer::BufferCreationError::BufferTypeNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+
+    {
+        let params = glium::DrawParameters::new(&display)
+                        .with_time_elapsed_query(&query);
+
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+               .unwrap();
+    }
+
+    query.write_u64_to_buffer(buffer.as_slice()).unwrap();
+
+    let data = match buffer.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert!(data[0] > 0);
+
+    display.assert_no_error(None);
+}
@@ -505,3 +505,100 @@ fn ssbos() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn reloadable_program() {
+    use std::fs::File;
+    use std::io::Write;
+
+    let display = support::build_display();
+
+    let dir = std::env::temp_dir();
+    let vertex_path = dir.join("glium-reloadable-program-test.vert");
+    let fragment_path = dir.join("glium-reloadable-program-test.frag");
+
+    File::create(&vertex_path).unwrap().write_all(b"
+        #version 110
+
+        attribute vec2 position;
+
+        void main() {
+            gl_Position = vec4(position, 0.0, 1.0);
+        }
+    ").unwrap();
+
+    File::create(&fragment_path).unwrap().write_all(b"
+        #version 110
+
+        void main() {
+            gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+        }
+    ").unwrap();
+
+    let mut program = glium::program::ReloadableProgram::new(&display,
+        glium::program::SourcePaths {
+            vertex_shader: vertex_path.clone(),
+            tessellation_control_shader: None,
+            tessellation_evaluation_shader: None,
+            geometry_shader: None,
+            fragment_shader: fragment_path.clone(),
+            transform_feedback_varyings: None,
+        }).unwrap();
+
+    assert!(!program.poll());
+    assert!(program.last_error().is_none());
+
+    // a broken edit keeps the previous program
+    File::create(&fragment_path).unwrap().write_all(b"invalid glsl code").unwrap();
+    assert!(!program.poll());
+    assert!(program.last_error().is_some());
+    let _: &glium::Program = &*program;
+
+    // fixing the source swaps in the new program
+    File::create(&fragment_path).unwrap().write_all(b"
+        #version 110
+
+        void main() {
+            gl_FragColor = vec4(0.0, 1.0, 0.0, 1.0);
+        }
+    ").unwrap();
+    assert!(program.poll());
+    assert!(program.last_error().is_none());
+
+    // an edit of the same length right after the previous one is detected as well, even if the
+    // modification time didn't change
+    File::create(&fragment_path).unwrap().write_all(b"
+        #version 110
+
+        void main() {
+            gl_FragColor = vec4(0.0, 0.0, 1.0, 1.0);
+        }
+    ").unwrap();
+    assert!(program.poll());
+
+    // rewriting the same content doesn't rebuild the program
+    File::create(&fragment_path).unwrap().write_all(b"
+        #version 110
+
+        void main() {
+            gl_FragColor = vec4(0.0, 0.0, 1.0, 1.0);
+        }
+    ").unwrap();
+    assert!(!program.poll());
+
+    // a file that is missing while an editor saves it, then comes back with the same content
+    std::fs::remove_file(&fragment_path).unwrap();
+    assert!(!program.poll());
+    assert!(program.last_error().is_some());
+    File::create(&fragment_path).unwrap().write_all(b"
+        #version 110
+
+        void main() {
+            gl_FragColor = vec4(0.0, 0.0, 1.0, 1.0);
+        }
+    ").unwrap();
+    assert!(!program.poll());
+    assert!(program.last_error().is_none());
+
+    display.assert_no_error(None);
+}
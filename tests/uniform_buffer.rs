@@ -198,7 +198,7 @@ fn block_wrong_type() {
     target.clear_color(0.0, 0.0, 0.0, 0.0);
 
     match target.draw(&vb, &ib, &program, &uniforms, &Default::default()) {
-        Err(glium::DrawError::UniformBlockLayoutMismatch { ref name })
+        Err(glium::DrawError::UniformBlockLayoutMismatch { ref name, .. })
             if name == &"MyBlock" => (),
         a => panic!("{:?}", a)
     }
@@ -206,6 +206,153 @@ fn block_wrong_type() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn block_struct() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                vec3 color;
+                float alpha;
+                vec4 weights[2];
+            };
+
+            void main() {
+                gl_FragColor = vec4(color, alpha) * weights[0] * weights[1];
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    #[derive(Copy, Clone)]
+    #[repr(C)]
+    struct MyBlock {
+        color: [f32; 3],
+        alpha: f32,
+        weights: [[f32; 4]; 2],
+    }
+
+    implement_uniform_block!(MyBlock, color, alpha, weights);
+
+    let buffer = match glium::uniforms::UniformBuffer::new_if_supported(&display, MyBlock {
+        color: [1.0, 1.0, 0.0],
+        alpha: 1.0,
+        weights: [[1.0, 1.0, 1.0, 1.0], [1.0, 1.0, 1.0, 1.0]],
+    }) {
+        None => return,
+        Some(b) => b
+    };
+
+    let uniforms = uniform!{
+        MyBlock: &buffer
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn block_struct_wrong_stride() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                float values[2];
+            };
+
+            void main() {
+                gl_FragColor = vec4(values[0], values[1], 0.0, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    // each element of a `float` array occupies 16 bytes with std140
+    #[derive(Copy, Clone)]
+    #[repr(C)]
+    struct MyBlock {
+        values: [f32; 2],
+    }
+
+    implement_uniform_block!(MyBlock, values);
+
+    let buffer = match glium::uniforms::UniformBuffer::new_if_supported(&display,
+                                                                        MyBlock { values: [0.0, 1.0] })
+    {
+        None => return,
+        Some(b) => b
+    };
+
+    let uniforms = uniform!{
+        MyBlock: &buffer
+    };
+
+    let mut target = display.draw();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match target.draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformBlockLayoutMismatch {
+            err: glium::uniforms::LayoutMismatchError::StrideMismatch { expected: 16,
+                                                                       obtained: 4, .. },
+            ..
+        }) => (),
+        a => panic!("{:?}", a)
+    }
+
+    target.finish().unwrap();
+    display.assert_no_error(None);
+}
+
 #[test]
 fn buffer_write() {
     let display = support::build_display();
@@ -320,3 +467,68 @@ fn empty_uniform_buffer() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn block_from_pool() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                vec3 color;
+            };
+
+            void main() {
+                gl_FragColor = vec4(color, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let mut pool = match glium::buffer::UniformBufferPool::new(&display, 4,
+                                                    glium::buffer::BufferMode::Default)
+    {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    // the second block is at a non-zero offset
+    let _first = pool.alloc(&[(0.0f32, 0.0f32, 1.0f32)]).unwrap();
+    let second = pool.alloc(&[(1.0f32, 1.0f32, 0.0f32)]).unwrap();
+
+    let uniforms = uniform!{
+        MyBlock: &second
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
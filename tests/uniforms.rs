@@ -171,3 +171,215 @@ fn uniform_wrong_type() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn uniforms_storage_array() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 colors[3];
+
+            void main() {
+                gl_FragColor = colors[0] + colors[1] + colors[2];
+            }
+        ",
+        None).unwrap();
+
+    let colors = [[0.5, 0.0, 0.0, 0.0f32], [0.5, 0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 1.0]];
+    let uniforms = glium::uniforms::UniformsStorage::new("colors", &colors[..]);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniforms_storage_ivec_array() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform ivec2 values[2];
+
+            void main() {
+                gl_FragColor = vec4(float(values[0].x), float(values[1].x),
+                                    float(values[0].y), float(values[1].y));
+            }
+        ",
+        None).unwrap();
+
+    let values = [[1, 0i32], [0, 1]];
+    let uniforms = glium::uniforms::UniformsStorage::new("values", &values[..]);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniforms_storage_array_of_structs() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            struct Light {
+                vec4 color;
+                float intensity;
+            };
+
+            uniform Light lights[2];
+
+            void main() {
+                gl_FragColor = lights[0].color * lights[0].intensity +
+                               lights[1].color * lights[1].intensity;
+            }
+        ",
+        None).unwrap();
+
+    let uniforms = glium::uniforms::UniformsStorage::new("lights[0].color",
+                                                         [1.0, 0.0, 0.0, 0.0f32]);
+    let uniforms = uniforms.add("lights[0].intensity", 1.0f32);
+    let uniforms = uniforms.add("lights[1].color", [0.0, 0.0, 0.0, 0.5f32]);
+    let uniforms = uniforms.add("lights[1].intensity", 2.0f32);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniform_array_too_long() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform float values[2];
+
+            void main() {
+                gl_FragColor = vec4(values[0], values[1], 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let values = [0.0, 0.5, 1.0f32];
+    let uniforms = glium::uniforms::UniformsStorage::new("values", &values[..]);
+
+    let mut target = display.draw();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    match target.draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformArrayTooLong { len: 3, max_len: 2, .. }) => (),
+        a => panic!("{:?}", a)
+    };
+    target.finish().unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniforms_storage_int_and_bool_vectors() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform ivec2 red_alpha;
+            uniform bvec2 green_blue;
+
+            void main() {
+                gl_FragColor = vec4(float(red_alpha.x), green_blue.x ? 1.0 : 0.0,
+                                    green_blue.y ? 1.0 : 0.0, float(red_alpha.y));
+            }
+        ",
+        None).unwrap();
+
+    let uniforms = glium::uniforms::UniformsStorage::new("red_alpha", [1, 1i32]);
+    let uniforms = uniforms.add("green_blue", [false, false]);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
@@ -652,3 +652,504 @@ fn transform_feedback() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn transform_feedback_multiple_buffers() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex1 {
+        output_val1: (f32, f32),
+    }
+
+    implement_vertex!(Vertex1, output_val1);
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex2 {
+        output_val2: f32,
+    }
+
+    implement_vertex!(Vertex2, output_val2);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            varying vec2 output_val1;
+            varying float output_val2;
+
+            void main() {
+                output_val1 = position;
+                output_val2 = position.x + position.y;
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_val1".to_string(), "output_val2".to_string()],
+            glium::program::TransformFeedbackMode::Separate
+        )),
+    };
+
+    let program = match glium::Program::new(&display, source) {
+        Ok(p) => p,
+        Err(glium::program::ProgramCreationError::TransformFeedbackNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let mut out_buffer1: glium::VertexBuffer<Vertex1> = glium::VertexBuffer::empty(&display, 6);
+    let mut out_buffer2: glium::VertexBuffer<Vertex2> = glium::VertexBuffer::empty(&display, 6);
+
+    {
+        // a single buffer is not enough for this program
+        match glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                           &mut out_buffer1)
+        {
+            Err(glium::vertex::TransformFeedbackSessionCreationError::WrongBuffersCount) => (),
+            _ => panic!()
+        }
+    }
+
+    {
+        let outputs = vec![
+            glium::vertex::TransformFeedbackOutput::new(&mut out_buffer1),
+            glium::vertex::TransformFeedbackOutput::new(&mut out_buffer2),
+        ];
+
+        let session = glium::vertex::TransformFeedbackSession::with_outputs(&display, &program,
+                                                                            outputs).unwrap();
+
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            .. Default::default()
+        };
+
+        let texture = support::build_renderable_texture(&display);
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &params).unwrap();
+    }
+
+    let result1 = match out_buffer1.read_if_supported() {
+        Some(r) => r,
+        None => return
+    };
+
+    let result2 = out_buffer2.read_if_supported().unwrap();
+
+    assert_eq!(result1[0].output_val1, (-1.0, 1.0));
+    assert_eq!(result1[5].output_val1, (1.0, -1.0));
+    assert_eq!(result2[0].output_val2, 0.0);
+    assert_eq!(result2[1].output_val2, 2.0);
+    assert_eq!(result2[2].output_val2, -2.0);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn transform_feedback_pause() {
+    let display = support::build_display();
+
+    if !glium::vertex::is_transform_feedback_pause_supported(&display) {
+        return;
+    }
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex {
+        output_val: (f32, f32),
+    }
+
+    implement_vertex!(Vertex, output_val);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+
+        vertex_shader: "
+            #version 110
+
+            uniform float scale;
+            attribute vec2 position;
+
+            varying vec2 output_val;
+
+            void main() {
+                output_val = position * scale;
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_val".to_string()],
+            glium::program::TransformFeedbackMode::Interleaved
+        )),
+    };
+
+    let program = match glium::Program::new(&display, source) {
+        Ok(p) => p,
+        Err(glium::program::ProgramCreationError::TransformFeedbackNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let mut out_buffer: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 12);
+
+    {
+        let session = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                                   &mut out_buffer).unwrap();
+
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            .. Default::default()
+        };
+
+        let texture = support::build_renderable_texture(&display);
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{ scale: 1.0f32 },
+                                  &params).unwrap();
+
+        // this draw doesn't write anything in the buffer
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{ scale: 3.0f32 },
+                                  &Default::default()).unwrap();
+
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{ scale: 2.0f32 },
+                                  &params).unwrap();
+    }
+
+    let result = match out_buffer.read_if_supported() {
+        Some(r) => r,
+        None => return
+    };
+
+    assert_eq!(result[0].output_val, (-1.0, 1.0));
+    assert_eq!(result[5].output_val, (1.0, -1.0));
+    assert_eq!(result[6].output_val, (-2.0, 2.0));
+    assert_eq!(result[11].output_val, (2.0, -2.0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn transform_feedback_draw_written_vertices() {
+    let display = support::build_display();
+
+    if !glium::vertex::is_draw_transform_feedback_supported(&display) {
+        return;
+    }
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex {
+        output_val: (f32, f32),
+    }
+
+    implement_vertex!(Vertex, output_val);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            varying vec2 output_val;
+
+            void main() {
+                output_val = position;
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_val".to_string()],
+            glium::program::TransformFeedbackMode::Interleaved
+        )),
+    };
+
+    let program = glium::Program::new(&display, source).unwrap();
+
+    let draw_program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 output_val;
+
+                void main() {
+                    gl_Position = vec4(output_val, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    // the buffer is larger than the number of vertices that are written
+    let mut out_buffer: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 64);
+    let texture = support::build_renderable_texture(&display);
+
+    let session = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                               &mut out_buffer).unwrap();
+
+    {
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &params).unwrap();
+    }
+
+    let vertices = session.finish().unwrap();
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vertices,
+                              &glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
+                              &draw_program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn transform_feedback_two_sessions() {
+    let display = support::build_display();
+
+    if !glium::vertex::is_transform_feedback_pause_supported(&display) {
+        return;
+    }
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex {
+        output_val: (f32, f32),
+    }
+
+    implement_vertex!(Vertex, output_val);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+
+        vertex_shader: "
+            #version 110
+
+            uniform float scale;
+            attribute vec2 position;
+
+            varying vec2 output_val;
+
+            void main() {
+                output_val = position * scale;
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_val".to_string()],
+            glium::program::TransformFeedbackMode::Interleaved
+        )),
+    };
+
+    let program = match glium::Program::new(&display, source) {
+        Ok(p) => p,
+        Err(glium::program::ProgramCreationError::TransformFeedbackNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let mut out_buffer1: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 12);
+    let mut out_buffer2: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 12);
+
+    {
+        let session1 = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                                    &mut out_buffer1).unwrap();
+        let session2 = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                                    &mut out_buffer2).unwrap();
+
+        let params1 = glium::DrawParameters {
+            transform_feedback: Some(&session1),
+            .. Default::default()
+        };
+
+        let params2 = glium::DrawParameters {
+            transform_feedback: Some(&session2),
+            .. Default::default()
+        };
+
+        let texture = support::build_renderable_texture(&display);
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{ scale: 1.0f32 },
+                                  &params1).unwrap();
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{ scale: 3.0f32 },
+                                  &params2).unwrap();
+
+        // the first session continues after the vertices it has already written
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{ scale: 2.0f32 },
+                                  &params1).unwrap();
+    }
+
+    let result1 = match out_buffer1.read_if_supported() {
+        Some(r) => r,
+        None => return
+    };
+
+    assert_eq!(result1[0].output_val, (-1.0, 1.0));
+    assert_eq!(result1[5].output_val, (1.0, -1.0));
+    assert_eq!(result1[6].output_val, (-2.0, 2.0));
+    assert_eq!(result1[11].output_val, (2.0, -2.0));
+
+    let result2 = out_buffer2.read_if_supported().unwrap();
+    assert_eq!(result2[0].output_val, (-3.0, 3.0));
+    assert_eq!(result2[5].output_val, (3.0, -3.0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn transform_feedback_vertices_with_indices() {
+    let display = support::build_display();
+
+    if !glium::vertex::is_draw_transform_feedback_supported(&display) {
+        return;
+    }
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex {
+        output_val: (f32, f32),
+    }
+
+    implement_vertex!(Vertex, output_val);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            varying vec2 output_val;
+
+            void main() {
+                output_val = position;
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_val".to_string()],
+            glium::program::TransformFeedbackMode::Interleaved
+        )),
+    };
+
+    let program = glium::Program::new(&display, source).unwrap();
+
+    let draw_program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 output_val;
+
+                void main() {
+                    gl_Position = vec4(output_val, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let mut out_buffer: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 6);
+    let texture = support::build_renderable_texture(&display);
+
+    let session = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                               &mut out_buffer).unwrap();
+
+    {
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &params).unwrap();
+    }
+
+    let vertices = session.finish().unwrap();
+
+    match texture.as_surface().draw(&vertices, &ib, &draw_program, &uniform!{},
+                                    &Default::default())
+    {
+        Err(glium::DrawError::TransformFeedbackVerticesWithIndices) => (),
+        a => panic!("{:?}", a)
+    }
+
+    display.assert_no_error(None);
+}
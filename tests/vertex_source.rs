@@ -649,9 +649,326 @@ fn per_instance_length_mismatch() {
     match display.draw().draw((&buffer1, buffer2, buffer3), &index_buffer, &program, &uniform!{},
                               &Default::default())
     {
-        Err(glium::DrawError::InstancesCountMismatch) => (),
+        Err(glium::DrawError::InstancesCountMismatch { .. }) => (),
         a => panic!("{:?}", a)
     }
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn instancing_with_divisor() {
+    let display = support::build_display();
+
+    let buffer1 = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: [f32; 2],
+        }
+
+        implement_vertex!(Vertex, position);
+
+        glium::VertexBuffer::new(&display,
+            vec![
+                Vertex { position: [-1.0,  1.0] },
+                Vertex { position: [ 1.0,  1.0] },
+                Vertex { position: [-1.0, -1.0] },
+                Vertex { position: [ 1.0, -1.0] },
+            ]
+        )
+    };
+
+    let buffer2 = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            color: [f32; 3],
+        }
+
+        implement_vertex!(Vertex, color);
+
+        glium::vertex::VertexBuffer::new(&display,
+            vec![
+                Vertex { color: [0.0, 0.0, 1.0] },
+                Vertex { color: [1.0, 0.0, 0.0] },
+            ]
+        )
+    };
+
+    // each element is used by two instances, so four instances are drawn
+    let buffer2 = match buffer2.per_instance_with_divisor_if_supported(2) {
+        Some(b) => b,
+        None => return
+    };
+
+    let index_buffer = glium::IndexBuffer::new(&display, PrimitiveType::TriangleStrip,
+                                               vec![0u16, 1, 2, 3]);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 330
+
+            in vec2 position;
+            in vec3 color;
+
+            out vec3 v_color;
+            flat out int instance;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                v_color = color;
+                instance = gl_InstanceID;
+            }
+        ",
+        "
+            #version 330
+            in vec3 v_color;
+            flat in int instance;
+
+            void main() {
+                if (instance != 3) {
+                    discard;
+                }
+
+                gl_FragColor = vec4(v_color, 1.0);
+            }
+        ",
+        None) {
+        Ok(p) => p,
+        _ => return
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw((&buffer1, buffer2), &index_buffer, &program, &uniform!{},
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn base_vertex() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    // the first four vertices are outside of the viewport
+    let vertex_buffer = glium::VertexBuffer::new(&display,
+        vec![
+            Vertex { position: [ 2.0,  3.0] },
+            Vertex { position: [ 3.0,  3.0] },
+            Vertex { position: [ 2.0,  2.0] },
+            Vertex { position: [ 3.0,  2.0] },
+            Vertex { position: [-1.0,  1.0] },
+            Vertex { position: [ 1.0,  1.0] },
+            Vertex { position: [-1.0, -1.0] },
+            Vertex { position: [ 1.0, -1.0] },
+        ]
+    );
+
+    let index_buffer = glium::IndexBuffer::new(&display, PrimitiveType::TriangleStrip,
+                                               vec![0u16, 1, 2, 3]);
+
+    let params = match glium::DrawParameters::new(&display).with_base_vertex_if_supported(4) {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vertex_buffer, &index_buffer, &program, &uniform!{},
+                              &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn base_instance() {
+    let display = support::build_display();
+
+    let buffer1 = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: [f32; 2],
+        }
+
+        implement_vertex!(Vertex, position);
+
+        glium::VertexBuffer::new(&display,
+            vec![
+                Vertex { position: [-1.0,  1.0] },
+                Vertex { position: [ 1.0,  1.0] },
+                Vertex { position: [-1.0, -1.0] },
+                Vertex { position: [ 1.0, -1.0] },
+            ]
+        )
+    };
+
+    let buffer2 = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            color: [f32; 3],
+        }
+
+        implement_vertex!(Vertex, color);
+
+        glium::vertex::VertexBuffer::new(&display,
+            vec![
+                Vertex { color: [0.0, 0.0, 1.0] },
+                Vertex { color: [0.0, 0.0, 1.0] },
+                Vertex { color: [0.0, 0.0, 1.0] },
+                Vertex { color: [1.0, 0.0, 0.0] },
+            ]
+        )
+    };
+
+    let buffer2 = match buffer2.per_instance_if_supported() {
+        Some(b) => b,
+        None => return
+    };
+
+    // only the last instance is drawn
+    let params = match glium::DrawParameters::new(&display).with_base_instance_if_supported(3) {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+                attribute vec3 color;
+
+                varying vec3 v_color;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                    v_color = color;
+                }
+            ",
+            fragment: "
+                #version 110
+
+                varying vec3 v_color;
+
+                void main() {
+                    gl_FragColor = vec4(v_color, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw((&buffer1, buffer2),
+                              &glium::index::NoIndices(PrimitiveType::TriangleStrip),
+                              &program, &uniform!{}, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn base_vertex_out_of_range_without_indices() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let vertex_buffer = glium::VertexBuffer::new(&display,
+        vec![
+            Vertex { position: [-1.0,  1.0] },
+            Vertex { position: [ 1.0,  1.0] },
+            Vertex { position: [-1.0, -1.0] },
+            Vertex { position: [ 1.0, -1.0] },
+        ]
+    );
+
+    let params = match glium::DrawParameters::new(&display).with_base_vertex_if_supported(5) {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let mut target = display.draw();
+    match target.draw(&vertex_buffer, &glium::index::NoIndices(PrimitiveType::TriangleStrip),
+                      &program, &uniform!{}, &params)
+    {
+        Err(glium::DrawError::BaseVertexOutOfRange { base_vertex: 5, vertices_count: 4 }) => (),
+        a => panic!("{:?}", a)
+    }
+    target.finish().unwrap();
+
+    display.assert_no_error(None);
+}